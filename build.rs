@@ -2,15 +2,57 @@ const COMMANDS: &[&str] = &[
   "get_network_state",
   "list_wifi_networks",
   "rescan_wifi",
+  "start_wifi_scanner",
+  "stop_wifi_scanner",
   "connect_to_wifi",
   "disconnect_from_wifi",
   "get_saved_wifi_networks",
   "delete_wifi_connection",
+  "get_mac_address_policy",
+  "set_mac_address_policy",
+  "list_devices",
+  "list_active_connections",
+  "get_ip_configuration",
+  "get_ip_settings",
+  "update_ip_settings",
+  "disconnect_device",
+  "set_device_managed",
+  "set_device_autoconnect",
+  "configure_wired_8021x",
+  "list_virtual_interfaces",
+  "create_virtual_interface",
+  "update_virtual_interface",
+  "delete_virtual_interface",
+  "list_modems",
+  "get_modem_status",
+  "create_mobile_broadband_connection",
+  "list_bluetooth_devices",
+  "connect_bluetooth_tether",
+  "get_proxy_settings",
+  "set_proxy_settings",
+  "get_effective_proxy",
+  "set_connection_metered",
+  "get_connection_priority",
+  "set_connection_priority",
+  "reorder_preferred_networks",
+  "get_default_route_order",
+  "get_wired_link_info",
   "toggle_network_state",
   "get_wireless_enabled",
   "set_wireless_enabled",
   "is_wireless_available",
   "get_network_stats",
+  "start_bandwidth_sampler",
+  "stop_bandwidth_sampler",
+  "get_bandwidth_history",
+  "get_interface_statistics",
+  "check_connectivity",
+  "set_connectivity_probe",
+  "get_data_usage",
+  "set_data_quota",
+  "ping",
+  "start_ping_monitor",
+  "stop_ping_monitor",
   "get_network_interfaces",
   "list_vpn_profiles",
   "get_vpn_status",
@@ -34,6 +34,46 @@ describe('network-manager guest-js smoke', () => {
     });
   });
 
+  it('maps list filters to native query', async () => {
+    invokeMock.mockResolvedValueOnce([]);
+    const mod = await import('./index');
+
+    await mod.listWifiNetworks({
+      minSignal: 30,
+      securityTypes: [mod.WiFiSecurityType.WPA2_PSK],
+      includeHidden: false,
+      sortBy: 'ssid',
+      limit: 5,
+      known: 'known-only',
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|list_wifi_networks', {
+      force_refresh: undefined,
+      ttl_ms: undefined,
+      query: {
+        min_signal: 30,
+        security_types: ['wpa2-psk'],
+        include_hidden: false,
+        dedupe_by_ssid: undefined,
+        sort_by: 'ssid',
+        limit: 5,
+        known: 'known-only',
+      },
+    });
+  });
+
+  it('calls background scanner commands', async () => {
+    const mod = await import('./index');
+
+    await mod.startWifiScanner({ panel_open: false, idle_interval_ms: 0 });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|start_wifi_scanner', {
+      config: { panel_open: false, idle_interval_ms: 0 },
+    });
+
+    await mod.stopWifiScanner();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|stop_wifi_scanner', undefined);
+  });
+
   it('maps connect input to native payload', async () => {
     const mod = await import('./index');
 
@@ -46,13 +86,125 @@ describe('network-manager guest-js smoke', () => {
     expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|connect_to_wifi', {
       config: {
         ssid: 'MyWiFi',
+        ssid_hex: undefined,
         password: 'secret',
         security_type: 'wpa2-psk',
         username: undefined,
+        mac_address_policy: undefined,
       },
     });
   });
 
+  it('passes raw ssid bytes through connect and delete', async () => {
+    const mod = await import('./index');
+
+    await mod.connectToWifi({
+      ssid: 'Café',
+      ssidHex: '436166e9',
+      password: 'secret',
+      securityType: mod.WiFiSecurityType.WPA2_PSK,
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|connect_to_wifi', {
+      config: {
+        ssid: 'Café',
+        ssid_hex: '436166e9',
+        password: 'secret',
+        security_type: 'wpa2-psk',
+        username: undefined,
+        mac_address_policy: undefined,
+      },
+    });
+
+    await mod.deleteWifiConnection('Café', '436166e9');
+
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|delete_wifi_connection', {
+      ssid: 'Café',
+      ssid_hex: '436166e9',
+    });
+  });
+
+  it('calls mac address policy commands', async () => {
+    invokeMock.mockResolvedValueOnce('stable');
+    const mod = await import('./index');
+
+    const policy = await mod.getMacAddressPolicy('wifi-uuid-1');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_mac_address_policy', {
+      uuid: 'wifi-uuid-1',
+    });
+    expect(policy).toBe('stable');
+
+    await mod.setMacAddressPolicy('wifi-uuid-1', { explicit: '02:00:5E:10:00:01' });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_mac_address_policy', {
+      uuid: 'wifi-uuid-1',
+      policy: { explicit: '02:00:5E:10:00:01' },
+    });
+  });
+
+  it('calls list devices command', async () => {
+    invokeMock.mockResolvedValueOnce([]);
+    const mod = await import('./index');
+
+    await mod.listDevices();
+
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|list_devices', undefined);
+  });
+
+  it('calls list active connections command', async () => {
+    invokeMock.mockResolvedValueOnce([]);
+    const mod = await import('./index');
+
+    await mod.listActiveConnections();
+
+    expect(invokeMock).toHaveBeenCalledWith(
+      'plugin:network-manager|list_active_connections',
+      undefined,
+    );
+  });
+
+  it('calls get ip configuration command', async () => {
+    const mod = await import('./index');
+
+    await mod.getIpConfiguration({ interface: 'wlan0' });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_ip_configuration', {
+      interface: 'wlan0',
+      uuid: undefined,
+    });
+
+    invokeMock.mockRejectedValueOnce(new Error('Device not found: eth9'));
+    await expect(mod.getIpConfiguration({ interface: 'eth9' })).rejects.toMatchObject({
+      code: mod.NetworkManagerErrorCode.DEVICE_NOT_FOUND,
+    });
+  });
+
+  it('calls ip settings commands', async () => {
+    const mod = await import('./index');
+    const settings = {
+      ipv4: {
+        method: 'manual' as const,
+        addresses: [{ address: '192.168.1.20', prefix: 24 }],
+        gateway: '192.168.1.1',
+        dns: ['1.1.1.1'],
+      },
+    };
+
+    await mod.updateIpSettings('eth-uuid-1', settings, true);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|update_ip_settings', {
+      uuid: 'eth-uuid-1',
+      settings,
+      reapply: true,
+    });
+
+    invokeMock.mockRejectedValueOnce(
+      new Error('Invalid connection settings: manual method needs at least one address'),
+    );
+    await expect(
+      mod.updateIpSettings('eth-uuid-1', { ipv4: { method: 'manual' } }),
+    ).rejects.toMatchObject({
+      code: mod.NetworkManagerErrorCode.INVALID_SETTINGS,
+    });
+  });
+
   it('calls disconnect command', async () => {
     const mod = await import('./index');
 
@@ -64,6 +216,272 @@ describe('network-manager guest-js smoke', () => {
     );
   });
 
+  it('calls device control commands', async () => {
+    const mod = await import('./index');
+
+    await mod.disconnectDevice('enp3s0');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|disconnect_device', {
+      interface: 'enp3s0',
+    });
+
+    await mod.setDeviceManaged('enp3s0', false);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_device_managed', {
+      interface: 'enp3s0',
+      managed: false,
+    });
+
+    await mod.setDeviceAutoconnect('enp3s0', true);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_device_autoconnect', {
+      interface: 'enp3s0',
+      autoconnect: true,
+    });
+  });
+
+  it('calls configure wired 802.1x command', async () => {
+    invokeMock.mockResolvedValueOnce('c0ffee00-0000-4000-8000-000000000001');
+    const mod = await import('./index');
+
+    const config = {
+      eap: 'peap' as const,
+      identity: 'alice',
+      password: 'secret',
+      ca_cert_path: '/etc/ssl/corp-ca.pem',
+    };
+    const uuid = await mod.configureWired8021x({ interface: 'enp3s0' }, config);
+
+    expect(uuid).toBe('c0ffee00-0000-4000-8000-000000000001');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|configure_wired_8021x', {
+      interface: 'enp3s0',
+      uuid: undefined,
+      config,
+    });
+  });
+
+  it('calls virtual interface commands', async () => {
+    const mod = await import('./index');
+    const config = {
+      interface_name: 'br0',
+      kind: { bridge: { stp: false, ports: ['eth0', 'eth1'] } },
+    };
+
+    await mod.createVirtualInterface(config);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|create_virtual_interface', {
+      config,
+    });
+
+    await mod.updateVirtualInterface('uuid-br0', config);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|update_virtual_interface', {
+      uuid: 'uuid-br0',
+      config,
+    });
+
+    await mod.deleteVirtualInterface('uuid-br0');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|delete_virtual_interface', {
+      uuid: 'uuid-br0',
+    });
+  });
+
+  it('calls proxy commands', async () => {
+    const mod = await import('./index');
+
+    await mod.getProxySettings('uuid-1');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_proxy_settings', {
+      uuid: 'uuid-1',
+    });
+
+    const settings = { method: 'manual' as const, manual: { http: 'proxy:3128' } };
+    await mod.setProxySettings('uuid-1', settings);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_proxy_settings', {
+      uuid: 'uuid-1',
+      settings,
+    });
+
+    await mod.getEffectiveProxy();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_effective_proxy', undefined);
+  });
+
+  it('measures a chosen interface or connection', async () => {
+    const mod = await import('./index');
+
+    await mod.getNetworkStats();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_network_stats', {
+      interface: undefined,
+      uuid: undefined,
+    });
+
+    await mod.getNetworkStats({ uuid: 'vpn-uuid' });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_network_stats', {
+      interface: undefined,
+      uuid: 'vpn-uuid',
+    });
+  });
+
+  it('controls the bandwidth sampler', async () => {
+    const mod = await import('./index');
+
+    await mod.startBandwidthSampler({ interval_ms: 2000, interfaces: ['wlan0'] });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|start_bandwidth_sampler', {
+      config: { interval_ms: 2000, interfaces: ['wlan0'] },
+    });
+
+    await mod.getBandwidthHistory('wlan0', 60);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_bandwidth_history', {
+      interface: 'wlan0',
+      window: 60,
+    });
+
+    await mod.stopBandwidthSampler();
+    expect(invokeMock).toHaveBeenCalledWith(
+      'plugin:network-manager|stop_bandwidth_sampler',
+      undefined,
+    );
+  });
+
+  it('checks connectivity and configures the probe', async () => {
+    const mod = await import('./index');
+
+    await mod.checkConnectivity();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|check_connectivity', undefined);
+
+    await mod.setConnectivityProbe({ url: 'http://example.com/generate_204', expected_status: 204, expected_body: null });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_connectivity_probe', {
+      config: { url: 'http://example.com/generate_204', expected_status: 204, expected_body: null },
+    });
+  });
+
+  it('pings and drives the ping monitor', async () => {
+    const mod = await import('./index');
+
+    await mod.ping({ targets: [{ kind: 'host', host: '1.1.1.1' }], count: 2 });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|ping', {
+      config: { targets: [{ kind: 'host', host: '1.1.1.1' }], count: 2 },
+    });
+
+    await mod.startPingMonitor({ every_ms: 30_000 });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|start_ping_monitor', {
+      config: { every_ms: 30_000 },
+    });
+
+    await mod.stopPingMonitor();
+    expect(invokeMock).toHaveBeenCalledWith(
+      'plugin:network-manager|stop_ping_monitor',
+      undefined,
+    );
+  });
+
+  it('reads data usage and sets quotas', async () => {
+    const mod = await import('./index');
+
+    await mod.getDataUsage({ kind: 'last-days', days: 7 }, 'Home');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_data_usage', {
+      range: { kind: 'last-days', days: 7 },
+      connection: 'Home',
+    });
+
+    await mod.setDataQuota('Home', { limit_bytes: 10_000_000_000, reset_day: 15 });
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_data_quota', {
+      connection: 'Home',
+      quota: { limit_bytes: 10_000_000_000, reset_day: 15 },
+    });
+  });
+
+  it('reads interface statistics', async () => {
+    const mod = await import('./index');
+
+    await mod.getInterfaceStatistics();
+    expect(invokeMock).toHaveBeenCalledWith(
+      'plugin:network-manager|get_interface_statistics',
+      undefined,
+    );
+  });
+
+  it('reads wired link details', async () => {
+    const mod = await import('./index');
+
+    await mod.getWiredLinkInfo('eth0');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_wired_link_info', {
+      interface: 'eth0',
+    });
+
+    await mod.getWiredLinkInfo();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_wired_link_info', {
+      interface: null,
+    });
+  });
+
+  it('calls connection priority commands', async () => {
+    const mod = await import('./index');
+
+    await mod.getConnectionPriority('uuid-1');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_connection_priority', {
+      uuid: 'uuid-1',
+    });
+
+    const priority = { autoconnect_priority: 10, ipv4_route_metric: 50 };
+    await mod.setConnectionPriority('uuid-1', priority, true);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_connection_priority', {
+      uuid: 'uuid-1',
+      priority,
+      reapply: true,
+    });
+
+    await mod.reorderPreferredNetworks(['uuid-2', 'uuid-1']);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|reorder_preferred_networks', {
+      uuids: ['uuid-2', 'uuid-1'],
+    });
+
+    await mod.getDefaultRouteOrder();
+    expect(invokeMock).toHaveBeenCalledWith(
+      'plugin:network-manager|get_default_route_order',
+      undefined,
+    );
+  });
+
+  it('sets whether a connection is metered', async () => {
+    const mod = await import('./index');
+
+    await mod.setConnectionMetered('uuid-1', 'yes');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|set_connection_metered', {
+      uuid: 'uuid-1',
+      metered: 'yes',
+    });
+
+    expect(mod.isMetered('guess-yes')).toBe(true);
+    expect(mod.isMetered('guess-no')).toBe(false);
+  });
+
+  it('calls bluetooth tether commands', async () => {
+    const mod = await import('./index');
+
+    await mod.listBluetoothDevices();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|list_bluetooth_devices', undefined);
+
+    const config = { address: '0C:8E:29:AA:BB:01', tether_type: 'panu' as const };
+    await mod.connectBluetoothTether(config);
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|connect_bluetooth_tether', {
+      config,
+    });
+  });
+
+  it('calls mobile broadband commands', async () => {
+    const mod = await import('./index');
+
+    await mod.listModems();
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|list_modems', undefined);
+
+    await mod.getModemStatus('cdc-wdm0');
+    expect(invokeMock).toHaveBeenCalledWith('plugin:network-manager|get_modem_status', {
+      modem: 'cdc-wdm0',
+    });
+
+    const config = { apn: 'internet', pin: '1234', roaming_allowed: false };
+    await mod.createMobileBroadbandConnection(config);
+    expect(invokeMock).toHaveBeenCalledWith(
+      'plugin:network-manager|create_mobile_broadband_connection',
+      { config },
+    );
+  });
+
   it('returns typed permission error', async () => {
     invokeMock.mockRejectedValueOnce(new Error('Permission denied while scanning'));
     const mod = await import('./index');
@@ -15,6 +15,10 @@ export enum NetworkManagerErrorCode {
   VPN_ACTIVATION_FAILED = 'VPN_ACTIVATION_FAILED',
   VPN_PLUGIN_UNAVAILABLE = 'VPN_PLUGIN_UNAVAILABLE',
   VPN_NOT_ACTIVE = 'VPN_NOT_ACTIVE',
+  INVALID_SSID = 'INVALID_SSID',
+  CONNECTION_NOT_FOUND = 'CONNECTION_NOT_FOUND',
+  INVALID_SETTINGS = 'INVALID_SETTINGS',
+  DEVICE_NOT_FOUND = 'DEVICE_NOT_FOUND',
   UNKNOWN = 'UNKNOWN',
 }
 
@@ -23,21 +27,679 @@ export interface NetworkManagerError extends Error {
   details?: unknown;
 }
 
+export interface IpAddressInfo {
+  address: string;
+  prefix: number;
+}
+
+/** NetworkManager's connectivity check result. */
+export type ConnectivityState = 'unknown' | 'none' | 'portal' | 'limited' | 'full';
+
+/** NetworkManager's `NMMetered`; only `unknown`, `yes` and `no` can be saved on a connection. */
+export type MeteredState = 'unknown' | 'yes' | 'no' | 'guess-yes' | 'guess-no';
+
+/** Payload of `metered-changed`. */
+export interface MeteredChangedEvent {
+  /** Primary connection the value belongs to, if any. */
+  uuid: string | null;
+  metered: MeteredState;
+  previous: MeteredState;
+}
+
 export interface NetworkInfo {
   name: string;
+  /** For display only: different networks can share it. */
   ssid: string;
+  /** Raw SSID bytes as lowercase hex; empty when not Wi-Fi. Identifies the network. */
+  ssid_hex: string;
+  /** UUID of the saved connection behind this entry, when there is one. */
+  uuid?: string | null;
   connection_type: string;
   icon: string;
+  /** First IPv4 address; `0.0.0.0` when there is none. */
   ip_address: string;
+  ipv4_addresses: IpAddressInfo[];
+  /** Includes link-local `fe80::` addresses. */
+  ipv6_addresses: IpAddressInfo[];
+  ip4_connectivity: ConnectivityState;
+  ip6_connectivity: ConnectivityState;
+  /**
+   * NetworkManager's overall verdict. Not meaningful when its check is off;
+   * `checkConnectivity` probes on its own then.
+   */
+  connectivity: ConnectivityState;
+  /** The address in use now; differs from the permanent one when randomized. */
   mac_address: string;
+  /** Burnt-in address (`PermHwAddress`), when the device has one. */
+  permanent_mac_address?: string | null;
   signal_strength: number;
   security_type: WiFiSecurityType;
   is_connected: boolean;
+  /** Set on the connection, or guessed by NetworkManager (phone hotspots, modems). */
+  metered: MeteredState;
+  /** Link details when the device is Ethernet. */
+  wired?: WiredLinkInfo | null;
+  /**
+   * Set while a connection the plugin started comes up, one event per step.
+   * Those events describe the device being activated, not the primary connection.
+   */
+  activation?: ConnectionActivation | null;
+}
+
+export type Duplex = 'full' | 'half';
+
+export interface WiredLinkInfo {
+  interface: string;
+  /** A cable is plugged in and the other end is up. */
+  carrier: boolean;
+  speed_mbps: number | null;
+  duplex: Duplex | null;
+  /** What the active profile asks for; `null` when it leaves the port alone. */
+  autoneg: boolean | null;
+}
+
+/** Payload of `cable-changed`. */
+export interface CableEvent {
+  interface: string;
+  plugged: boolean;
+}
+
+/** `yes` and `guess-yes`: traffic probably costs money. */
+export function isMetered(metered: MeteredState): boolean {
+  return metered === 'yes' || metered === 'guess-yes';
+}
+
+export type DeviceType =
+  | 'ethernet'
+  | 'wifi'
+  | 'bluetooth'
+  | 'modem'
+  | 'infiniband'
+  | 'bond'
+  | 'vlan'
+  | 'bridge'
+  | 'team'
+  | 'tun'
+  | 'ip-tunnel'
+  | 'macvlan'
+  | 'vxlan'
+  | 'veth'
+  | 'dummy'
+  | 'ppp'
+  | 'wire-guard'
+  | 'wifi-p2p'
+  | 'loopback'
+  | 'generic'
+  | 'unknown';
+
+export type DeviceState =
+  | 'unknown'
+  | 'unmanaged'
+  | 'unavailable'
+  | 'disconnected'
+  | 'prepare'
+  | 'config'
+  | 'need-auth'
+  | 'ip-config'
+  | 'ip-check'
+  | 'secondaries'
+  | 'activated'
+  | 'deactivating'
+  | 'failed';
+
+export interface ConnectionRef {
+  id: string;
+  uuid: string;
+  /** NetworkManager setting type: `802-11-wireless`, `802-3-ethernet`, `vpn`… */
+  connection_type: string;
+}
+
+export interface DeviceInfo {
+  interface: string;
+  ip_interface: string | null;
+  device_type: DeviceType;
+  state: DeviceState;
+  /** `NMDeviceStateReason` name, e.g. `carrier` or `no-secrets`. */
+  state_reason: string;
+  state_reason_code: number;
+  driver: string | null;
+  driver_version: string | null;
+  firmware_version: string | null;
+  hw_address: string | null;
+  permanent_hw_address: string | null;
+  mtu: number;
+  managed: boolean;
+  autoconnect: boolean;
+  carrier: boolean | null;
+  /** Wired speed or Wi-Fi bitrate, Mb/s. */
+  speed_mbps: number | null;
+  active_connection: ConnectionRef | null;
+  available_connections: ConnectionRef[];
+}
+
+export interface RouteInfo {
+  destination: string;
+  prefix: number;
+  /** `null` for on-link routes. */
+  next_hop: string | null;
+  metric: number | null;
+}
+
+export interface IpFamilyConfiguration {
+  addresses: IpAddressInfo[];
+  gateway: string | null;
+  nameservers: string[];
+  domains: string[];
+  searches: string[];
+  routes: RouteInfo[];
+}
+
+export interface IpConfiguration {
+  interface: string;
+  ipv4: IpFamilyConfiguration;
+  ipv6: IpFamilyConfiguration;
+  /** The DHCPv4 lease; all empty when the address is not from DHCP. */
+  dhcp4: DhcpLease;
+  dhcp6: DhcpLease;
+}
+
+export interface DhcpLease {
+  /** Lease length in seconds. */
+  lease_time: number | null;
+  /** Server address for DHCPv4, server DUID for DHCPv6. */
+  server: string | null;
+  /** End of the lease, in seconds since the Unix epoch. */
+  expiry: number | null;
+  /** Every option the server sent, keyed by NetworkManager's names. */
+  options: Record<string, string>;
+}
+
+/** `dhcp` (DHCPv6 without SLAAC) and `ignore` are for IPv6 only. */
+export type IpMethod = 'auto' | 'manual' | 'link-local' | 'shared' | 'disabled' | 'dhcp' | 'ignore';
+
+/** Saved settings of one address family, as a connection editor shows them. */
+export interface IpFamilySettings {
+  method: IpMethod;
+  /** Required with `manual`; with `auto` they are added to the leased ones. */
+  addresses?: IpAddressInfo[];
+  gateway?: string | null;
+  dns?: string[];
+  dns_search?: string[];
+  /** Lower wins. NetworkManager's default when absent. */
+  route_metric?: number | null;
+  /** Use only `dns`, ignoring servers from DHCP / router advertisements. */
+  ignore_auto_dns?: boolean;
+  routes?: RouteInfo[];
+}
+
+/** A family left out is not touched. */
+export interface IpSettings {
+  ipv4?: IpFamilySettings | null;
+  ipv6?: IpFamilySettings | null;
+}
+
+/** Which device `getIpConfiguration` describes. Neither field: the primary connection's. */
+export interface IpConfigurationTarget {
+  interface?: string;
+  /** UUID of a connection that is currently active. */
+  uuid?: string;
+}
+
+export type ActiveConnectionState =
+  | 'unknown'
+  | 'activating'
+  | 'activated'
+  | 'deactivating'
+  | 'deactivated';
+
+/** How a saved connection ranks against the others. */
+export interface ConnectionPriority {
+  /** -999 to 999; higher is tried first. */
+  autoconnect_priority: number;
+  /** Lower carries traffic; NetworkManager's per-type default when absent. */
+  ipv4_route_metric?: number | null;
+  ipv6_route_metric?: number | null;
+}
+
+export interface DefaultRoute {
+  uuid: string;
+  connection_id: string;
+  interface: string;
+  gateway: string | null;
+  metric: number;
+}
+
+/** Installed default routes per family, the one in use first. */
+export interface DefaultRouteOrder {
+  ipv4: DefaultRoute[];
+  ipv6: DefaultRoute[];
+}
+
+export interface ActiveConnectionInfo {
+  id: string;
+  uuid: string;
+  connection_type: string;
+  /** Interfaces the connection runs on. */
+  devices: string[];
+  state: ActiveConnectionState;
+  /** Holds the IPv4 default route. */
+  default: boolean;
+  /** Holds the IPv6 default route. */
+  default6: boolean;
+  vpn: boolean;
+  /** NetworkManager's primary connection. */
+  is_primary: boolean;
+  /** Interface of the bond or bridge this is a port of. */
+  master: string | null;
+  /** Interfaces of active ports of this connection. */
+  slaves: string[];
+}
+
+export type ProxyMethod = 'none' | 'auto' | 'manual';
+
+/** Fixed proxies, each `host:port`. */
+export interface ManualProxy {
+  http?: string | null;
+  https?: string | null;
+  ftp?: string | null;
+  /** Used for whatever has no proxy of its own. */
+  socks?: string | null;
+  /** Hosts reached directly; shell patterns such as `*.corp.example`. */
+  ignore_hosts?: string[];
+}
+
+export interface ProxySettings {
+  method: ProxyMethod;
+  pac_url?: string | null;
+  pac_script?: string | null;
+  manual?: ManualProxy | null;
+  browser_only?: boolean;
+}
+
+export interface EffectiveProxy {
+  uuid: string;
+  connection_id: string;
+  interface: string | null;
+  settings: ProxySettings;
+  /** Configured PAC URL, or the WPAD one from DHCP when none is configured. */
+  pac_url: string | null;
+  /** `pac_url` came from WPAD. */
+  wpad: boolean;
+}
+
+export type BluetoothTetherType = 'panu' | 'dun';
+
+export interface BluetoothDeviceInfo {
+  name: string;
+  address: string;
+  capabilities: BluetoothTetherType[];
+  state: DeviceState;
+  active_connection: ConnectionRef | null;
+  available_connections: ConnectionRef[];
+}
+
+export interface BluetoothTetherConfig {
+  address: string;
+  tether_type: BluetoothTetherType;
+  /** Name for a new profile. */
+  id?: string;
+  /** Required for DUN. */
+  mobile_broadband?: MobileBroadbandConfig;
+}
+
+export type ModemState =
+  | 'failed'
+  | 'unknown'
+  | 'initializing'
+  | 'locked'
+  | 'disabled'
+  | 'disabling'
+  | 'enabling'
+  | 'enabled'
+  | 'searching'
+  | 'registered'
+  | 'disconnecting'
+  | 'connecting'
+  | 'connected';
+
+export type RegistrationState =
+  | 'unknown'
+  | 'idle'
+  | 'searching'
+  | 'denied'
+  | 'home'
+  | 'roaming'
+  | 'emergency-only';
+
+export interface ModemInfo {
+  /** ModemManager index; changes when the modem is replugged. */
+  id: string;
+  /** NetworkManager interface, e.g. `cdc-wdm0`. */
+  interface: string | null;
+  manufacturer: string | null;
+  model: string | null;
+  revision: string | null;
+  /** IMEI, or ESN/MEID for CDMA. */
+  equipment_identifier: string | null;
+  state: ModemState;
+  /** `gsm-umts`, `lte`, `5gnr`, `cdma-evdo`… */
+  capabilities: string[];
+  /** e.g. `sim-pin`; null when unlocked. */
+  unlock_required: string | null;
+  own_numbers: string[];
+}
+
+export interface ModemStatus {
+  id: string;
+  interface: string | null;
+  state: ModemState;
+  operator_name: string | null;
+  /** MCC + MNC. */
+  operator_code: string | null;
+  /** Fastest first: `5gnr`, `lte`, `hspa-plus`, `umts`, `edge`… */
+  access_technologies: string[];
+  /** 0–100. */
+  signal_quality: number;
+  signal_recent: boolean;
+  registration_state: RegistrationState;
+}
+
+export type MobileBroadbandType = 'gsm' | 'cdma';
+
+export interface MobileBroadbandConfig {
+  id?: string;
+  /** Defaults to `gsm`, which covers UMTS, LTE and 5G. */
+  network_type?: MobileBroadbandType;
+  /** GSM only. Empty lets the modem pick one where the operator allows it. */
+  apn?: string;
+  username?: string;
+  password?: string;
+  /** SIM PIN, 4–8 digits. */
+  pin?: string;
+  /** Defaults to true. */
+  roaming_allowed?: boolean;
+  /** Bind to this modem interface. */
+  interface?: string;
+  autoconnect?: boolean;
+}
+
+export interface VlanConfig {
+  /** VLAN tag, 1–4094. */
+  id: number;
+  /** Interface the tagged frames go out on. */
+  parent: string;
+}
+
+/** Timers in seconds; absent ones keep NetworkManager's defaults. */
+export interface BridgeConfig {
+  stp?: boolean;
+  priority?: number | null;
+  forward_delay?: number | null;
+  hello_time?: number | null;
+  max_age?: number | null;
+  /** Interfaces attached to the bridge; a port profile is kept for each. */
+  ports?: string[];
+}
+
+export type BondMode =
+  | 'balance-rr'
+  | 'active-backup'
+  | 'balance-xor'
+  | 'broadcast'
+  | '802.3ad'
+  | 'balance-tlb'
+  | 'balance-alb';
+
+export interface BondConfig {
+  mode: BondMode;
+  /** Link monitoring interval in ms; 0 turns it off. */
+  miimon?: number | null;
+  /** Interfaces attached to the bond; a port profile is kept for each. */
+  ports?: string[];
+}
+
+export type VirtualInterfaceKind =
+  | { vlan: VlanConfig }
+  | { bridge: BridgeConfig }
+  | { bond: BondConfig };
+
+export interface VirtualInterfaceConfig {
+  /** Interface NetworkManager creates, e.g. `br0` or `eth0.10`. */
+  interface_name: string;
+  /** Profile name. Defaults to `interface_name`. */
+  id?: string;
+  autoconnect?: boolean;
+  kind: VirtualInterfaceKind;
+}
+
+export interface VirtualInterfaceProfile {
+  id: string;
+  uuid: string;
+  interface_name: string;
+  autoconnect: boolean;
+  kind: VirtualInterfaceKind;
+}
+
+export type EapMethod = 'peap' | 'ttls' | 'tls';
+
+export type Phase2Auth = 'mschapv2' | 'mschap' | 'pap' | 'chap' | 'gtc' | 'md5';
+
+/**
+ * 802.1X settings for a wired port. PEAP/TTLS use `identity` + `password`;
+ * TLS a client certificate and private key. Paths must be absolute.
+ */
+export interface Eap8021xConfig {
+  /** Name for a new profile. */
+  id?: string;
+  eap: EapMethod;
+  identity: string;
+  anonymous_identity?: string;
+  password?: string;
+  /** Defaults to `mschapv2` for PEAP and TTLS. */
+  phase2_auth?: Phase2Auth;
+  ca_cert_path?: string;
+  system_ca_certs?: boolean;
+  domain_suffix_match?: string;
+  client_cert_path?: string;
+  private_key_path?: string;
+  private_key_password?: string;
+}
+
+/** Which wired profile `configureWired8021x` writes: a new one on `interface`, or `uuid`. */
+export interface Wired8021xTarget {
+  interface?: string;
+  uuid?: string;
+}
+
+/** A step of `NetworkInfo.activation`, sent until the device is up or has failed. */
+export interface ConnectionActivation {
+  uuid: string;
+  interface: string;
+  state: DeviceState;
+  /** `NMDeviceStateReason` name, e.g. `supplicant-failed`. */
+  reason: string;
+  reason_code: number;
+  failed: boolean;
+}
+
+/** One sample of the background bandwidth sampler. */
+export interface BandwidthPoint {
+  /** Seconds since the epoch. */
+  timestamp: number;
+  download_speed: number;
+  upload_speed: number;
+}
+
+export interface BandwidthSamplerConfig {
+  /** Defaults to 1000, from 1000 to 60000. */
+  interval_ms?: number;
+  /** Points kept per interface. Defaults to 300, at most 86400. */
+  history_size?: number;
+  /** Interfaces to sample; every interface but loopback when empty. */
+  interfaces?: string[];
+}
+
+/** Where the fallback connectivity probe looks. Plain `http://` only. */
+export interface ConnectivityProbeConfig {
+  /** Defaults to `http://nmcheck.gnome.org/check_network_status.txt`. */
+  url?: string;
+  /** Defaults to 200. */
+  expected_status?: number;
+  /** Text the body must contain; `null` checks the status only. */
+  expected_body?: string | null;
+  /** 500 to 30000 ms. Defaults to 5000. */
+  timeout_ms?: number;
+}
+
+export interface ConnectivityStatus {
+  state: ConnectivityState;
+  /** The portal's login page, when behind one and it could be found. */
+  portal_url: string | null;
+  /** `probe` when NetworkManager's own check is off. */
+  source: 'network-manager' | 'probe';
+}
+
+/** Payload of `captive-portal-detected`. */
+export interface CaptivePortalEvent {
+  url: string | null;
+  uuid: string | null;
+  name: string;
+}
+
+/**
+ * `auto` uses ICMP echo when unprivileged ICMP sockets are allowed and
+ * TCP connect timing otherwise.
+ */
+export type PingMethod = 'auto' | 'icmp' | 'tcp';
+
+export type PingTarget =
+  /** Default gateway of the primary connection; TCP uses port 80. */
+  | { kind: 'gateway' }
+  /** First DNS server of the primary connection; TCP uses port 53. */
+  | { kind: 'dns' }
+  /** TCP uses `port`, 443 by default. */
+  | { kind: 'host'; host: string; port?: number | null };
+
+export interface PingConfig {
+  /** Defaults to the gateway and the DNS server. */
+  targets?: PingTarget[];
+  /** Probes per target, 1 to 100. Defaults to 4. */
+  count?: number;
+  /** 200 to 10000 ms between probes. Defaults to 1000. */
+  interval_ms?: number;
+  /** 100 to 10000 ms to wait for each reply. Defaults to 1000. */
+  timeout_ms?: number;
+  method?: PingMethod;
+}
+
+export interface PingMonitorConfig extends PingConfig {
+  /** 1 s to 1 h from the start of one round to the next. Defaults to 10000. */
+  every_ms?: number;
+}
+
+/** Round trips to one target, in milliseconds; `null` without replies. */
+export interface PingResult {
+  /** `gateway`, `dns`, or the host as given. */
+  target: string;
+  address: string;
+  /** The method actually used. */
+  method: Exclude<PingMethod, 'auto'>;
+  sent: number;
+  received: number;
+  /** 0 to 100. */
+  packet_loss: number;
+  rtt_min_ms: number | null;
+  rtt_avg_ms: number | null;
+  rtt_max_ms: number | null;
+  jitter_ms: number | null;
+  /** Why nothing was sent to this target. */
+  error: string | null;
+}
+
+/** Which local calendar days `getDataUsage` adds up. */
+export type DataUsageRange =
+  | { kind: 'today' }
+  /** The current billing period of the connection's quota, or the calendar month. */
+  | { kind: 'month' }
+  /** The last `days` days, today included. */
+  | { kind: 'last-days'; days: number }
+  /** Both ends included, `YYYY-MM-DD`. */
+  | { kind: 'between'; from: string; to: string };
+
+/** A monthly allowance for one connection. */
+export interface DataQuota {
+  /** Download and upload together, per billing period. */
+  limit_bytes: number;
+  /** Day of the month the period starts on, 1 to 28. Defaults to 1. */
+  reset_day?: number;
+  /** Percentages that raise `data-quota-warning`. Defaults to `[80, 100]`. */
+  thresholds?: number[];
+}
+
+export interface DailyUsage {
+  date: string;
+  rx_bytes: number;
+  tx_bytes: number;
+}
+
+export interface DataUsage {
+  /** The SSID for Wi-Fi, the profile UUID for the rest. */
+  connection: string;
+  uuid: string;
+  name: string;
+  from: string;
+  to: string;
+  rx_bytes: number;
+  tx_bytes: number;
+  total_bytes: number;
+  /** Only days with traffic, oldest first. */
+  days: DailyUsage[];
+  quota: DataQuota | null;
+}
+
+/** Payload of `data-quota-warning`. */
+export interface DataQuotaWarningEvent {
+  connection: string;
+  name: string;
+  threshold: number;
+  used_bytes: number;
+  limit_bytes: number;
+  period_start: string;
+}
+
+/** Per-second growth of the error counters since the previous reading. */
+export interface ErrorRates {
+  rx_errors: number;
+  tx_errors: number;
+  rx_dropped: number;
+  tx_dropped: number;
+  collisions: number;
+}
+
+/** Kernel counters of one interface; a counter the driver lacks reads 0. */
+export interface InterfaceStatistics {
+  interface: string;
+  rx_bytes: number;
+  tx_bytes: number;
+  rx_packets: number;
+  tx_packets: number;
+  rx_errors: number;
+  tx_errors: number;
+  rx_dropped: number;
+  tx_dropped: number;
+  collisions: number;
+  /** Multicast packets received. */
+  multicast: number;
+  /** All 0 on the first call. */
+  error_rates: ErrorRates;
 }
 
 export interface NetworkStats {
   download_speed: number;
   upload_speed: number;
+  /** Moving average over the last five samples; steadier for display. */
+  download_speed_avg: number;
+  upload_speed_avg: number;
   total_downloaded: number;
   total_uploaded: number;
   connection_duration: number;
@@ -58,25 +720,84 @@ export enum WiFiSecurityType {
  */
 export interface WiFiConnectionConfig {
   ssid: string;
+  /** Raw SSID bytes in hex, from `NetworkInfo.ssid_hex`. Takes precedence over `ssid`. */
+  ssid_hex?: string;
   password?: string;
   security_type: WiFiSecurityType;
   username?: string;
+  mac_address_policy?: MacAddressPolicy;
 }
 
+/**
+ * MAC address a connection presents. `stable` is random but fixed per
+ * connection; `{ explicit: 'aa:bb:cc:dd:ee:ff' }` is that exact address.
+ */
+export type MacAddressPolicy =
+  | 'preserve'
+  | 'permanent'
+  | 'random'
+  | 'stable'
+  | { explicit: string };
+
 /**
  * Ergonomic input for frontend code.
  * This is converted to WiFiConnectionConfig before invoking Rust.
  */
 export interface ConnectToWifiInput {
   ssid: string;
+  ssidHex?: string;
   password?: string;
   securityType: WiFiSecurityType;
   username?: string;
+  macAddressPolicy?: MacAddressPolicy;
+}
+
+export type WifiSortKey = 'signal' | 'ssid' | 'none';
+
+export type KnownNetworkFilter = 'all' | 'known-only' | 'unknown-only';
+
+/**
+ * Wire-format expected by the `query` argument of `list_wifi_networks`.
+ */
+export interface WifiNetworkQuery {
+  min_signal?: number;
+  security_types?: WiFiSecurityType[];
+  include_hidden?: boolean;
+  dedupe_by_ssid?: boolean;
+  sort_by?: WifiSortKey;
+  limit?: number;
+  known?: KnownNetworkFilter;
 }
 
 export interface ListWifiNetworksOptions {
   forceRefresh?: boolean;
   ttlMs?: number;
+  /** Drop networks weaker than this (0–100). */
+  minSignal?: number;
+  /** Keep only these security types. */
+  securityTypes?: WiFiSecurityType[];
+  /** Keep access points with a hidden SSID. Defaults to `true`. */
+  includeHidden?: boolean;
+  /** One entry per SSID, the strongest; hidden networks all stay. Defaults to `true`. */
+  dedupeBySsid?: boolean;
+  /** Defaults to `'signal'` (strongest first). */
+  sortBy?: WifiSortKey;
+  limit?: number;
+  /** Filter on whether a saved connection exists. Defaults to `'all'`. */
+  known?: KnownNetworkFilter;
+}
+
+/**
+ * Pace of the background scanner. Results arrive through the
+ * `wifi-networks-changed` event.
+ */
+export interface WifiScanConfig {
+  /** Whether the Wi-Fi list is on screen. Defaults to `true`. */
+  panel_open?: boolean;
+  /** Interval while the panel is open. Defaults to 15000, minimum 10000. */
+  active_interval_ms?: number;
+  /** Interval while the panel is closed; `0` pauses. Defaults to 300000, minimum 30000. */
+  idle_interval_ms?: number;
 }
 
 export type VpnType =
@@ -114,6 +835,9 @@ export interface VpnStatus {
   active_profile_uuid: string | null;
   active_profile_name: string | null;
   ip_address: string | null;
+  /** Direcciones dentro del túnel, de ambas familias. */
+  ipv4_addresses: IpAddressInfo[];
+  ipv6_addresses: IpAddressInfo[];
   gateway: string | null;
   since_unix_ms: number | null;
   /** La interfaz del túnel: sdwan0, tailscale0, wg0… */
@@ -201,6 +925,14 @@ function normalizeInvokeError(error: unknown): NetworkManagerError {
     code = NetworkManagerErrorCode.VPN_PLUGIN_UNAVAILABLE;
   } else if (message.includes('no active vpn connection')) {
     code = NetworkManagerErrorCode.VPN_NOT_ACTIVE;
+  } else if (message.includes('invalid ssid')) {
+    code = NetworkManagerErrorCode.INVALID_SSID;
+  } else if (message.includes('connection not found')) {
+    code = NetworkManagerErrorCode.CONNECTION_NOT_FOUND;
+  } else if (message.includes('invalid connection settings')) {
+    code = NetworkManagerErrorCode.INVALID_SETTINGS;
+  } else if (message.includes('device not found')) {
+    code = NetworkManagerErrorCode.DEVICE_NOT_FOUND;
   }
 
   const typedError = new Error(rawMessage) as NetworkManagerError;
@@ -225,12 +957,29 @@ export async function getCurrentNetworkState(): Promise<NetworkInfo> {
   return await invokeWithTypedError<NetworkInfo>('plugin:network-manager|get_network_state');
 }
 
+function toNativeWifiNetworkQuery(options: ListWifiNetworksOptions): WifiNetworkQuery | undefined {
+  const query: WifiNetworkQuery = {
+    min_signal: options.minSignal,
+    security_types: options.securityTypes,
+    include_hidden: options.includeHidden,
+    dedupe_by_ssid: options.dedupeBySsid,
+    sort_by: options.sortBy,
+    limit: options.limit,
+    known: options.known,
+  };
+
+  return Object.values(query).some((value) => value !== undefined) ? query : undefined;
+}
+
 export async function listWifiNetworks(
   options: ListWifiNetworksOptions = {},
 ): Promise<NetworkInfo[]> {
+  const query = toNativeWifiNetworkQuery(options);
+
   return await invokeWithTypedError<NetworkInfo[]>('plugin:network-manager|list_wifi_networks', {
     force_refresh: options.forceRefresh,
     ttl_ms: options.ttlMs,
+    ...(query ? { query } : {}),
   });
 }
 
@@ -238,6 +987,20 @@ export async function rescanWifi(): Promise<NetworkInfo[]> {
   return await invokeWithTypedError<NetworkInfo[]>('plugin:network-manager|rescan_wifi');
 }
 
+/**
+ * Starts the background scanner, or changes its pace if it is running.
+ * Call it again with `panel_open: false` when the Wi-Fi list is hidden.
+ */
+export async function startWifiScanner(config: WifiScanConfig = {}): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|start_wifi_scanner', {
+    config,
+  });
+}
+
+export async function stopWifiScanner(): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|stop_wifi_scanner');
+}
+
 function toNativeWiFiConnectionConfig(
   config: ConnectToWifiInput | WiFiConnectionConfig,
 ): WiFiConnectionConfig {
@@ -247,9 +1010,11 @@ function toNativeWiFiConnectionConfig(
 
   return {
     ssid: config.ssid,
+    ssid_hex: config.ssidHex,
     password: config.password,
     security_type: config.securityType,
     username: config.username,
+    mac_address_policy: config.macAddressPolicy,
   };
 }
 
@@ -267,16 +1032,249 @@ export async function disconnectFromWifi(): Promise<void> {
   return await invokeWithTypedError<void>('plugin:network-manager|disconnect_from_wifi');
 }
 
+/** Takes the device down and keeps it from autoconnecting until used again. */
+export async function disconnectDevice(iface: string): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|disconnect_device', {
+    interface: iface,
+  });
+}
+
+/** Not persistent across NetworkManager restarts. */
+export async function setDeviceManaged(iface: string, managed: boolean): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_device_managed', {
+    interface: iface,
+    managed,
+  });
+}
+
+export async function setDeviceAutoconnect(iface: string, autoconnect: boolean): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_device_autoconnect', {
+    interface: iface,
+    autoconnect,
+  });
+}
+
+/**
+ * Creates (with `interface`) or updates (with `uuid`) a wired 802.1X
+ * profile and activates it. Resolves with the profile's UUID; follow
+ * `network-changed` events for the outcome, in `activation`.
+ */
+export async function configureWired8021x(
+  target: Wired8021xTarget,
+  config: Eap8021xConfig,
+): Promise<string> {
+  return await invokeWithTypedError<string>('plugin:network-manager|configure_wired_8021x', {
+    interface: target.interface,
+    uuid: target.uuid,
+    config,
+  });
+}
+
+export async function getProxySettings(uuid: string): Promise<ProxySettings> {
+  return await invokeWithTypedError<ProxySettings>('plugin:network-manager|get_proxy_settings', {
+    uuid,
+  });
+}
+
+export async function setProxySettings(uuid: string, settings: ProxySettings): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_proxy_settings', {
+    uuid,
+    settings,
+  });
+}
+
+export async function getEffectiveProxy(): Promise<EffectiveProxy> {
+  return await invokeWithTypedError<EffectiveProxy>('plugin:network-manager|get_effective_proxy');
+}
+
+export async function setConnectionMetered(uuid: string, metered: MeteredState): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_connection_metered', {
+    uuid,
+    metered,
+  });
+}
+
+export async function listBluetoothDevices(): Promise<BluetoothDeviceInfo[]> {
+  return await invokeWithTypedError<BluetoothDeviceInfo[]>(
+    'plugin:network-manager|list_bluetooth_devices',
+  );
+}
+
+/** Resolves with the profile's UUID; follow `network-changed` events for the outcome, in `activation`. */
+export async function connectBluetoothTether(config: BluetoothTetherConfig): Promise<string> {
+  return await invokeWithTypedError<string>('plugin:network-manager|connect_bluetooth_tether', {
+    config,
+  });
+}
+
+export async function listModems(): Promise<ModemInfo[]> {
+  return await invokeWithTypedError<ModemInfo[]>('plugin:network-manager|list_modems');
+}
+
+/** `modem` is a modem id or interface; the first modem when omitted. */
+export async function getModemStatus(modem?: string): Promise<ModemStatus> {
+  return await invokeWithTypedError<ModemStatus>('plugin:network-manager|get_modem_status', {
+    modem,
+  });
+}
+
+export async function createMobileBroadbandConnection(
+  config: MobileBroadbandConfig,
+): Promise<ConnectionRef> {
+  return await invokeWithTypedError<ConnectionRef>(
+    'plugin:network-manager|create_mobile_broadband_connection',
+    { config },
+  );
+}
+
+export async function listVirtualInterfaces(): Promise<VirtualInterfaceProfile[]> {
+  return await invokeWithTypedError<VirtualInterfaceProfile[]>(
+    'plugin:network-manager|list_virtual_interfaces',
+  );
+}
+
+export async function createVirtualInterface(
+  config: VirtualInterfaceConfig,
+): Promise<VirtualInterfaceProfile> {
+  return await invokeWithTypedError<VirtualInterfaceProfile>(
+    'plugin:network-manager|create_virtual_interface',
+    { config },
+  );
+}
+
+export async function updateVirtualInterface(
+  uuid: string,
+  config: VirtualInterfaceConfig,
+): Promise<VirtualInterfaceProfile> {
+  return await invokeWithTypedError<VirtualInterfaceProfile>(
+    'plugin:network-manager|update_virtual_interface',
+    { uuid, config },
+  );
+}
+
+export async function deleteVirtualInterface(uuid: string): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|delete_virtual_interface', {
+    uuid,
+  });
+}
+
 export async function getSavedWifiNetworks(): Promise<NetworkInfo[]> {
   return await invokeWithTypedError<NetworkInfo[]>('plugin:network-manager|get_saved_wifi_networks');
 }
 
-export async function deleteWifiConnection(ssid: string): Promise<void> {
+export async function deleteWifiConnection(ssid: string, ssidHex?: string): Promise<void> {
   return await invokeWithTypedError<void>('plugin:network-manager|delete_wifi_connection', {
     ssid,
+    ssid_hex: ssidHex,
+  });
+}
+
+/** `null` when the connection follows NetworkManager's default. */
+export async function getMacAddressPolicy(uuid: string): Promise<MacAddressPolicy | null> {
+  return await invokeWithTypedError<MacAddressPolicy | null>(
+    'plugin:network-manager|get_mac_address_policy',
+    { uuid },
+  );
+}
+
+/** Pass `null` to go back to NetworkManager's default. Applies on next activation. */
+export async function setMacAddressPolicy(
+  uuid: string,
+  policy: MacAddressPolicy | null,
+): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_mac_address_policy', {
+    uuid,
+    policy,
+  });
+}
+
+export async function listDevices(): Promise<DeviceInfo[]> {
+  return await invokeWithTypedError<DeviceInfo[]>('plugin:network-manager|list_devices');
+}
+
+export async function listActiveConnections(): Promise<ActiveConnectionInfo[]> {
+  return await invokeWithTypedError<ActiveConnectionInfo[]>(
+    'plugin:network-manager|list_active_connections',
+  );
+}
+
+export async function getIpConfiguration(
+  target: IpConfigurationTarget = {},
+): Promise<IpConfiguration> {
+  return await invokeWithTypedError<IpConfiguration>(
+    'plugin:network-manager|get_ip_configuration',
+    {
+      interface: target.interface,
+      uuid: target.uuid,
+    },
+  );
+}
+
+export async function getIpSettings(uuid: string): Promise<IpSettings> {
+  return await invokeWithTypedError<IpSettings>('plugin:network-manager|get_ip_settings', {
+    uuid,
+  });
+}
+
+/**
+ * Validates and saves IP settings on a connection. With `reapply`, an active
+ * connection picks them up without reconnecting.
+ */
+export async function updateIpSettings(
+  uuid: string,
+  settings: IpSettings,
+  reapply = false,
+): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|update_ip_settings', {
+    uuid,
+    settings,
+    reapply,
   });
 }
 
+/** Link details of an Ethernet port, plugged in or not; the first Ethernet device by default. */
+export async function getWiredLinkInfo(iface?: string): Promise<WiredLinkInfo> {
+  return await invokeWithTypedError<WiredLinkInfo>('plugin:network-manager|get_wired_link_info', {
+    interface: iface ?? null,
+  });
+}
+
+export async function getConnectionPriority(uuid: string): Promise<ConnectionPriority> {
+  return await invokeWithTypedError<ConnectionPriority>(
+    'plugin:network-manager|get_connection_priority',
+    { uuid },
+  );
+}
+
+/**
+ * Saves the autoconnect priority and route metrics of a connection. With
+ * `reapply`, an active connection's routes move to the new metrics at once.
+ */
+export async function setConnectionPriority(
+  uuid: string,
+  priority: ConnectionPriority,
+  reapply = false,
+): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_connection_priority', {
+    uuid,
+    priority,
+    reapply,
+  });
+}
+
+/** Ranks saved connections, most preferred first, through their autoconnect priorities. */
+export async function reorderPreferredNetworks(uuids: string[]): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|reorder_preferred_networks', {
+    uuids,
+  });
+}
+
+export async function getDefaultRouteOrder(): Promise<DefaultRouteOrder> {
+  return await invokeWithTypedError<DefaultRouteOrder>(
+    'plugin:network-manager|get_default_route_order',
+  );
+}
+
 export async function toggleNetwork(enabled: boolean): Promise<void> {
   return await invokeWithTypedError<void>('plugin:network-manager|toggle_network_state', {
     enabled,
@@ -297,8 +1295,107 @@ export async function isWirelessAvailable(): Promise<boolean> {
   return await invokeWithTypedError<boolean>('plugin:network-manager|is_wireless_available');
 }
 
-export async function getNetworkStats(): Promise<NetworkStats> {
-  return await invokeWithTypedError<NetworkStats>('plugin:network-manager|get_network_stats');
+/** Which interface `getNetworkStats` measures; the primary connection's by default. */
+export type NetworkStatsTarget = IpConfigurationTarget;
+
+export async function getNetworkStats(target: NetworkStatsTarget = {}): Promise<NetworkStats> {
+  return await invokeWithTypedError<NetworkStats>('plugin:network-manager|get_network_stats', {
+    interface: target.interface,
+    uuid: target.uuid,
+  });
+}
+
+/**
+ * Starts sampling bandwidth in the background, or reconfigures a running
+ * sampler. Each pass is emitted as `network-stats` with a `NetworkStats[]`.
+ */
+export async function startBandwidthSampler(config: BandwidthSamplerConfig = {}): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|start_bandwidth_sampler', {
+    config,
+  });
+}
+
+export async function stopBandwidthSampler(): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|stop_bandwidth_sampler');
+}
+
+/**
+ * Sampled points of `iface` (the primary connection's interface by default),
+ * oldest first, from the last `window` seconds or all that are kept.
+ */
+export async function getBandwidthHistory(
+  iface?: string,
+  window?: number,
+): Promise<BandwidthPoint[]> {
+  return await invokeWithTypedError<BandwidthPoint[]>(
+    'plugin:network-manager|get_bandwidth_history',
+    { interface: iface, window },
+  );
+}
+
+export async function getInterfaceStatistics(): Promise<InterfaceStatistics[]> {
+  return await invokeWithTypedError<InterfaceStatistics[]>(
+    'plugin:network-manager|get_interface_statistics',
+  );
+}
+
+/**
+ * Data used per connection over `range` (the current month by default),
+ * from a ledger kept in the app data dir across restarts. `connection`
+ * takes a UUID, an SSID or a profile name.
+ */
+export async function getDataUsage(
+  range?: DataUsageRange,
+  connection?: string,
+): Promise<DataUsage[]> {
+  return await invokeWithTypedError<DataUsage[]>('plugin:network-manager|get_data_usage', {
+    range,
+    connection,
+  });
+}
+
+/** Sets the monthly quota of a connection, or clears it with `null`. */
+export async function setDataQuota(connection: string, quota: DataQuota | null): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_data_quota', {
+    connection,
+    quota,
+  });
+}
+
+/**
+ * Checks connectivity now: NetworkManager's `CheckConnectivity`, or the
+ * plugin's own probe when NetworkManager's check is turned off.
+ */
+export async function checkConnectivity(): Promise<ConnectivityStatus> {
+  return await invokeWithTypedError<ConnectivityStatus>(
+    'plugin:network-manager|check_connectivity',
+  );
+}
+
+/** Points the fallback probe at another URL. */
+export async function setConnectivityProbe(config: ConnectivityProbeConfig): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|set_connectivity_probe', {
+    config,
+  });
+}
+
+/** Measures latency and loss to every target, all at once. */
+export async function ping(config?: PingConfig): Promise<PingResult[]> {
+  return await invokeWithTypedError<PingResult[]>('plugin:network-manager|ping', { config });
+}
+
+/**
+ * Pings in rounds in the background, or reconfigures a running monitor.
+ * Each round is emitted as `ping-result` with a `PingResult[]`.
+ */
+export async function startPingMonitor(config?: PingMonitorConfig): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|start_ping_monitor', {
+    config,
+  });
+}
+
+export async function stopPingMonitor(): Promise<void> {
+  return await invokeWithTypedError<void>('plugin:network-manager|stop_ping_monitor');
 }
 
 export async function getNetworkInterfaces(): Promise<string[]> {
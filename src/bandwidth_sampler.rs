@@ -0,0 +1,222 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::error::{NetworkError, Result};
+use crate::models::{BandwidthPoint, BandwidthSamplerConfig, NetworkStats};
+use crate::network_stats::NetworkStatsTracker;
+use crate::NetworkManagerState;
+
+/// The last points of one interface, oldest first. Once full, every new
+/// point pushes the oldest one out, so memory stays bounded however long
+/// the sampler runs.
+pub struct BandwidthHistory {
+    capacity: usize,
+    points: VecDeque<BandwidthPoint>,
+}
+
+impl BandwidthHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            points: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, point: BandwidthPoint) {
+        while self.points.len() >= self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back(point);
+    }
+
+    /// Changes the size, dropping the oldest points if it shrinks.
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.points.len() > capacity {
+            self.points.pop_front();
+        }
+    }
+
+    /// Points from the last `window` seconds before `now`, or all of them.
+    pub fn window(&self, now: u64, window: Option<u64>) -> Vec<BandwidthPoint> {
+        let cutoff = window.map(|window| now.saturating_sub(window)).unwrap_or(0);
+        self.points
+            .iter()
+            .filter(|point| point.timestamp >= cutoff)
+            .cloned()
+            .collect()
+    }
+}
+
+enum Command {
+    Configure(BandwidthSamplerConfig),
+    Stop,
+}
+
+/// Handle to the background sampling thread. Dropping it stops the thread.
+pub struct BandwidthSampler {
+    tx: Sender<Command>,
+}
+
+impl BandwidthSampler {
+    pub fn spawn<R: Runtime>(app: AppHandle<R>, config: BandwidthSamplerConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || run(app, rx, config));
+        Self { tx }
+    }
+
+    /// Changes the pace or the interfaces of a running sampler. Fails if the
+    /// thread is gone.
+    pub fn configure(&self, config: BandwidthSamplerConfig) -> Result<()> {
+        self.tx.send(Command::Configure(config)).map_err(|_| {
+            NetworkError::OperationError("bandwidth sampler is not running".to_string())
+        })
+    }
+}
+
+impl Drop for BandwidthSampler {
+    fn drop(&mut self) {
+        let _ = self.tx.send(Command::Stop);
+    }
+}
+
+/// Time between samples. Counters are cheap to read, but every sample is
+/// an event the frontend has to handle.
+pub fn sample_interval(config: &BandwidthSamplerConfig) -> Duration {
+    Duration::from_millis(config.interval_ms.clamp(1_000, 60_000))
+}
+
+/// Points kept per interface: a day at one per second at most.
+pub fn history_size(config: &BandwidthSamplerConfig) -> usize {
+    config.history_size.clamp(1, 86_400)
+}
+
+fn run<R: Runtime>(app: AppHandle<R>, rx: Receiver<Command>, mut config: BandwidthSamplerConfig) {
+    let mut trackers: HashMap<String, NetworkStatsTracker> = HashMap::new();
+    // The first pass only takes the baseline every speed is measured from.
+    sample(&config, &mut trackers);
+    let mut deadline = Instant::now() + sample_interval(&config);
+
+    loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Command::Configure(new_config)) => {
+                config = new_config;
+                if let Some(state) = app.try_state::<NetworkManagerState<R>>() {
+                    state.resize_bandwidth_history(history_size(&config));
+                }
+                deadline = Instant::now() + sample_interval(&config);
+                continue;
+            }
+            Ok(Command::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        let Some(state) = app.try_state::<NetworkManagerState<R>>() else {
+            break;
+        };
+
+        let stats = sample(&config, &mut trackers);
+        if !stats.is_empty() {
+            state.record_bandwidth(&stats, timestamp(), history_size(&config));
+            let _ = app.emit("network-stats", &stats);
+        }
+
+        // Keep to the grid rather than drifting by however long a pass took,
+        // unless the machine was asleep and the grid is long gone.
+        deadline += sample_interval(&config);
+        if deadline < Instant::now() {
+            deadline = Instant::now() + sample_interval(&config);
+        }
+    }
+}
+
+/// Reads every interface the configuration asks for. An interface seen for
+/// the first time only gets its baseline; one that went away loses its
+/// tracker and starts over if it comes back.
+fn sample(
+    config: &BandwidthSamplerConfig,
+    trackers: &mut HashMap<String, NetworkStatsTracker>,
+) -> Vec<NetworkStats> {
+    let interfaces = if config.interfaces.is_empty() {
+        crate::network_stats::get_network_interfaces().unwrap_or_default()
+    } else {
+        config.interfaces.clone()
+    };
+    trackers.retain(|interface, _| interfaces.contains(interface));
+
+    let mut stats = Vec::new();
+    for interface in interfaces {
+        match trackers.entry(interface) {
+            Entry::Occupied(mut entry) => match entry.get_mut().get_stats() {
+                Ok(current) => stats.push(current),
+                Err(e) => {
+                    log::debug!("bandwidth sample of {} failed: {}", entry.key(), e);
+                    entry.remove();
+                }
+            },
+            Entry::Vacant(entry) => {
+                if let Ok(tracker) = NetworkStatsTracker::new(entry.key().clone()) {
+                    entry.insert(tracker);
+                }
+            }
+        }
+    }
+    stats.sort_by(|a, b| a.interface.cmp(&b.interface));
+    stats
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(timestamp: u64) -> BandwidthPoint {
+        BandwidthPoint {
+            timestamp,
+            download_speed: timestamp * 10,
+            upload_speed: timestamp,
+        }
+    }
+
+    #[test]
+    fn history_keeps_only_the_newest_points() {
+        let mut history = BandwidthHistory::new(3);
+        for t in 1..=5 {
+            history.push(point(t));
+        }
+        assert_eq!(history.window(5, None), vec![point(3), point(4), point(5)]);
+
+        history.resize(2);
+        assert_eq!(history.window(5, None), vec![point(4), point(5)]);
+    }
+
+    #[test]
+    fn window_counts_back_from_now() {
+        let mut history = BandwidthHistory::new(10);
+        for t in 100..105 {
+            history.push(point(t));
+        }
+        assert_eq!(history.window(104, Some(2)), vec![point(102), point(103), point(104)]);
+        assert_eq!(history.window(200, Some(10)), Vec::new());
+    }
+
+    #[test]
+    fn sampler_settings_are_bounded() {
+        let config = BandwidthSamplerConfig {
+            interval_ms: 10,
+            history_size: 0,
+            interfaces: Vec::new(),
+        };
+        assert_eq!(sample_interval(&config), Duration::from_secs(1));
+        assert_eq!(history_size(&config), 1);
+    }
+}
@@ -2,7 +2,9 @@ use tauri::{AppHandle, Manager};
 
 use crate::{NetworkError, NetworkManagerState};
 use crate::models::{
-    NetworkInfo, WiFiConnectionConfig, VpnCreateConfig, VpnProfile, VpnStatus, VpnUpdateConfig,
+    ActiveConnectionInfo, BandwidthPoint, BandwidthSamplerConfig, BluetoothDeviceInfo, BluetoothTetherConfig, ConnectionRef, DeviceInfo,
+    ConnectionPriority, ConnectivityProbeConfig, ConnectivityStatus, DataQuota, DataUsage, DataUsageRange, DefaultRouteOrder, WiredLinkInfo, EffectiveProxy, InterfaceStatistics, MeteredState, PingConfig, PingMonitorConfig, PingResult, ProxySettings, Eap8021xConfig, IpConfiguration, IpSettings, MacAddressPolicy, NetworkInfo, WiFiConnectionConfig, VpnCreateConfig, VpnProfile, VpnStatus, VpnUpdateConfig,
+    MobileBroadbandConfig, ModemInfo, ModemStatus, VirtualInterfaceConfig, VirtualInterfaceProfile, WifiNetworkQuery, WifiScanConfig,
 };
 use crate::error::Result;
 
@@ -48,16 +50,21 @@ pub async fn get_network_state(app_handle: AppHandle) -> Result<NetworkInfo>  {
     .await
 }
 
-/// List available WiFi networks
-#[tauri::command]
+/// List available WiFi networks, filtered and sorted as `query` asks
+#[tauri::command(rename_all = "snake_case")]
 pub async fn list_wifi_networks(
     app_handle: AppHandle,
     force_refresh: Option<bool>,
     ttl_ms: Option<u64>,
+    query: Option<WifiNetworkQuery>,
 ) -> Result<Vec<NetworkInfo>>  {
     off_thread(move || {
         let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
-        state.list_wifi_networks(force_refresh.unwrap_or(false), ttl_ms)
+        state.list_wifi_networks(
+            force_refresh.unwrap_or(false),
+            ttl_ms,
+            &query.unwrap_or_default(),
+        )
     })
     .await
 }
@@ -72,6 +79,29 @@ pub async fn rescan_wifi(app_handle: AppHandle) -> Result<Vec<NetworkInfo>>  {
     .await
 }
 
+/// Start the background WiFi scanner, or change its pace if it is already running
+#[tauri::command]
+pub async fn start_wifi_scanner(
+    app_handle: AppHandle,
+    config: Option<WifiScanConfig>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.start_wifi_scanner(app_handle.clone(), config.unwrap_or_default())
+    })
+    .await
+}
+
+/// Stop the background WiFi scanner
+#[tauri::command]
+pub async fn stop_wifi_scanner(app_handle: AppHandle) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.stop_wifi_scanner()
+    })
+    .await
+}
+
 /// Connect to a WiFi network
 #[tauri::command]
 pub async fn connect_to_wifi(app_handle: AppHandle, config: WiFiConnectionConfig) -> Result<()>  {
@@ -94,6 +124,274 @@ pub async fn disconnect_from_wifi(app_handle: AppHandle) -> Result<()>  {
     .await
 }
 
+/// Disconnect a device by interface name, keeping it from autoconnecting
+#[tauri::command]
+pub async fn disconnect_device(app_handle: AppHandle, interface: String) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.disconnect_device(&interface)
+    })
+    .await
+}
+
+/// Set whether NetworkManager manages a device
+#[tauri::command]
+pub async fn set_device_managed(
+    app_handle: AppHandle,
+    interface: String,
+    managed: bool,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_device_managed(&interface, managed)
+    })
+    .await
+}
+
+/// Set whether NetworkManager may autoconnect a device
+#[tauri::command]
+pub async fn set_device_autoconnect(
+    app_handle: AppHandle,
+    interface: String,
+    autoconnect: bool,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_device_autoconnect(&interface, autoconnect)
+    })
+    .await
+}
+
+/// Create or update a wired 802.1X connection and bring it up
+#[tauri::command]
+pub async fn configure_wired_8021x(
+    app_handle: AppHandle,
+    interface: Option<String>,
+    uuid: Option<String>,
+    config: Eap8021xConfig,
+) -> Result<String>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.configure_wired_8021x(interface.as_deref(), uuid.as_deref(), config)
+    })
+    .await
+}
+
+/// List saved VLAN, bridge and bond profiles
+#[tauri::command]
+pub async fn list_virtual_interfaces(app_handle: AppHandle) -> Result<Vec<VirtualInterfaceProfile>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.list_virtual_interfaces()
+    })
+    .await
+}
+
+/// Create a VLAN, bridge or bond profile with its port profiles
+#[tauri::command]
+pub async fn create_virtual_interface(
+    app_handle: AppHandle,
+    config: VirtualInterfaceConfig,
+) -> Result<VirtualInterfaceProfile>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.create_virtual_interface(config)
+    })
+    .await
+}
+
+/// Update a VLAN, bridge or bond profile and its port profiles
+#[tauri::command]
+pub async fn update_virtual_interface(
+    app_handle: AppHandle,
+    uuid: String,
+    config: VirtualInterfaceConfig,
+) -> Result<VirtualInterfaceProfile>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.update_virtual_interface(&uuid, config)
+    })
+    .await
+}
+
+/// Delete a VLAN, bridge or bond profile and its port profiles
+#[tauri::command]
+pub async fn delete_virtual_interface(app_handle: AppHandle, uuid: String) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.delete_virtual_interface(&uuid)
+    })
+    .await
+}
+
+/// List modems known to ModemManager
+#[tauri::command]
+pub async fn list_modems(app_handle: AppHandle) -> Result<Vec<ModemInfo>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.list_modems()
+    })
+    .await
+}
+
+/// Get operator, access technology, signal and registration of a modem
+#[tauri::command]
+pub async fn get_modem_status(app_handle: AppHandle, modem: Option<String>) -> Result<ModemStatus>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_modem_status(modem.as_deref())
+    })
+    .await
+}
+
+/// Create a GSM or CDMA connection profile
+#[tauri::command]
+pub async fn create_mobile_broadband_connection(
+    app_handle: AppHandle,
+    config: MobileBroadbandConfig,
+) -> Result<ConnectionRef>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.create_mobile_broadband_connection(config)
+    })
+    .await
+}
+
+/// List paired Bluetooth devices that can tether
+#[tauri::command]
+pub async fn list_bluetooth_devices(app_handle: AppHandle) -> Result<Vec<BluetoothDeviceInfo>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.list_bluetooth_devices()
+    })
+    .await
+}
+
+/// Tether through a paired phone over PAN or DUN
+#[tauri::command]
+pub async fn connect_bluetooth_tether(
+    app_handle: AppHandle,
+    config: BluetoothTetherConfig,
+) -> Result<String>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.connect_bluetooth_tether(config)
+    })
+    .await
+}
+
+/// Get the proxy settings of a saved connection
+#[tauri::command]
+pub async fn get_proxy_settings(app_handle: AppHandle, uuid: String) -> Result<ProxySettings>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_proxy_settings(&uuid)
+    })
+    .await
+}
+
+/// Replace the proxy settings of a saved connection
+#[tauri::command]
+pub async fn set_proxy_settings(
+    app_handle: AppHandle,
+    uuid: String,
+    settings: ProxySettings,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_proxy_settings(&uuid, settings)
+    })
+    .await
+}
+
+/// Get the proxy configuration of the primary connection
+#[tauri::command]
+pub async fn get_effective_proxy(app_handle: AppHandle) -> Result<EffectiveProxy>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_effective_proxy()
+    })
+    .await
+}
+
+/// Mark a saved connection as metered or not
+#[tauri::command]
+pub async fn set_connection_metered(
+    app_handle: AppHandle,
+    uuid: String,
+    metered: MeteredState,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_connection_metered(&uuid, metered)
+    })
+    .await
+}
+
+/// Get carrier, speed, duplex and autonegotiation of an Ethernet port
+#[tauri::command]
+pub async fn get_wired_link_info(
+    app_handle: AppHandle,
+    interface: Option<String>,
+) -> Result<WiredLinkInfo>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_wired_link_info(interface.as_deref())
+    })
+    .await
+}
+
+/// Get the autoconnect priority and route metrics of a saved connection
+#[tauri::command]
+pub async fn get_connection_priority(
+    app_handle: AppHandle,
+    uuid: String,
+) -> Result<ConnectionPriority>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_connection_priority(&uuid)
+    })
+    .await
+}
+
+/// Set the autoconnect priority and route metrics of a saved connection
+#[tauri::command]
+pub async fn set_connection_priority(
+    app_handle: AppHandle,
+    uuid: String,
+    priority: ConnectionPriority,
+    reapply: Option<bool>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_connection_priority(&uuid, priority, reapply.unwrap_or(false))
+    })
+    .await
+}
+
+/// Rank saved connections, most preferred first
+#[tauri::command]
+pub async fn reorder_preferred_networks(
+    app_handle: AppHandle,
+    uuids: Vec<String>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.reorder_preferred_networks(&uuids)
+    })
+    .await
+}
+
+/// Get the default routes in the order they are used
+#[tauri::command]
+pub async fn get_default_route_order(app_handle: AppHandle) -> Result<DefaultRouteOrder>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_default_route_order()
+    })
+    .await
+}
+
 /// Get saved WiFi networks
 #[tauri::command]
 pub async fn get_saved_wifi_networks(app_handle: AppHandle) -> Result<Vec<NetworkInfo>>  {
@@ -104,12 +402,16 @@ pub async fn get_saved_wifi_networks(app_handle: AppHandle) -> Result<Vec<Networ
     .await
 }
 
-/// Delete a WiFi connection by SSID
-#[tauri::command]
-pub async fn delete_wifi_connection(app_handle: AppHandle, ssid: String) -> Result<()>  {
+/// Delete a WiFi connection by SSID, or by its raw bytes when `ssid_hex` is given
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_wifi_connection(
+    app_handle: AppHandle,
+    ssid: String,
+    ssid_hex: Option<String>,
+) -> Result<()>  {
     off_thread(move || {
         let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
-        let deleted = state.delete_wifi_connection(&ssid)?;
+        let deleted = state.delete_wifi_connection(&ssid, ssid_hex.as_deref())?;
         if !deleted {
             return Err(NetworkError::OperationError(format!(
                 "No saved WiFi connection found for SSID '{}'",
@@ -121,6 +423,92 @@ pub async fn delete_wifi_connection(app_handle: AppHandle, ssid: String) -> Resu
     .await
 }
 
+/// Get the MAC address policy of a saved connection
+#[tauri::command]
+pub async fn get_mac_address_policy(
+    app_handle: AppHandle,
+    uuid: String,
+) -> Result<Option<MacAddressPolicy>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_mac_address_policy(&uuid)
+    })
+    .await
+}
+
+/// Set the MAC address policy of a saved connection, or clear it with `None`
+#[tauri::command]
+pub async fn set_mac_address_policy(
+    app_handle: AppHandle,
+    uuid: String,
+    policy: Option<MacAddressPolicy>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_mac_address_policy(&uuid, policy)
+    })
+    .await
+}
+
+/// List every network device NetworkManager knows about
+#[tauri::command]
+pub async fn list_devices(app_handle: AppHandle) -> Result<Vec<DeviceInfo>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.list_devices()
+    })
+    .await
+}
+
+/// List active connections, with the primary one marked
+#[tauri::command]
+pub async fn list_active_connections(app_handle: AppHandle) -> Result<Vec<ActiveConnectionInfo>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.list_active_connections()
+    })
+    .await
+}
+
+/// Get the live IP configuration of a device, by interface or active connection UUID
+#[tauri::command]
+pub async fn get_ip_configuration(
+    app_handle: AppHandle,
+    interface: Option<String>,
+    uuid: Option<String>,
+) -> Result<IpConfiguration>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_ip_configuration(interface.as_deref(), uuid.as_deref())
+    })
+    .await
+}
+
+/// Get the saved IPv4/IPv6 settings of a connection
+#[tauri::command]
+pub async fn get_ip_settings(app_handle: AppHandle, uuid: String) -> Result<IpSettings>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_ip_settings(&uuid)
+    })
+    .await
+}
+
+/// Save IPv4/IPv6 settings on a connection, optionally reapplying them to its device
+#[tauri::command]
+pub async fn update_ip_settings(
+    app_handle: AppHandle,
+    uuid: String,
+    settings: IpSettings,
+    reapply: Option<bool>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.update_ip_settings(&uuid, settings, reapply.unwrap_or(false))
+    })
+    .await
+}
+
 /// Toggle network on or off
 #[tauri::command]
 pub async fn toggle_network_state(app_handle: AppHandle, enabled: bool) -> Result<()>  {
@@ -159,12 +547,147 @@ pub async fn is_wireless_available(app_handle: AppHandle) -> Result<bool>  {
     .await
 }
 
-/// Get network statistics for the active interface
+/// Get network statistics for an interface, a connection, or the primary connection
+#[tauri::command]
+pub async fn get_network_stats(
+    app_handle: AppHandle,
+    interface: Option<String>,
+    uuid: Option<String>,
+) -> Result<crate::models::NetworkStats>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_network_stats(interface.as_deref(), uuid.as_deref())
+    })
+    .await
+}
+
+/// Start the background bandwidth sampler, or reconfigure it if it is already running
+#[tauri::command]
+pub async fn start_bandwidth_sampler(
+    app_handle: AppHandle,
+    config: Option<BandwidthSamplerConfig>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.start_bandwidth_sampler(app_handle.clone(), config.unwrap_or_default())
+    })
+    .await
+}
+
+/// Stop the background bandwidth sampler
+#[tauri::command]
+pub async fn stop_bandwidth_sampler(app_handle: AppHandle) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.stop_bandwidth_sampler()
+    })
+    .await
+}
+
+/// Get the sampled bandwidth history of an interface
+#[tauri::command]
+pub async fn get_bandwidth_history(
+    app_handle: AppHandle,
+    interface: Option<String>,
+    window: Option<u64>,
+) -> Result<Vec<BandwidthPoint>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_bandwidth_history(interface.as_deref(), window)
+    })
+    .await
+}
+
+/// Get packet, error and drop counters of every interface
+#[tauri::command]
+pub async fn get_interface_statistics(app_handle: AppHandle) -> Result<Vec<InterfaceStatistics>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_interface_statistics()
+    })
+    .await
+}
+
+/// Check connectivity now, through NetworkManager or the plugin's own probe
+#[tauri::command]
+pub async fn check_connectivity(app_handle: AppHandle) -> Result<ConnectivityStatus>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.check_connectivity()
+    })
+    .await
+}
+
+/// Set where the plugin's own connectivity probe looks
+#[tauri::command]
+pub async fn set_connectivity_probe(
+    app_handle: AppHandle,
+    config: ConnectivityProbeConfig,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_connectivity_probe(config)
+    })
+    .await
+}
+
+/// Get the data used per connection over a range of days
+#[tauri::command]
+pub async fn get_data_usage(
+    app_handle: AppHandle,
+    range: Option<DataUsageRange>,
+    connection: Option<String>,
+) -> Result<Vec<DataUsage>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.get_data_usage(&range.unwrap_or_default(), connection.as_deref())
+    })
+    .await
+}
+
+/// Set or clear the monthly data quota of a connection
+#[tauri::command]
+pub async fn set_data_quota(
+    app_handle: AppHandle,
+    connection: String,
+    quota: Option<DataQuota>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.set_data_quota(&connection, quota)
+    })
+    .await
+}
+
+/// Measure round trips to the gateway, the DNS server or other hosts
+#[tauri::command]
+pub async fn ping(app_handle: AppHandle, config: Option<PingConfig>) -> Result<Vec<PingResult>>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.ping(&config.unwrap_or_default())
+    })
+    .await
+}
+
+/// Start the continuous ping monitor, or reconfigure it if it is already running
+#[tauri::command]
+pub async fn start_ping_monitor(
+    app_handle: AppHandle,
+    config: Option<PingMonitorConfig>,
+) -> Result<()>  {
+    off_thread(move || {
+        let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
+        state.start_ping_monitor(app_handle.clone(), config.unwrap_or_default())
+    })
+    .await
+}
+
+/// Stop the continuous ping monitor
 #[tauri::command]
-pub async fn get_network_stats(app_handle: AppHandle) -> Result<crate::models::NetworkStats>  {
+pub async fn stop_ping_monitor(app_handle: AppHandle) -> Result<()>  {
     off_thread(move || {
         let state = app_handle.state::<NetworkManagerState<tauri::Wry>>();
-        state.get_network_stats()
+        state.stop_ping_monitor()
     })
     .await
 }
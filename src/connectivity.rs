@@ -0,0 +1,234 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::error::{NetworkError, Result};
+use crate::models::{ConnectivityProbeConfig, ConnectivityState};
+
+/// Enough for any status line and headers, and for the body of a check
+/// page; a portal's login page is cut short, which is fine.
+const MAX_RESPONSE: usize = 64 * 1024;
+
+/// What the probe concluded, with the portal's login page when it found
+/// one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeOutcome {
+    pub state: ConnectivityState,
+    pub portal_url: Option<String>,
+}
+
+/// Host, port and path of a plain `http://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeUrl {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl ProbeUrl {
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid = |why: &str| NetworkError::InvalidSettings(format!("probe URL {}: {}", url, why));
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid("only http:// can be intercepted by a portal"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(slash) => (&rest[..slash], &rest[slash..]),
+            None => (rest, "/"),
+        };
+        // An IPv6 literal keeps its brackets in the Host header but not
+        // when it is resolved.
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                (host, port.parse().map_err(|_| invalid("bad port"))?)
+            }
+            _ => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(invalid("no host"));
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    fn origin(&self) -> String {
+        if self.port == 80 {
+            format!("http://{}", self.host)
+        } else {
+            format!("http://{}:{}", self.host, self.port)
+        }
+    }
+}
+
+pub fn probe_timeout(config: &ConnectivityProbeConfig) -> Duration {
+    Duration::from_millis(config.timeout_ms.clamp(500, 30_000))
+}
+
+/// Fetches the probe URL and judges the answer. Not reaching the server at
+/// all, name resolution included, is `Limited`: the caller only probes
+/// with a connection up.
+pub fn probe(config: &ConnectivityProbeConfig) -> Result<ProbeOutcome> {
+    let url = ProbeUrl::parse(&config.url)?;
+    let limited = ProbeOutcome {
+        state: ConnectivityState::Limited,
+        portal_url: None,
+    };
+    match fetch(&url, probe_timeout(config)) {
+        Ok(response) => Ok(classify(&response, &url, config)),
+        Err(e) => {
+            log::debug!("connectivity probe of {} failed: {}", config.url, e);
+            Ok(limited)
+        }
+    }
+}
+
+fn fetch(url: &ProbeUrl, timeout: Duration) -> std::io::Result<Vec<u8>> {
+    let host = url.host.trim_start_matches('[').trim_end_matches(']');
+    let address: SocketAddr = (host, url.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address"))?;
+    let mut stream = TcpStream::connect_timeout(&address, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    // One write: a request split over several segments is one more
+    // thing for a portal's interception to get wrong.
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: tauri-plugin-network-manager\r\n\
+         Cache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        url.path, url.host
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = Vec::new();
+    stream.take(MAX_RESPONSE as u64).read_to_end(&mut response)?;
+    Ok(response)
+}
+
+/// The expected answer means full connectivity. A redirect is a portal
+/// sending the browser to its login page; any other answer is a portal
+/// serving that page in place of the real one, at the probe URL itself.
+pub fn classify(response: &[u8], url: &ProbeUrl, config: &ConnectivityProbeConfig) -> ProbeOutcome {
+    let text = String::from_utf8_lossy(response);
+    let (head, body) = text.split_once("\r\n\r\n").unwrap_or((&text, ""));
+    let mut lines = head.lines();
+    let status: Option<u16> = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok());
+    let Some(status) = status else {
+        return ProbeOutcome {
+            state: ConnectivityState::Limited,
+            portal_url: None,
+        };
+    };
+
+    let body_matches = config
+        .expected_body
+        .as_deref()
+        .map_or(true, |expected| body.contains(expected));
+    if status == config.expected_status && body_matches {
+        return ProbeOutcome {
+            state: ConnectivityState::Full,
+            portal_url: None,
+        };
+    }
+
+    let location = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("location"))
+        .map(|(_, value)| value.trim().to_string());
+    let portal_url = match location {
+        Some(location) if (300..400).contains(&status) => {
+            if location.starts_with('/') {
+                format!("{}{}", url.origin(), location)
+            } else {
+                location
+            }
+        }
+        _ => config.url.clone(),
+    };
+    ProbeOutcome {
+        state: ConnectivityState::Portal,
+        portal_url: Some(portal_url),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Serves `response` once on a port of 127.0.0.1 and returns a probe
+    /// configuration pointing at it.
+    fn serve(response: &'static str) -> ConnectivityProbeConfig {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                // Closing with part of the request unread would reset the
+                // connection under the response.
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut chunk) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&chunk[..read]),
+                    }
+                }
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        ConnectivityProbeConfig {
+            url: format!("http://127.0.0.1:{}/check", port),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn expected_answer_is_full_connectivity() {
+        let config = serve("HTTP/1.1 200 OK\r\nContent-Length: 25\r\n\r\nNetworkManager is online\n");
+        let outcome = probe(&config).unwrap();
+        assert_eq!(outcome.state, ConnectivityState::Full);
+        assert_eq!(outcome.portal_url, None);
+    }
+
+    #[test]
+    fn redirect_points_at_the_portal() {
+        let config = serve("HTTP/1.1 302 Found\r\nLocation: /login?next=1\r\nContent-Length: 0\r\n\r\n");
+        let port = ProbeUrl::parse(&config.url).unwrap().port;
+        let outcome = probe(&config).unwrap();
+        assert_eq!(outcome.state, ConnectivityState::Portal);
+        assert_eq!(
+            outcome.portal_url,
+            Some(format!("http://127.0.0.1:{}/login?next=1", port))
+        );
+
+        let config = serve("HTTP/1.1 200 OK\r\n\r\n<html>Welcome to Cafe Wi-Fi</html>");
+        let outcome = probe(&config).unwrap();
+        assert_eq!(outcome.state, ConnectivityState::Portal);
+        assert_eq!(outcome.portal_url, Some(config.url.clone()));
+    }
+
+    #[test]
+    fn unreachable_server_is_limited() {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let config = ConnectivityProbeConfig {
+            url: format!("http://127.0.0.1:{}/", port),
+            ..Default::default()
+        };
+        assert_eq!(probe(&config).unwrap().state, ConnectivityState::Limited);
+    }
+
+    #[test]
+    fn probe_urls_are_plain_http() {
+        let url = ProbeUrl::parse("http://[::1]:8080/a/b").unwrap();
+        assert_eq!((url.host.as_str(), url.port, url.path.as_str()), ("[::1]", 8080, "/a/b"));
+        let url = ProbeUrl::parse("http://example.com").unwrap();
+        assert_eq!((url.port, url.path.as_str()), (80, "/"));
+        assert!(ProbeUrl::parse("https://example.com/").is_err());
+        assert!(ProbeUrl::parse("http://:80/").is_err());
+    }
+}
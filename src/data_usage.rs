@@ -0,0 +1,708 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::error::{NetworkError, Result};
+use crate::models::{DailyUsage, DataQuota, DataQuotaWarningEvent, DataUsage, DataUsageRange};
+use crate::NetworkManagerState;
+
+/// Name of the ledger inside the app data dir.
+pub const LEDGER_FILE: &str = "data_usage.json";
+
+/// Time between two readings of the counters. Whatever moves between the
+/// last reading and the app closing is lost, so this is also the most a
+/// session can go uncounted.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Days of history kept: a little over a year, so this month can be set
+/// against the same month last year.
+const RETENTION_DAYS: i64 = 400;
+
+/// An active connection whose traffic the ledger counts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerConnection {
+    pub uuid: String,
+    pub name: String,
+    /// Set for Wi-Fi only.
+    pub ssid: Option<String>,
+    /// The interface whose counters carry the connection's traffic.
+    pub interface: String,
+}
+
+impl LedgerConnection {
+    /// The key its usage is filed under: the SSID for Wi-Fi, so a network
+    /// keeps its history when its profile is deleted and recreated, and
+    /// the UUID for the rest.
+    pub fn key(&self) -> &str {
+        self.ssid.as_deref().unwrap_or(&self.uuid)
+    }
+}
+
+/// Years a date may fall in. Older or later ones are typos, and four
+/// digits keep the `YYYY-MM-DD` keys of the ledger sorting by date.
+const YEARS: std::ops::RangeInclusive<i64> = 1970..=9999;
+
+/// A local calendar day, counted from 1970-01-01.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Day(i64);
+
+impl Day {
+    /// Today in the local time zone, the one the user's carrier bills in.
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or(0);
+        Self((seconds + local_utc_offset(seconds)).div_euclid(86_400))
+    }
+
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> Self {
+        // Howard Hinnant's days_from_civil, with years starting in March so
+        // the leap day is the last of the year.
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month_from_march = (i64::from(month) + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+        let day_of_era =
+            year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Self(era * 146_097 + day_of_era - 719_468)
+    }
+
+    pub fn ymd(self) -> (i64, u32, u32) {
+        let days = self.0 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        } as u32;
+        let year = year_of_era + era * 400;
+        (if month <= 2 { year + 1 } else { year }, month, day)
+    }
+
+    /// Reads `YYYY-MM-DD`, rejecting dates that do not exist.
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = || NetworkError::InvalidSettings(format!("invalid date: {}", text));
+        let mut parts = text.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let year: i64 = next()?.parse().map_err(|_| invalid())?;
+        let month: u32 = next()?.parse().map_err(|_| invalid())?;
+        let day: u32 = next()?.parse().map_err(|_| invalid())?;
+        if !YEARS.contains(&year) {
+            return Err(invalid());
+        }
+        let parsed = Self::from_ymd(year, month, day);
+        if parsed.ymd() != (year, month, day) {
+            return Err(invalid());
+        }
+        Ok(parsed)
+    }
+
+    fn add_days(self, days: i64) -> Self {
+        Self(self.0 + days)
+    }
+}
+
+/// Seconds the local time zone is ahead of UTC at `seconds` past the
+/// epoch, daylight saving included; 0 when the C library cannot tell.
+fn local_utc_offset(seconds: i64) -> i64 {
+    let time = seconds as libc::time_t;
+    // SAFETY: `tm` is plain data, and `localtime_r` only writes to the
+    // one passed in.
+    let mut local: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut local) }.is_null() {
+        return 0;
+    }
+    local.tm_gmtoff as i64
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// First day of the billing period `today` falls in, for a period that
+/// starts on `reset_day` of each month.
+pub fn period_start(today: Day, reset_day: u8) -> Day {
+    let reset_day = u32::from(reset_day.clamp(1, 28));
+    let (year, month, day) = today.ymd();
+    if day >= reset_day {
+        Day::from_ymd(year, month, reset_day)
+    } else if month == 1 {
+        Day::from_ymd(year - 1, 12, reset_day)
+    } else {
+        Day::from_ymd(year, month - 1, reset_day)
+    }
+}
+
+/// Last day of the billing period that starts on `start`.
+fn period_end(start: Day) -> Day {
+    let (year, month, day) = start.ymd();
+    let next = if month == 12 {
+        Day::from_ymd(year + 1, 1, day)
+    } else {
+        Day::from_ymd(year, month + 1, day)
+    };
+    next.add_days(-1)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct DayUsage {
+    rx: u64,
+    tx: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct LedgerEntry {
+    uuid: String,
+    name: String,
+    /// Keyed by `YYYY-MM-DD`, which sorts by date.
+    #[serde(default)]
+    days: BTreeMap<String, DayUsage>,
+    #[serde(default)]
+    quota: Option<DataQuota>,
+    /// Thresholds already announced in the period starting on
+    /// `warned_period`, kept so a restart does not announce them again.
+    #[serde(default)]
+    warned: Vec<u8>,
+    #[serde(default)]
+    warned_period: Option<String>,
+}
+
+impl LedgerEntry {
+    fn total(&self, from: Day, to: Day) -> DayUsage {
+        self.days
+            .range(from.to_string()..=to.to_string())
+            .fold(DayUsage::default(), |sum, (_, day)| DayUsage {
+                rx: sum.rx.saturating_add(day.rx),
+                tx: sum.tx.saturating_add(day.tx),
+            })
+    }
+}
+
+/// Bytes per connection and day, with the quotas set on them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Ledger {
+    #[serde(default)]
+    connections: BTreeMap<String, LedgerEntry>,
+}
+
+impl Ledger {
+    /// Adds traffic to `connection` on `day`.
+    pub fn record(&mut self, connection: &LedgerConnection, day: Day, rx: u64, tx: u64) {
+        let key = connection.key();
+        // A quota set by UUID before a Wi-Fi network was first used moves
+        // to the SSID it is filed under.
+        if !self.connections.contains_key(key) {
+            if let Some(entry) = self.connections.remove(&connection.uuid) {
+                self.connections.insert(key.to_string(), entry);
+            }
+        }
+        let entry = self.connections.entry(key.to_string()).or_default();
+        entry.uuid = connection.uuid.clone();
+        entry.name = connection.name.clone();
+        let usage = entry.days.entry(day.to_string()).or_default();
+        usage.rx = usage.rx.saturating_add(rx);
+        usage.tx = usage.tx.saturating_add(tx);
+    }
+
+    /// The key of the entry `connection` names, by key (UUID or SSID),
+    /// profile UUID or profile name, in that order.
+    fn find_key(&self, connection: &str) -> Option<String> {
+        if self.connections.contains_key(connection) {
+            return Some(connection.to_string());
+        }
+        self.connections
+            .iter()
+            .find(|(_, entry)| entry.uuid == connection)
+            .or_else(|| self.connections.iter().find(|(_, entry)| entry.name == connection))
+            .map(|(key, _)| key.clone())
+    }
+
+    /// Sets or, with `None`, removes the quota of `connection`. A quota can
+    /// be set before the connection is first used, by UUID or SSID.
+    pub fn set_quota(&mut self, connection: &str, quota: Option<DataQuota>) -> Result<()> {
+        let quota = quota.map(validate_quota).transpose()?;
+        let key = self
+            .find_key(connection)
+            .unwrap_or_else(|| connection.to_string());
+        let entry = self.connections.entry(key).or_default();
+        if entry.uuid.is_empty() && entry.name.is_empty() {
+            entry.name = connection.to_string();
+        }
+        entry.quota = quota;
+        entry.warned.clear();
+        entry.warned_period = None;
+        Ok(())
+    }
+
+    /// Usage of every connection, or of the one `connection` names, over
+    /// `range`. A connection that never moved a byte gives an empty list.
+    pub fn usage(
+        &self,
+        range: &DataUsageRange,
+        connection: Option<&str>,
+        today: Day,
+    ) -> Result<Vec<DataUsage>> {
+        let key = connection.and_then(|connection| self.find_key(connection));
+        if connection.is_some() && key.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let mut usage = Vec::new();
+        for (entry_key, entry) in &self.connections {
+            if key.as_ref().is_some_and(|key| key != entry_key) {
+                continue;
+            }
+            let (from, to) = match range {
+                DataUsageRange::Today => (today, today),
+                DataUsageRange::Month => {
+                    let reset_day = entry.quota.as_ref().map(|quota| quota.reset_day).unwrap_or(1);
+                    let start = period_start(today, reset_day);
+                    (start, period_end(start))
+                }
+                DataUsageRange::LastDays { days } => {
+                    (today.add_days(1 - i64::from((*days).max(1))), today)
+                }
+                DataUsageRange::Between { from, to } => {
+                    let (from, to) = (Day::parse(from)?, Day::parse(to)?);
+                    if from > to {
+                        return Err(NetworkError::InvalidSettings(
+                            "range starts after it ends".to_string(),
+                        ));
+                    }
+                    (from, to)
+                }
+            };
+
+            let total = entry.total(from, to);
+            usage.push(DataUsage {
+                connection: entry_key.clone(),
+                uuid: entry.uuid.clone(),
+                name: entry.name.clone(),
+                from: from.to_string(),
+                to: to.to_string(),
+                rx_bytes: total.rx,
+                tx_bytes: total.tx,
+                total_bytes: total.rx.saturating_add(total.tx),
+                days: entry
+                    .days
+                    .range(from.to_string()..=to.to_string())
+                    .map(|(date, day)| DailyUsage {
+                        date: date.clone(),
+                        rx_bytes: day.rx,
+                        tx_bytes: day.tx,
+                    })
+                    .collect(),
+                quota: entry.quota.clone(),
+            });
+        }
+        Ok(usage)
+    }
+
+    /// Thresholds crossed since the last check, each reported once per
+    /// billing period.
+    pub fn check_quotas(&mut self, today: Day) -> Vec<DataQuotaWarningEvent> {
+        let mut warnings = Vec::new();
+        for (key, entry) in &mut self.connections {
+            let Some(quota) = entry.quota.clone() else {
+                continue;
+            };
+            let start = period_start(today, quota.reset_day);
+            if entry.warned_period.as_deref() != Some(start.to_string().as_str()) {
+                entry.warned.clear();
+                entry.warned_period = Some(start.to_string());
+            }
+
+            let total = entry.total(start, today);
+            let used = total.rx.saturating_add(total.tx);
+            for &threshold in &quota.thresholds {
+                let crossed = u128::from(used) * 100
+                    >= u128::from(quota.limit_bytes) * u128::from(threshold);
+                if crossed && !entry.warned.contains(&threshold) {
+                    entry.warned.push(threshold);
+                    warnings.push(DataQuotaWarningEvent {
+                        connection: key.clone(),
+                        name: entry.name.clone(),
+                        threshold,
+                        used_bytes: used,
+                        limit_bytes: quota.limit_bytes,
+                        period_start: start.to_string(),
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Forgets days past the retention window, and connections left with
+    /// neither history nor quota.
+    pub fn prune(&mut self, today: Day) {
+        let cutoff = today.add_days(-RETENTION_DAYS).to_string();
+        for entry in self.connections.values_mut() {
+            entry.days.retain(|date, _| *date >= cutoff);
+        }
+        self.connections
+            .retain(|_, entry| !entry.days.is_empty() || entry.quota.is_some());
+    }
+}
+
+fn validate_quota(mut quota: DataQuota) -> Result<DataQuota> {
+    if quota.limit_bytes == 0 {
+        return Err(NetworkError::InvalidSettings(
+            "quota limit must be above 0".to_string(),
+        ));
+    }
+    if !(1..=28).contains(&quota.reset_day) {
+        return Err(NetworkError::InvalidSettings(format!(
+            "quota reset day must be 1 to 28, got {}",
+            quota.reset_day
+        )));
+    }
+    if quota.thresholds.contains(&0) {
+        return Err(NetworkError::InvalidSettings(
+            "quota thresholds must be above 0%".to_string(),
+        ));
+    }
+    quota.thresholds.sort_unstable();
+    quota.thresholds.dedup();
+    Ok(quota)
+}
+
+/// The ledger together with the file it lives in.
+pub struct DataUsageLedger {
+    path: PathBuf,
+    pub ledger: Ledger,
+}
+
+impl DataUsageLedger {
+    /// Loads the ledger at `path`, or starts an empty one. A file that does
+    /// not parse is moved aside rather than overwritten.
+    pub fn open(path: PathBuf) -> Self {
+        let ledger = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                log::warn!("data usage ledger {} is unreadable: {}", path.display(), e);
+                let _ = fs::rename(&path, path.with_extension("json.corrupt"));
+                Ledger::default()
+            }),
+            Err(_) => Ledger::default(),
+        };
+        Self { path, ledger }
+    }
+
+    /// Writes the ledger through a temporary file, so a crash halfway
+    /// leaves the previous version in place.
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = serde_json::to_string(&self.ledger)
+            .map_err(|e| NetworkError::OperationError(e.to_string()))?;
+        let temporary = self.path.with_extension("json.tmp");
+        fs::write(&temporary, text)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Counter readings the next poll is measured against, per interface,
+/// with the connection that owned the interface then.
+#[derive(Default)]
+pub struct Accountant {
+    baselines: HashMap<String, (String, u64, u64)>,
+}
+
+impl Accountant {
+    /// Files what each connection moved since the previous reading and
+    /// tells whether anything was added. The first reading of an
+    /// interface, or the first after another connection took it over, is
+    /// only a baseline. A counter that went backwards was reset and counts
+    /// from zero.
+    pub fn observe(
+        &mut self,
+        ledger: &mut Ledger,
+        readings: &[(LedgerConnection, u64, u64)],
+        day: Day,
+    ) -> bool {
+        self.baselines
+            .retain(|interface, _| readings.iter().any(|(c, _, _)| &c.interface == interface));
+
+        let mut changed = false;
+        for (connection, rx, tx) in readings {
+            let previous = self.baselines.insert(
+                connection.interface.clone(),
+                (connection.uuid.clone(), *rx, *tx),
+            );
+            let Some((uuid, previous_rx, previous_tx)) = previous else {
+                continue;
+            };
+            if uuid != connection.uuid {
+                continue;
+            }
+            let rx = crate::network_stats::counter_delta(previous_rx, *rx);
+            let tx = crate::network_stats::counter_delta(previous_tx, *tx);
+            if rx > 0 || tx > 0 {
+                ledger.record(connection, day, rx, tx);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Starts counting traffic into the ledger in `NetworkManagerState`. The
+/// thread lives as long as the app.
+pub fn spawn<R: Runtime>(app: AppHandle<R>) {
+    std::thread::spawn(move || {
+        let mut accountant = Accountant::default();
+        while let Some(state) = app.try_state::<NetworkManagerState<R>>() {
+            if let Err(e) = poll(&app, &state, &mut accountant) {
+                log::debug!("data usage poll failed: {}", e);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+fn poll<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &NetworkManagerState<R>,
+    accountant: &mut Accountant,
+) -> Result<()> {
+    let connections = {
+        let manager = state.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.data_usage_connections()?,
+            _none => return Err(NetworkError::NotInitialized),
+        }
+    };
+    let root = Path::new(crate::network_stats::SYSFS_NET_ROOT);
+    let readings: Vec<(LedgerConnection, u64, u64)> = connections
+        .into_iter()
+        .filter_map(|connection| {
+            let counters =
+                crate::network_stats::read_interface_counters(root, &connection.interface).ok()?;
+            Some((connection, counters.rx_bytes, counters.tx_bytes))
+        })
+        .collect();
+
+    let today = Day::today();
+    let warnings = {
+        let mut store = state.data_usage.write().map_err(|_| NetworkError::LockError)?;
+        let Some(store) = store.as_mut() else {
+            return Ok(());
+        };
+        let recorded = accountant.observe(&mut store.ledger, &readings, today);
+        let warnings = store.ledger.check_quotas(today);
+        if recorded || !warnings.is_empty() {
+            store.ledger.prune(today);
+            store.save()?;
+        }
+        warnings
+    };
+    for warning in warnings {
+        let _ = app.emit("data-quota-warning", &warning);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wifi(uuid: &str, ssid: &str, interface: &str) -> LedgerConnection {
+        LedgerConnection {
+            uuid: uuid.to_string(),
+            name: ssid.to_string(),
+            ssid: Some(ssid.to_string()),
+            interface: interface.to_string(),
+        }
+    }
+
+    fn day(text: &str) -> Day {
+        Day::parse(text).unwrap()
+    }
+
+    #[test]
+    fn days_round_trip_through_the_calendar() {
+        assert_eq!(Day::from_ymd(1970, 1, 1), Day(0));
+        assert_eq!(day("2024-02-29").to_string(), "2024-02-29");
+        assert_eq!(day("2024-03-01"), day("2024-02-29").add_days(1));
+        assert!(Day::parse("2023-02-29").is_err());
+        assert!(Day::parse("2024-13-01").is_err());
+        assert!(Day::parse("yesterday").is_err());
+    }
+
+    #[test]
+    fn dates_outside_the_supported_years_are_rejected() {
+        assert!(Day::parse("100000000000000000-01-01").is_err());
+        assert!(Day::parse("9223372036854775807-12-31").is_err());
+        assert!(Day::parse("1969-12-31").is_err());
+        assert!(Day::parse("10000-01-01").is_err());
+        assert_eq!(day("9999-12-31").to_string(), "9999-12-31");
+
+        let mut ledger = Ledger::default();
+        ledger.record(&wifi("uuid-home", "Home", "wlan0"), day("2024-05-01"), 1, 1);
+        let range = DataUsageRange::Between {
+            from: "2024-01-01".to_string(),
+            to: "100000000000000000-01-01".to_string(),
+        };
+        assert!(ledger.usage(&range, None, day("2024-05-01")).is_err());
+    }
+
+    #[test]
+    fn billing_periods_start_on_the_reset_day() {
+        assert_eq!(period_start(day("2024-03-20"), 15), day("2024-03-15"));
+        assert_eq!(period_start(day("2024-03-10"), 15), day("2024-02-15"));
+        assert_eq!(period_start(day("2024-01-10"), 15), day("2023-12-15"));
+        assert_eq!(period_end(day("2024-02-15")), day("2024-03-14"));
+        assert_eq!(period_end(day("2024-12-01")), day("2024-12-31"));
+    }
+
+    #[test]
+    fn counters_are_filed_per_connection_and_survive_resets() {
+        let mut ledger = Ledger::default();
+        let mut accountant = Accountant::default();
+        let home = wifi("uuid-home", "Home", "wlan0");
+        let today = day("2024-05-02");
+
+        assert!(!accountant.observe(&mut ledger, &[(home.clone(), 1_000, 100)], today));
+        assert!(accountant.observe(&mut ledger, &[(home.clone(), 1_500, 300)], today));
+        // The driver was reloaded and the counters started over.
+        assert!(accountant.observe(&mut ledger, &[(home.clone(), 50, 10)], today));
+
+        // Another network on the same interface only takes a baseline.
+        let cafe = wifi("uuid-cafe", "Cafe", "wlan0");
+        assert!(!accountant.observe(&mut ledger, &[(cafe.clone(), 9_000, 900)], today));
+        assert!(accountant.observe(&mut ledger, &[(cafe, 9_100, 950)], today));
+
+        let usage = ledger.usage(&DataUsageRange::Today, Some("Home"), today).unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!((usage[0].rx_bytes, usage[0].tx_bytes), (550, 210));
+        assert_eq!(usage[0].uuid, "uuid-home");
+
+        let usage = ledger.usage(&DataUsageRange::Today, None, today).unwrap();
+        assert_eq!(usage.len(), 2);
+        assert!(ledger.usage(&DataUsageRange::Today, Some("nope"), today).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ranges_select_days() {
+        let mut ledger = Ledger::default();
+        let home = wifi("uuid-home", "Home", "wlan0");
+        for (date, bytes) in [("2024-04-30", 1), ("2024-05-01", 10), ("2024-05-03", 100)] {
+            ledger.record(&home, day(date), bytes, 0);
+        }
+        let today = day("2024-05-03");
+        let total = |range: DataUsageRange| ledger.usage(&range, None, today).unwrap()[0].rx_bytes;
+
+        assert_eq!(total(DataUsageRange::Today), 100);
+        assert_eq!(total(DataUsageRange::Month), 110);
+        assert_eq!(total(DataUsageRange::LastDays { days: 3 }), 110);
+        assert_eq!(
+            total(DataUsageRange::Between {
+                from: "2024-04-01".to_string(),
+                to: "2024-05-01".to_string(),
+            }),
+            11
+        );
+        assert!(ledger
+            .usage(
+                &DataUsageRange::Between {
+                    from: "2024-05-02".to_string(),
+                    to: "2024-05-01".to_string(),
+                },
+                None,
+                today,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn quota_thresholds_warn_once_per_period() {
+        let mut ledger = Ledger::default();
+        let home = wifi("uuid-home", "Home", "wlan0");
+        // Set by UUID before the network was ever used.
+        ledger
+            .set_quota(
+                "uuid-home",
+                Some(DataQuota {
+                    limit_bytes: 1_000,
+                    reset_day: 10,
+                    thresholds: vec![100, 80, 80],
+                }),
+            )
+            .unwrap();
+
+        let today = day("2024-05-12");
+        ledger.record(&home, day("2024-05-09"), 700, 0);
+        assert_eq!(ledger.connections.len(), 1);
+        assert_eq!(
+            ledger.connections["Home"].quota.as_ref().map(|quota| quota.thresholds.clone()),
+            Some(vec![80, 100])
+        );
+        ledger.record(&home, day("2024-05-11"), 500, 0);
+        assert!(ledger.check_quotas(today).is_empty());
+
+        ledger.record(&home, today, 300, 0);
+        let warnings = ledger.check_quotas(today);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].threshold, 80);
+        assert_eq!(warnings[0].period_start, "2024-05-10");
+        assert!(ledger.check_quotas(today).is_empty());
+
+        ledger.record(&home, today, 200, 0);
+        assert_eq!(ledger.check_quotas(today)[0].threshold, 100);
+
+        // A new period warns again.
+        let next = day("2024-06-10");
+        ledger.record(&home, next, 900, 0);
+        assert_eq!(ledger.check_quotas(next).len(), 1);
+
+        let bad = DataQuota {
+            limit_bytes: 1,
+            reset_day: 31,
+            thresholds: Vec::new(),
+        };
+        assert!(ledger.set_quota("Home", Some(bad)).is_err());
+    }
+
+    #[test]
+    fn ledger_persists_and_prunes() {
+        let dir = std::env::temp_dir().join(format!("data-usage-{}", uuid::Uuid::new_v4()));
+        let path = dir.join(LEDGER_FILE);
+        let home = wifi("uuid-home", "Home", "wlan0");
+
+        let mut store = DataUsageLedger::open(path.clone());
+        store.ledger.record(&home, day("2023-01-01"), 5, 5);
+        store.ledger.record(&home, day("2024-05-01"), 10, 20);
+        store.save().unwrap();
+
+        let mut store = DataUsageLedger::open(path.clone());
+        store.ledger.prune(day("2024-05-01"));
+        let usage = store
+            .ledger
+            .usage(&DataUsageRange::LastDays { days: 1000 }, None, day("2024-05-01"))
+            .unwrap();
+        assert_eq!(usage[0].total_bytes, 30);
+        assert_eq!(usage[0].days.len(), 1);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(DataUsageLedger::open(path.clone()).ledger.connections.is_empty());
+        assert!(path.with_extension("json.corrupt").exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -9,6 +9,22 @@ use crate::error::Result;
 use crate::models::*;
 use crate::nm_helpers::NetworkManagerHelpers;
 
+/// A ModemManager object path with the properties of each interface it has.
+type ModemObject = (String, HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>);
+
+/// Longest an activation is followed. NetworkManager gives up on 802.1X
+/// and DHCP well before this.
+const ACTIVATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// A saved profile that makes an interface a port of a bridge or bond.
+struct PortProfile {
+    path: zbus::zvariant::OwnedObjectPath,
+    uuid: String,
+    interface: String,
+    /// The master as `connection.master` names it, by UUID or interface.
+    master: String,
+}
+
 impl<R: Runtime> VSKNetworkManager<'static, R> {
     fn vpn_type_from_service_type(service_type: &str) -> VpnType {
         match service_type {
@@ -66,6 +82,57 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         v.downcast_ref::<bool>().ok()
     }
 
+    fn extract_u32_from_dict(
+        dict: &HashMap<String, zbus::zvariant::OwnedValue>,
+        key: &str,
+    ) -> Option<u32> {
+        let value = dict.get(key)?;
+        let v: &zbus::zvariant::Value<'_> = value;
+        v.downcast_ref::<u32>().ok()
+    }
+
+    /// An object path property, with NetworkManager's `/` placeholder read as
+    /// "none".
+    fn extract_path_from_dict(
+        dict: &HashMap<String, zbus::zvariant::OwnedValue>,
+        key: &str,
+    ) -> Option<zbus::zvariant::OwnedObjectPath> {
+        let value = dict.get(key)?;
+        match value.downcast_ref::<Value>() {
+            Ok(Value::ObjectPath(path)) if path.as_str() != "/" => Some(path.into()),
+            _ => None,
+        }
+    }
+
+    fn extract_paths_from_dict(
+        dict: &HashMap<String, zbus::zvariant::OwnedValue>,
+        key: &str,
+    ) -> Vec<zbus::zvariant::OwnedObjectPath> {
+        match dict.get(key).map(|value| value.downcast_ref::<Value>()) {
+            Some(Ok(Value::Array(arr))) => arr
+                .iter()
+                .filter_map(|v| match v {
+                    Value::ObjectPath(path) => Some(path.to_owned().into()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// All properties of one D-Bus interface on a NetworkManager object.
+    fn get_all_properties(
+        &self,
+        path: &zbus::zvariant::OwnedObjectPath,
+        interface: &'static str,
+    ) -> Result<HashMap<String, zbus::zvariant::OwnedValue>> {
+        let props = zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
+            .destination("org.freedesktop.NetworkManager")?
+            .path(path.as_str())?
+            .build()?;
+        Ok(props.get_all(Some(InterfaceName::from_static_str_unchecked(interface)).into())?)
+    }
+
     fn string_map_from_section(
         settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>,
         section_name: &str,
@@ -137,6 +204,72 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         Err(crate::error::NetworkError::VpnProfileNotFound(uuid.to_string()))
     }
 
+    /// Settings object path for a saved connection of any type.
+    fn connection_path_by_uuid(&self, uuid: &str) -> Result<zbus::zvariant::OwnedObjectPath> {
+        let settings_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager/Settings",
+            "org.freedesktop.NetworkManager.Settings",
+        )?;
+
+        settings_proxy
+            .call("GetConnectionByUuid", &(uuid,))
+            .map_err(|_| crate::error::NetworkError::ConnectionNotFound(uuid.to_string()))
+    }
+
+    /// Rewrites a saved connection in place.
+    ///
+    /// `edit` gets every section the profile has now, so whatever it does not
+    /// touch is written back unchanged. Secrets are not part of `GetSettings`
+    /// and NetworkManager keeps the stored ones when they are left out.
+    fn update_connection_settings<F>(&self, uuid: &str, edit: F) -> Result<()>
+    where
+        F: for<'a> FnOnce(&mut HashMap<String, HashMap<String, Value<'a>>>) -> Result<()>,
+    {
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let existing_settings = self.get_connection_settings(&conn_path)?;
+
+        let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+        for (section_name, dict) in &existing_settings {
+            let mut section_map: HashMap<String, Value> = HashMap::new();
+            for (k, v) in dict {
+                if let Ok(val) = v.downcast_ref::<Value>() {
+                    section_map.insert(k.clone(), val);
+                }
+            }
+            settings.insert(section_name.clone(), section_map);
+        }
+
+        edit(&mut settings)?;
+
+        let conn_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            conn_path.as_str(),
+            "org.freedesktop.NetworkManager.Settings.Connection",
+        )?;
+        let _result: HashMap<String, zbus::zvariant::OwnedValue> = conn_proxy.call(
+            "Update2",
+            &(
+                settings,
+                crate::nm_constants::UPDATE2_FLAG_TO_DISK,
+                HashMap::<String, Value>::new(),
+            ),
+        )?;
+        Ok(())
+    }
+
+    /// The section that holds a connection's link-layer settings, for the
+    /// types where a MAC address can be chosen.
+    fn link_section_for(connection_type: &str) -> Option<&'static str> {
+        match connection_type {
+            "802-11-wireless" => Some("802-11-wireless"),
+            "802-3-ethernet" => Some("802-3-ethernet"),
+            _ => None,
+        }
+    }
+
     fn vpn_profile_from_settings(
         &self,
         settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>,
@@ -183,6 +316,17 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         }
     }
 
+    /// Mobile broadband icon for a ModemManager signal quality.
+    fn get_cellular_icon(strength: u8) -> String {
+        match strength {
+            0 => "network-cellular-signal-none-symbolic".to_string(),
+            1..=25 => "network-cellular-signal-weak-symbolic".to_string(),
+            26..=50 => "network-cellular-signal-ok-symbolic".to_string(),
+            51..=75 => "network-cellular-signal-good-symbolic".to_string(),
+            _ => "network-cellular-signal-excellent-symbolic".to_string(),
+        }
+    }
+
     /// The icon for anything that is not Wi-Fi.
     ///
     /// Ethernet is one of these, not all of them: a VPN tunnel, a mobile modem
@@ -196,6 +340,7 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         match connection_type {
             "Ethernet" => "network-wired-symbolic".to_string(),
             "Modem" => "network-cellular-connected-symbolic".to_string(),
+            "Bluetooth" => "bluetooth-active-symbolic".to_string(),
             "VPN" | "WireGuard" => "network-vpn-symbolic".to_string(),
             _ => "network-transmit-receive-symbolic".to_string(),
         }
@@ -242,6 +387,54 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         }
     }
 
+    /// NetworkManager's last connectivity verdict, without checking again.
+    fn connectivity_state(&self) -> ConnectivityState {
+        let state = self
+            .proxy
+            .get(
+                InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
+                "Connectivity",
+            )
+            .ok()
+            .and_then(|value| value.downcast_ref::<u32>().ok())
+            .unwrap_or(0);
+        NetworkManagerHelpers::connectivity_from_nm(state)
+    }
+
+    /// Whether NetworkManager checks connectivity at all: it has a check
+    /// URI configured and the check is not switched off. Versions before
+    /// 1.10 lack both properties and always check when configured to.
+    pub fn connectivity_check_enabled(&self) -> bool {
+        let flag = |name: &str| {
+            self.proxy
+                .get(
+                    InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
+                    name,
+                )
+                .ok()
+                .and_then(|value| value.downcast_ref::<bool>().ok())
+                .unwrap_or(true)
+        };
+        flag("ConnectivityCheckAvailable") && flag("ConnectivityCheckEnabled")
+    }
+
+    /// Has NetworkManager check connectivity now and waits for the verdict.
+    pub fn check_connectivity(&self) -> Result<ConnectivityState> {
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+        let state: u32 = nm_proxy.call("CheckConnectivity", &())?;
+        Ok(NetworkManagerHelpers::connectivity_from_nm(state))
+    }
+
+    /// Whether anything carries the default route.
+    pub fn has_primary_connection(&self) -> bool {
+        self.primary_connection_path().is_some()
+    }
+
     pub fn get_current_network_state(&self) -> Result<NetworkInfo> {
         // Get active connections
         let active_connections_variant = self.proxy.get(
@@ -326,6 +519,7 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                             Ok(zbus::zvariant::Value::U32(device_type)) => match device_type {
                                 1 => "Ethernet".to_string(),
                                 2 => "WiFi".to_string(),
+                                5 => "Bluetooth".to_string(),
                                 8 => "Modem".to_string(),
                                 16 => "VPN".to_string(),
                                 29 => "WireGuard".to_string(),
@@ -338,13 +532,24 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                         let mut network_info = NetworkInfo {
                             name: "Unknown".to_string(),
                             ssid: "Unknown".to_string(),
+                            ssid_hex: String::new(),
+                            uuid: None,
                             connection_type: connection_type_str.clone(),
                             icon: "network-offline-symbolic".to_string(),
                             ip_address: "0.0.0.0".to_string(),
+                            ipv4_addresses: Vec::new(),
+                            ipv6_addresses: Vec::new(),
+                            ip4_connectivity: ConnectivityState::Unknown,
+                            ip6_connectivity: ConnectivityState::Unknown,
+                            connectivity: ConnectivityState::Unknown,
                             mac_address: "00:00:00:00:00:00".to_string(),
+                            permanent_mac_address: None,
                             signal_strength: 0,
                             security_type: WiFiSecurityType::None,
                             is_connected: is_connected && NetworkManagerHelpers::has_internet_connectivity(&self.proxy)?,
+                            metered: MeteredState::Unknown,
+                            wired: None,
+                            activation: None,
                         };
 
                         let hw_address_variant = device_properties_proxy.get(
@@ -359,6 +564,38 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                             _ => "00:00:00:00:00:00".to_string(),
                         };
 
+                        // HwAddress is whatever the connection's MAC policy put
+                        // on the device; the burnt-in address lives on the
+                        // type-specific interface, and only some types have one.
+                        let hw_interface = match connection_type_str.as_str() {
+                            "WiFi" => Some("org.freedesktop.NetworkManager.Device.Wireless"),
+                            "Ethernet" => Some("org.freedesktop.NetworkManager.Device.Wired"),
+                            _ => None,
+                        };
+                        if let Some(hw_interface) = hw_interface {
+                            if let Ok(perm) = device_properties_proxy.get(
+                                InterfaceName::from_static_str_unchecked(hw_interface),
+                                "PermHwAddress",
+                            ) {
+                                if let Ok(Value::Str(s)) = perm.downcast_ref() {
+                                    if !s.is_empty() {
+                                        network_info.permanent_mac_address = Some(s.to_string());
+                                    }
+                                }
+                            }
+                        }
+
+                        if let Ok(uuid) = properties_proxy.get(
+                            InterfaceName::from_static_str_unchecked(
+                                "org.freedesktop.NetworkManager.Connection.Active",
+                            ),
+                            "Uuid",
+                        ) {
+                            if let Ok(Value::Str(s)) = uuid.downcast_ref() {
+                                network_info.uuid = Some(s.to_string());
+                            }
+                        }
+
                         // For WiFi networks, get additional details
                         if connection_type_str == "WiFi" {
                             // Get active access point
@@ -401,10 +638,14 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                                     "Ssid",
                                 )?;
 
-                                network_info.ssid = match ssid_variant.downcast_ref() {
-                                    Ok(v) => NetworkManagerHelpers::ssid_from_value(&v),
-                                    _ => "Unknown".to_string(),
+                                let ssid_bytes = match ssid_variant.downcast_ref() {
+                                    Ok(v) => NetworkManagerHelpers::ssid_bytes_from_value(&v),
+                                    _ => None,
                                 };
+                                if let Some(bytes) = ssid_bytes {
+                                    network_info.ssid = NetworkManagerHelpers::ssid_display(&bytes);
+                                    network_info.ssid_hex = NetworkManagerHelpers::ssid_to_hex(&bytes);
+                                }
                                 network_info.name = network_info.ssid.clone();
 
                                 // Get signal strength
@@ -434,44 +675,82 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                             network_info.icon =
                                 Self::get_icon_for(&connection_type_str, network_info.is_connected);
                         }
-                        // Get IP configuration
-                        let ip4_config_path = device_properties_proxy.get(
-                            InterfaceName::from_static_str_unchecked(
-                                "org.freedesktop.NetworkManager.Device",
-                            ),
-                            "Ip4Config",
-                        )?;
-
-                        // Retrieve IP address if available
-                        if let Ok(zbus::zvariant::Value::ObjectPath(config_path)) =
-                            ip4_config_path.downcast_ref()
-                        {
-                            // Crear un proxy de propiedades para la configuración IP
-                            let ip_config_properties_proxy =
-                                zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
-                                    .destination("org.freedesktop.NetworkManager")?
-                                    .path(config_path)?
-                                    .build()?;
 
-                            let addresses_variant = ip_config_properties_proxy.get(
+                        // Modems and tethers have no SSID, so the profile
+                        // name stands in.
+                        if connection_type_str == "Modem" || connection_type_str == "Bluetooth" {
+                            if let Ok(id) = properties_proxy.get(
                                 InterfaceName::from_static_str_unchecked(
-                                    "org.freedesktop.NetworkManager.IP4Config",
+                                    "org.freedesktop.NetworkManager.Connection.Active",
                                 ),
-                                "Addresses",
-                            )?;
+                                "Id",
+                            ) {
+                                if let Ok(Value::Str(s)) = id.downcast_ref() {
+                                    network_info.name = s.to_string();
+                                }
+                            }
+                        }
 
-                            if let Ok(Value::Array(addr_arr)) = addresses_variant.downcast_ref() {
-                                if let Some(Value::Array(ip_tuple)) = addr_arr.first() {
-                                    if ip_tuple.len() >= 1 {
-                                        if let Value::U32(ip_int) = &ip_tuple[0] {
-                                            use std::net::Ipv4Addr;
-                                            network_info.ip_address =
-                                                Ipv4Addr::from((*ip_int).to_be()).to_string();
-                                        }
-                                    }
+                        // The signal of a modem comes from ModemManager,
+                        // which NetworkManager does not relay.
+                        if connection_type_str == "Modem" {
+                            let udi = device_properties_proxy
+                                .get(
+                                    InterfaceName::from_static_str_unchecked(
+                                        "org.freedesktop.NetworkManager.Device",
+                                    ),
+                                    "Udi",
+                                )
+                                .ok()
+                                .and_then(|v| v.downcast_ref::<String>().ok());
+                            if let Some((_, modem_interfaces)) = udi.and_then(|udi| {
+                                self.managed_modems().into_iter().find(|(path, _)| *path == udi)
+                            }) {
+                                if let Some(props) =
+                                    modem_interfaces.get("org.freedesktop.ModemManager1.Modem")
+                                {
+                                    network_info.signal_strength = Self::modem_signal_quality(props).0;
+                                }
+                                if network_info.is_connected {
+                                    network_info.icon =
+                                        Self::get_cellular_icon(network_info.signal_strength);
                                 }
                             }
                         }
+                        // Get IP configuration
+                        let ip_configs = device_properties_proxy.get_all(
+                            Some(InterfaceName::from_static_str_unchecked(
+                                "org.freedesktop.NetworkManager.Device",
+                            ))
+                            .into(),
+                        )?;
+
+                        if let Some(config_path) = Self::extract_path_from_dict(&ip_configs, "Ip4Config") {
+                            network_info.ipv4_addresses = self.ip_config_addresses(&config_path, false);
+                        }
+                        if let Some(config_path) = Self::extract_path_from_dict(&ip_configs, "Ip6Config") {
+                            network_info.ipv6_addresses = self.ip_config_addresses(&config_path, true);
+                        }
+                        if let Some(first) = network_info.ipv4_addresses.first() {
+                            network_info.ip_address = first.address.clone();
+                        }
+
+                        // Per-family results of NetworkManager's connectivity
+                        // probe: an IPv6-only network shows up here as full on
+                        // 6 and none on 4.
+                        network_info.ip4_connectivity = NetworkManagerHelpers::connectivity_from_nm(
+                            Self::extract_u32_from_dict(&ip_configs, "Ip4Connectivity").unwrap_or(0),
+                        );
+                        network_info.ip6_connectivity = NetworkManagerHelpers::connectivity_from_nm(
+                            Self::extract_u32_from_dict(&ip_configs, "Ip6Connectivity").unwrap_or(0),
+                        );
+                        network_info.metered = NetworkManagerHelpers::metered_from_nm(
+                            Self::extract_u32_from_dict(&ip_configs, "Metered").unwrap_or(0),
+                        );
+                        network_info.connectivity = self.connectivity_state();
+                        if connection_type_str == "Ethernet" {
+                            network_info.wired = self.wired_link_info(&device_path.clone().into()).ok();
+                        }
 
                         Ok(network_info)
                     }
@@ -482,9 +761,9 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         }
     }
 
-    /// Lightweight: get the SSID of the currently connected access point (if any).
+    /// Lightweight: get the raw SSID of the currently connected access point (if any).
     /// Avoids the ~10 D-Bus calls that `get_current_network_state()` makes.
-    fn connected_wifi_ssid(&self) -> Result<Option<String>> {
+    fn connected_wifi_ssid(&self) -> Result<Option<Vec<u8>>> {
         let devices = self.wireless_device_paths()?;
         for device_path in &devices {
             let wireless_props = zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
@@ -516,9 +795,10 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                 )?;
 
                 if let Ok(v) = ssid_variant.downcast_ref() {
-                    let ssid = NetworkManagerHelpers::ssid_from_value(&v);
-                    if !ssid.is_empty() && ssid != "Unknown" {
-                        return Ok(Some(ssid));
+                    if let Some(ssid) = NetworkManagerHelpers::ssid_bytes_from_value(&v) {
+                        if !ssid.is_empty() {
+                            return Ok(Some(ssid));
+                        }
                     }
                 }
             }
@@ -526,8 +806,21 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         Ok(None)
     }
 
-    /// List available WiFi networks
+    /// List available WiFi networks: one entry per SSID, strongest first.
     pub fn list_wifi_networks(&self) -> Result<Vec<NetworkInfo>> {
+        Ok(NetworkManagerHelpers::apply_wifi_query(
+            self.list_access_points()?,
+            &WifiNetworkQuery::default(),
+            None,
+        ))
+    }
+
+    /// Every access point every Wi-Fi device can see, in device order.
+    ///
+    /// Nothing is merged or dropped here: a mesh with six radios is six
+    /// entries. [`NetworkManagerHelpers::apply_wifi_query`] turns this into
+    /// the list a person wants to look at.
+    pub fn list_access_points(&self) -> Result<Vec<NetworkInfo>> {
         let devices_variant = self.proxy.get(
             InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
             "Devices",
@@ -601,10 +894,12 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                                             "Ssid",
                                         )?;
 
-                                        let ssid = match ssid_variant.downcast_ref() {
-                                            Ok(v) => NetworkManagerHelpers::ssid_from_value(&v),
-                                            _ => "Unknown".to_string(),
+                                        let ssid_bytes = match ssid_variant.downcast_ref() {
+                                            Ok(v) => NetworkManagerHelpers::ssid_bytes_from_value(&v)
+                                                .unwrap_or_default(),
+                                            _ => Vec::new(),
                                         };
+                                        let ssid = NetworkManagerHelpers::ssid_display(&ssid_bytes);
 
                                         let strength_variant = ap_props.get(
                                             InterfaceName::from_static_str_unchecked(
@@ -620,23 +915,32 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
 
                                         let security_type = NetworkManagerHelpers::detect_security_type(&ap_props)?;
 
-                                        let is_connected = connected_ssid.as_deref() == Some(ssid.as_str());
+                                        let is_connected = connected_ssid.as_deref() == Some(ssid_bytes.as_slice());
 
                                         let network_info = NetworkInfo {
                                             name: ssid.clone(),
                                             ssid,
+                                            ssid_hex: NetworkManagerHelpers::ssid_to_hex(&ssid_bytes),
+                                            uuid: None,
                                             connection_type: "wifi".to_string(),
                                             icon: Self::get_wifi_icon(strength),
                                             ip_address: "0.0.0.0".to_string(),
+                                            ipv4_addresses: Vec::new(),
+                                            ipv6_addresses: Vec::new(),
+                                            ip4_connectivity: ConnectivityState::Unknown,
+                                            ip6_connectivity: ConnectivityState::Unknown,
+                                            connectivity: ConnectivityState::Unknown,
                                             mac_address: mac_address.clone(),
+                                            permanent_mac_address: None,
                                             signal_strength: strength,
                                             security_type,
                                             is_connected,
+                                            metered: MeteredState::Unknown,
+                                            wired: None,
+                                            activation: None,
                                         };
 
-                                        if !networks.iter().any(|n: &NetworkInfo| n.ssid == network_info.ssid) {
-                                            networks.push(network_info);
-                                        }
+                                        networks.push(network_info);
                                     }
                                 }
                             }
@@ -646,110 +950,698 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
             }
         }
 
-        networks.sort_by(|a, b| b.signal_strength.cmp(&a.signal_strength));
         Ok(networks)
     }
 
-    /// Collect paths of all wireless (type=2) devices from NetworkManager
-    fn wireless_device_paths(&self) -> Result<Vec<zbus::zvariant::OwnedObjectPath>> {
-        let devices_variant = self.proxy.get(
-            InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
-            "Devices",
+    /// Paths of every device NetworkManager has realized, in its own order.
+    fn device_paths(&self) -> Result<Vec<zbus::zvariant::OwnedObjectPath>> {
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
         )?;
+        Ok(nm_proxy.call("GetDevices", &())?)
+    }
 
-        let mut paths = Vec::new();
-        if let Ok(zbus::zvariant::Value::Array(devices)) = devices_variant.downcast_ref() {
-            for device in devices.iter() {
-                if let zbus::zvariant::Value::ObjectPath(device_path) = device {
-                    let device_props =
-                        zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
-                            .destination("org.freedesktop.NetworkManager")?
-                            .path(device_path.as_str())?
-                            .build()?;
-
-                    let device_type_variant = device_props.get(
-                        InterfaceName::from_static_str_unchecked(
-                            "org.freedesktop.NetworkManager.Device",
-                        ),
-                        "DeviceType",
-                    )?;
-
-                    if let Ok(zbus::zvariant::Value::U32(device_type)) =
-                        device_type_variant.downcast_ref()
-                    {
-                        if device_type == 2u32 {
-                            let owned = zbus::zvariant::OwnedObjectPath::try_from(
-                                device_path.as_str(),
-                            )
-                            .unwrap();
-                            paths.push(owned);
-                        }
-                    }
-                }
+    /// Every network device with its state, hardware and connections.
+    pub fn list_devices(&self) -> Result<Vec<DeviceInfo>> {
+        let mut devices = Vec::new();
+        for device_path in self.device_paths()? {
+            match self.device_info(&device_path) {
+                Ok(device) => devices.push(device),
+                // A device can go away between GetDevices and the reads
+                // below (a USB adapter unplugged, a tunnel torn down).
+                Err(e) => log::debug!("skipping device {}: {}", device_path.as_str(), e),
             }
         }
-        Ok(paths)
+        Ok(devices)
     }
 
-    /// Request an explicit WiFi scan through NetworkManager and return a fresh list.
-    pub fn rescan_wifi(&self) -> Result<Vec<NetworkInfo>> {
-        let devices = self.wireless_device_paths()?;
+    fn device_info(&self, device_path: &zbus::zvariant::OwnedObjectPath) -> Result<DeviceInfo> {
+        let props = self.get_all_properties(device_path, "org.freedesktop.NetworkManager.Device")?;
 
-        if devices.is_empty() {
-            return Err(crate::error::NetworkError::OperationError(
-                "No wireless device available for scanning".to_string(),
-            ));
+        let device_type = NetworkManagerHelpers::device_type_from_nm(
+            Self::extract_u32_from_dict(&props, "DeviceType").unwrap_or(0),
+        );
+        let state_reason_code = match props.get("StateReason").map(|v| v.downcast_ref::<Value>()) {
+            Some(Ok(Value::Structure(reason))) => match reason.fields().get(1) {
+                Some(Value::U32(code)) => *code,
+                _ => 0,
+            },
+            _ => 0,
+        };
+        let non_empty = |key: &str| Self::extract_string_from_dict(&props, key).filter(|s| !s.is_empty());
+
+        let mut device = DeviceInfo {
+            interface: Self::extract_string_from_dict(&props, "Interface").unwrap_or_default(),
+            ip_interface: non_empty("IpInterface"),
+            device_type,
+            state: NetworkManagerHelpers::device_state_from_nm(
+                Self::extract_u32_from_dict(&props, "State").unwrap_or(0),
+            ),
+            state_reason: NetworkManagerHelpers::device_state_reason_name(state_reason_code)
+                .to_string(),
+            state_reason_code,
+            driver: non_empty("Driver"),
+            driver_version: non_empty("DriverVersion"),
+            firmware_version: non_empty("FirmwareVersion"),
+            hw_address: non_empty("HwAddress"),
+            permanent_hw_address: None,
+            mtu: Self::extract_u32_from_dict(&props, "Mtu").unwrap_or(0),
+            managed: Self::extract_bool_from_dict(&props, "Managed").unwrap_or(false),
+            autoconnect: Self::extract_bool_from_dict(&props, "Autoconnect").unwrap_or(false),
+            carrier: None,
+            speed_mbps: None,
+            active_connection: None,
+            available_connections: Vec::new(),
+        };
+
+        // Carrier, speed and the burnt-in address live on the type-specific
+        // interface. Missing properties are left as None.
+        let type_interface = match device_type {
+            DeviceType::Ethernet => Some("org.freedesktop.NetworkManager.Device.Wired"),
+            DeviceType::Wifi => Some("org.freedesktop.NetworkManager.Device.Wireless"),
+            DeviceType::Bond => Some("org.freedesktop.NetworkManager.Device.Bond"),
+            DeviceType::Bridge => Some("org.freedesktop.NetworkManager.Device.Bridge"),
+            DeviceType::Vlan => Some("org.freedesktop.NetworkManager.Device.Vlan"),
+            _ => None,
+        };
+        if let Some(type_interface) = type_interface {
+            if let Ok(type_props) = self.get_all_properties(device_path, type_interface) {
+                device.permanent_hw_address = Self::extract_string_from_dict(&type_props, "PermHwAddress")
+                    .filter(|s| !s.is_empty());
+                device.carrier = Self::extract_bool_from_dict(&type_props, "Carrier");
+                device.speed_mbps = match device_type {
+                    // Wired speed is already Mb/s; 0 means unknown.
+                    DeviceType::Ethernet => Self::extract_u32_from_dict(&type_props, "Speed")
+                        .filter(|speed| *speed > 0),
+                    // Bitrate is kbit/s.
+                    DeviceType::Wifi => Self::extract_u32_from_dict(&type_props, "Bitrate")
+                        .filter(|rate| *rate > 0)
+                        .map(|rate| rate / 1000),
+                    _ => None,
+                };
+            }
         }
 
-        for device_path in &devices {
-            let wireless_proxy = zbus::blocking::Proxy::new(
-                &self.connection,
-                "org.freedesktop.NetworkManager",
-                device_path.as_str(),
-                "org.freedesktop.NetworkManager.Device.Wireless",
-            )?;
+        if let Some(active_path) = Self::extract_path_from_dict(&props, "ActiveConnection") {
+            if let Ok(active) =
+                self.get_all_properties(&active_path, "org.freedesktop.NetworkManager.Connection.Active")
+            {
+                device.active_connection = Some(ConnectionRef {
+                    id: Self::extract_string_from_dict(&active, "Id").unwrap_or_default(),
+                    uuid: Self::extract_string_from_dict(&active, "Uuid").unwrap_or_default(),
+                    connection_type: Self::extract_string_from_dict(&active, "Type")
+                        .unwrap_or_default(),
+                });
+            }
+        }
 
-            let options: HashMap<String, zbus::zvariant::OwnedValue> = HashMap::new();
-            if wireless_proxy.call::<_, _, ()>("RequestScan", &(options,)).is_ok() {
-                return self.list_wifi_networks();
+        for conn_path in Self::extract_paths_from_dict(&props, "AvailableConnections") {
+            if let Ok(settings) = self.get_connection_settings(&conn_path) {
+                if let Some(connection) = Self::connection_ref_from_settings(&settings) {
+                    device.available_connections.push(connection);
+                }
             }
         }
 
-        Err(crate::error::NetworkError::OperationError(
-            "Failed to request WiFi scan on available wireless devices".to_string(),
-        ))
+        Ok(device)
     }
 
-    /// Connect to a WiFi network
-    pub fn connect_to_wifi(&self, config: WiFiConnectionConfig) -> Result<()> {
-        // Log connection attempt
-        log::debug!("connect_to_wifi called: ssid='{}' security={:?} username={:?}",
-                  config.ssid, config.security_type, config.username);
+    /// `AddressData` of an IP4Config or IP6Config object.
+    fn ip_config_addresses(
+        &self,
+        config_path: &zbus::zvariant::OwnedObjectPath,
+        ipv6: bool,
+    ) -> Vec<IpAddressInfo> {
+        let interface = if ipv6 {
+            "org.freedesktop.NetworkManager.IP6Config"
+        } else {
+            "org.freedesktop.NetworkManager.IP4Config"
+        };
+        match self.get_all_properties(config_path, interface) {
+            Ok(props) => props
+                .get("AddressData")
+                .map(|data| NetworkManagerHelpers::addresses_from_address_data(data))
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        }
+    }
 
-        // Create connection settings
-        let mut connection_settings = HashMap::new();
-        let mut wifi_settings = HashMap::new();
-        let mut security_settings = HashMap::new();
+    /// Everything an IP4Config or IP6Config object says about the link.
+    fn ip_family_configuration(
+        &self,
+        config_path: &zbus::zvariant::OwnedObjectPath,
+        ipv6: bool,
+    ) -> Result<IpFamilyConfiguration> {
+        let interface = if ipv6 {
+            "org.freedesktop.NetworkManager.IP6Config"
+        } else {
+            "org.freedesktop.NetworkManager.IP4Config"
+        };
+        let props = self.get_all_properties(config_path, interface)?;
+        let strings = |key: &str| -> Vec<String> {
+            props
+                .get(key)
+                .map(|v| NetworkManagerHelpers::strings_from_value(v))
+                .unwrap_or_default()
+        };
 
-        // Set connection name and type
-        let mut connection = HashMap::new();
-        connection.insert("id".to_string(), Value::from(config.ssid.clone()));
-        connection.insert("type".to_string(), Value::from("802-11-wireless"));
-        connection_settings.insert("connection".to_string(), connection);
+        Ok(IpFamilyConfiguration {
+            addresses: props
+                .get("AddressData")
+                .map(|v| NetworkManagerHelpers::addresses_from_address_data(v))
+                .unwrap_or_default(),
+            gateway: Self::extract_string_from_dict(&props, "Gateway").filter(|g| !g.is_empty()),
+            nameservers: if ipv6 {
+                props
+                    .get("Nameservers")
+                    .map(|v| NetworkManagerHelpers::ipv6_nameservers(v))
+                    .unwrap_or_default()
+            } else {
+                props
+                    .get("NameserverData")
+                    .map(|v| NetworkManagerHelpers::nameservers_from_nameserver_data(v))
+                    .unwrap_or_default()
+            },
+            domains: strings("Domains"),
+            searches: strings("Searches"),
+            routes: props
+                .get("RouteData")
+                .map(|v| NetworkManagerHelpers::routes_from_route_data(v))
+                .unwrap_or_default(),
+        })
+    }
 
-        // Set WiFi settings
-        wifi_settings.insert("ssid".to_string(), Value::from(config.ssid.as_bytes().to_vec()));
-        wifi_settings.insert("mode".to_string(), Value::from("infrastructure"));
+    /// `Options` of a DHCP4Config or DHCP6Config object.
+    fn dhcp_options(
+        &self,
+        config_path: &zbus::zvariant::OwnedObjectPath,
+        ipv6: bool,
+    ) -> HashMap<String, String> {
+        let interface = if ipv6 {
+            "org.freedesktop.NetworkManager.DHCP6Config"
+        } else {
+            "org.freedesktop.NetworkManager.DHCP4Config"
+        };
+        match self.get_all_properties(config_path, interface) {
+            Ok(props) => props
+                .get("Options")
+                .map(|v| NetworkManagerHelpers::string_dict(v))
+                .unwrap_or_default(),
+            Err(_) => HashMap::new(),
+        }
+    }
 
-        // Set security settings based on security type
-        match config.security_type {
-            WiFiSecurityType::None => {
-                // No security settings needed
+    /// Device whose kernel or IP interface is `interface`.
+    fn device_path_by_interface(&self, interface: &str) -> Result<zbus::zvariant::OwnedObjectPath> {
+        for device_path in self.device_paths()? {
+            let Ok(props) =
+                self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")
+            else {
+                continue;
+            };
+            let matches = |key: &str| Self::extract_string_from_dict(&props, key).as_deref() == Some(interface);
+            if matches("Interface") || matches("IpInterface") {
+                return Ok(device_path);
             }
-            WiFiSecurityType::Wep => {
-                security_settings.insert("key-mgmt".to_string(), Value::from("none"));
-                if let Some(password) = config.password.clone() {
-                    security_settings.insert("wep-key0".to_string(), Value::from(password));
+        }
+        Err(crate::error::NetworkError::DeviceNotFound(interface.to_string()))
+    }
+
+    /// The active connection with `uuid`, with its properties.
+    fn active_connection_by_uuid(
+        &self,
+        uuid: &str,
+    ) -> Result<(zbus::zvariant::OwnedObjectPath, HashMap<String, zbus::zvariant::OwnedValue>)> {
+        for active_path in self.active_connection_paths()? {
+            let Ok(props) = self.get_all_properties(
+                &active_path,
+                "org.freedesktop.NetworkManager.Connection.Active",
+            ) else {
+                continue;
+            };
+            if Self::extract_string_from_dict(&props, "Uuid").as_deref() == Some(uuid) {
+                return Ok((active_path, props));
+            }
+        }
+        Err(crate::error::NetworkError::NoConnection)
+    }
+
+    /// First device of the active connection with `uuid`.
+    fn device_path_by_active_uuid(&self, uuid: &str) -> Result<zbus::zvariant::OwnedObjectPath> {
+        let (_, props) = self.active_connection_by_uuid(uuid)?;
+        Self::extract_paths_from_dict(&props, "Devices")
+            .into_iter()
+            .next()
+            .ok_or(crate::error::NetworkError::NoConnection)
+    }
+
+    /// The interface that carries an active connection's traffic. A plugin
+    /// VPN (OpenVPN, vpnc…) lists the device it runs over rather than its
+    /// tunnel, so the tunnel is found as the device holding the address
+    /// the VPN was given.
+    fn active_connection_ip_interface(
+        &self,
+        props: &HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> Result<String> {
+        if Self::extract_bool_from_dict(props, "Vpn").unwrap_or(false) {
+            let vpn_addresses: Vec<IpAddressInfo> = [("Ip4Config", false), ("Ip6Config", true)]
+                .into_iter()
+                .filter_map(|(key, ipv6)| {
+                    Self::extract_path_from_dict(props, key)
+                        .map(|path| self.ip_config_addresses(&path, ipv6))
+                })
+                .flatten()
+                .collect();
+            if !vpn_addresses.is_empty() {
+                for device_path in self.device_paths()? {
+                    let Ok(device) =
+                        self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")
+                    else {
+                        continue;
+                    };
+                    let holds_vpn_address = [("Ip4Config", false), ("Ip6Config", true)]
+                        .into_iter()
+                        .filter_map(|(key, ipv6)| {
+                            Self::extract_path_from_dict(&device, key)
+                                .map(|path| self.ip_config_addresses(&path, ipv6))
+                        })
+                        .flatten()
+                        .any(|address| vpn_addresses.contains(&address));
+                    if holds_vpn_address {
+                        if let Some(interface) = self.device_ip_interface(&device_path) {
+                            return Ok(interface);
+                        }
+                    }
+                }
+            }
+        }
+
+        Self::extract_paths_from_dict(props, "Devices")
+            .into_iter()
+            .next()
+            .and_then(|device| self.device_ip_interface(&device))
+            .ok_or(crate::error::NetworkError::NoConnection)
+    }
+
+    /// The interface whose counters bandwidth stats read: `interface`, once
+    /// checked to be a valid name, the one carrying the active connection `uuid`, or with neither
+    /// the one carrying the primary connection.
+    pub fn resolve_stats_interface(
+        &self,
+        interface: Option<&str>,
+        uuid: Option<&str>,
+    ) -> Result<String> {
+        if let Some(interface) = interface {
+            // It ends up in a sysfs path.
+            NetworkManagerHelpers::validate_interface_name(interface)?;
+            return Ok(interface.to_string());
+        }
+        let props = match uuid {
+            Some(uuid) => self.active_connection_by_uuid(uuid)?.1,
+            None => {
+                let primary = self
+                    .primary_connection_path()
+                    .ok_or(crate::error::NetworkError::NoConnection)?;
+                self.get_all_properties(&primary, "org.freedesktop.NetworkManager.Connection.Active")?
+            }
+        };
+        self.active_connection_ip_interface(&props)
+    }
+
+    /// Live IP configuration of a device, named by interface, by the UUID of
+    /// the connection active on it, or, with neither, the primary connection's.
+    pub fn get_ip_configuration(
+        &self,
+        interface: Option<&str>,
+        uuid: Option<&str>,
+    ) -> Result<IpConfiguration> {
+        let device_path = match (interface, uuid) {
+            (Some(interface), _) => self.device_path_by_interface(interface)?,
+            (None, Some(uuid)) => self.device_path_by_active_uuid(uuid)?,
+            (None, None) => {
+                let primary = self
+                    .primary_connection_path()
+                    .ok_or(crate::error::NetworkError::NoConnection)?;
+                let props = self.get_all_properties(
+                    &primary,
+                    "org.freedesktop.NetworkManager.Connection.Active",
+                )?;
+                Self::extract_paths_from_dict(&props, "Devices")
+                    .into_iter()
+                    .next()
+                    .ok_or(crate::error::NetworkError::NoConnection)?
+            }
+        };
+
+        let props = self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")?;
+        let family = |key: &str, ipv6: bool| -> Result<IpFamilyConfiguration> {
+            match Self::extract_path_from_dict(&props, key) {
+                Some(path) => self.ip_family_configuration(&path, ipv6),
+                None => Ok(IpFamilyConfiguration::default()),
+            }
+        };
+        let dhcp = |key: &str, ipv6: bool| {
+            Self::extract_path_from_dict(&props, key)
+                .map(|path| self.dhcp_options(&path, ipv6))
+                .map(NetworkManagerHelpers::dhcp_lease_from_options)
+                .unwrap_or_default()
+        };
+
+        Ok(IpConfiguration {
+            interface: Self::extract_string_from_dict(&props, "IpInterface")
+                .filter(|s| !s.is_empty())
+                .or_else(|| Self::extract_string_from_dict(&props, "Interface"))
+                .unwrap_or_default(),
+            ipv4: family("Ip4Config", false)?,
+            ipv6: family("Ip6Config", true)?,
+            dhcp4: dhcp("Dhcp4Config", false),
+            dhcp6: dhcp("Dhcp6Config", true),
+        })
+    }
+
+    /// Paths of every active connection, in NetworkManager's order.
+    fn active_connection_paths(&self) -> Result<Vec<zbus::zvariant::OwnedObjectPath>> {
+        let active = self.proxy.get(
+            InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
+            "ActiveConnections",
+        )?;
+        match active.downcast_ref() {
+            Ok(Value::Array(arr)) => Ok(arr
+                .iter()
+                .filter_map(|v| match v {
+                    Value::ObjectPath(path) => Some(path.to_owned().into()),
+                    _ => None,
+                })
+                .collect()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn device_interface(&self, device_path: &zbus::zvariant::OwnedObjectPath) -> Option<String> {
+        let props = zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
+            .destination("org.freedesktop.NetworkManager")
+            .ok()?
+            .path(device_path.as_str())
+            .ok()?
+            .build()
+            .ok()?;
+        let interface = props
+            .get(
+                InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager.Device"),
+                "Interface",
+            )
+            .ok()?;
+        interface.downcast_ref::<String>().ok()
+    }
+
+    /// The interface a device's IP traffic goes through: the kernel
+    /// interface, except for modems and PPP links, whose `IpInterface` is
+    /// the `ppp0` or `wwan0` that carries addresses.
+    fn device_ip_interface(&self, device_path: &zbus::zvariant::OwnedObjectPath) -> Option<String> {
+        let props = self
+            .get_all_properties(device_path, "org.freedesktop.NetworkManager.Device")
+            .ok()?;
+        Self::extract_string_from_dict(&props, "IpInterface")
+            .filter(|s| !s.is_empty())
+            .or_else(|| Self::extract_string_from_dict(&props, "Interface"))
+    }
+
+    /// Every active connection, with the primary one marked and bond or
+    /// bridge ports tied to their master.
+    pub fn list_active_connections(&self) -> Result<Vec<ActiveConnectionInfo>> {
+        let primary = self.primary_connection_path();
+        let mut interfaces: HashMap<zbus::zvariant::OwnedObjectPath, Option<String>> = HashMap::new();
+        let mut interface_of = |path: zbus::zvariant::OwnedObjectPath| -> Option<String> {
+            interfaces
+                .entry(path.clone())
+                .or_insert_with(|| self.device_interface(&path))
+                .clone()
+        };
+
+        let mut connections = Vec::new();
+        for active_path in self.active_connection_paths()? {
+            // Connections can finish deactivating while being listed.
+            let Ok(props) = self.get_all_properties(
+                &active_path,
+                "org.freedesktop.NetworkManager.Connection.Active",
+            ) else {
+                continue;
+            };
+
+            connections.push(ActiveConnectionInfo {
+                id: Self::extract_string_from_dict(&props, "Id").unwrap_or_default(),
+                uuid: Self::extract_string_from_dict(&props, "Uuid").unwrap_or_default(),
+                connection_type: Self::extract_string_from_dict(&props, "Type").unwrap_or_default(),
+                devices: Self::extract_paths_from_dict(&props, "Devices")
+                    .into_iter()
+                    .filter_map(&mut interface_of)
+                    .collect(),
+                state: NetworkManagerHelpers::active_connection_state_from_nm(
+                    Self::extract_u32_from_dict(&props, "State").unwrap_or(0),
+                ),
+                default: Self::extract_bool_from_dict(&props, "Default").unwrap_or(false),
+                default6: Self::extract_bool_from_dict(&props, "Default6").unwrap_or(false),
+                vpn: Self::extract_bool_from_dict(&props, "Vpn").unwrap_or(false),
+                is_primary: primary.as_ref() == Some(&active_path),
+                master: Self::extract_path_from_dict(&props, "Master").and_then(&mut interface_of),
+                slaves: Vec::new(),
+            });
+        }
+
+        // NetworkManager only records the link from port to master.
+        let ports: Vec<(String, Vec<String>)> = connections
+            .iter()
+            .filter_map(|c| c.master.clone().map(|master| (master, c.devices.clone())))
+            .collect();
+        for connection in &mut connections {
+            for (master, devices) in &ports {
+                if connection.devices.contains(master) {
+                    connection.slaves.extend(devices.iter().cloned());
+                }
+            }
+        }
+
+        Ok(connections)
+    }
+
+    /// The active connections whose traffic the data-usage ledger counts,
+    /// with the interface carrying each. VPNs are left out because their
+    /// bytes already count on the connection they run over, and bond or
+    /// bridge ports because their master counts them.
+    pub(crate) fn data_usage_connections(&self) -> Result<Vec<crate::data_usage::LedgerConnection>> {
+        let mut connections = Vec::new();
+        for active_path in self.active_connection_paths()? {
+            let Ok(props) = self.get_all_properties(
+                &active_path,
+                "org.freedesktop.NetworkManager.Connection.Active",
+            ) else {
+                continue;
+            };
+            let connection_type = Self::extract_string_from_dict(&props, "Type").unwrap_or_default();
+            if Self::extract_bool_from_dict(&props, "Vpn").unwrap_or(false)
+                || Self::extract_path_from_dict(&props, "Master").is_some()
+                || matches!(connection_type.as_str(), "vpn" | "wireguard" | "loopback")
+            {
+                continue;
+            }
+            let Some(uuid) = Self::extract_string_from_dict(&props, "Uuid") else {
+                continue;
+            };
+            let Ok(interface) = self.active_connection_ip_interface(&props) else {
+                continue;
+            };
+            let ssid = if connection_type == "802-11-wireless" {
+                Self::extract_path_from_dict(&props, "Connection")
+                    .and_then(|path| self.get_connection_settings(&path).ok())
+                    .and_then(|settings| {
+                        let ssid = settings.get("802-11-wireless")?.get("ssid")?;
+                        NetworkManagerHelpers::ssid_bytes_from_value(ssid)
+                    })
+                    .map(|bytes| NetworkManagerHelpers::ssid_display(&bytes))
+            } else {
+                None
+            };
+
+            connections.push(crate::data_usage::LedgerConnection {
+                uuid,
+                name: Self::extract_string_from_dict(&props, "Id").unwrap_or_default(),
+                ssid,
+                interface,
+            });
+        }
+        Ok(connections)
+    }
+
+    fn connection_ref_from_settings(
+        settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>,
+    ) -> Option<ConnectionRef> {
+        let connection = settings.get("connection")?;
+        Some(ConnectionRef {
+            id: Self::extract_string_from_dict(connection, "id").unwrap_or_default(),
+            uuid: Self::extract_string_from_dict(connection, "uuid")?,
+            connection_type: Self::extract_string_from_dict(connection, "type").unwrap_or_default(),
+        })
+    }
+
+    /// Collect paths of all wireless (type=2) devices from NetworkManager
+    fn wireless_device_paths(&self) -> Result<Vec<zbus::zvariant::OwnedObjectPath>> {
+        let devices_variant = self.proxy.get(
+            InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
+            "Devices",
+        )?;
+
+        let mut paths = Vec::new();
+        if let Ok(zbus::zvariant::Value::Array(devices)) = devices_variant.downcast_ref() {
+            for device in devices.iter() {
+                if let zbus::zvariant::Value::ObjectPath(device_path) = device {
+                    let device_props =
+                        zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
+                            .destination("org.freedesktop.NetworkManager")?
+                            .path(device_path.as_str())?
+                            .build()?;
+
+                    let device_type_variant = device_props.get(
+                        InterfaceName::from_static_str_unchecked(
+                            "org.freedesktop.NetworkManager.Device",
+                        ),
+                        "DeviceType",
+                    )?;
+
+                    if let Ok(zbus::zvariant::Value::U32(device_type)) =
+                        device_type_variant.downcast_ref()
+                    {
+                        if device_type == 2u32 {
+                            let owned = zbus::zvariant::OwnedObjectPath::try_from(
+                                device_path.as_str(),
+                            )
+                            .unwrap();
+                            paths.push(owned);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Request an explicit WiFi scan through NetworkManager and return a fresh list.
+    pub fn rescan_wifi(&self) -> Result<Vec<NetworkInfo>> {
+        self.request_rescan()?;
+        self.list_wifi_networks()
+    }
+
+    /// Request a scan on the first wireless device that accepts one.
+    pub fn request_rescan(&self) -> Result<()> {
+        let devices = self.wireless_device_paths()?;
+
+        if devices.is_empty() {
+            return Err(crate::error::NetworkError::OperationError(
+                "No wireless device available for scanning".to_string(),
+            ));
+        }
+
+        for device_path in &devices {
+            let wireless_proxy = zbus::blocking::Proxy::new(
+                &self.connection,
+                "org.freedesktop.NetworkManager",
+                device_path.as_str(),
+                "org.freedesktop.NetworkManager.Device.Wireless",
+            )?;
+
+            let options: HashMap<String, zbus::zvariant::OwnedValue> = HashMap::new();
+            if wireless_proxy.call::<_, _, ()>("RequestScan", &(options,)).is_ok() {
+                return Ok(());
+            }
+        }
+
+        Err(crate::error::NetworkError::OperationError(
+            "Failed to request WiFi scan on available wireless devices".to_string(),
+        ))
+    }
+
+    /// Ask every wireless device for a scan unless it scanned less than
+    /// `min_gap` ago, judged by its `LastScan`.
+    ///
+    /// Returns whether any scan was actually requested; `false` means the
+    /// current access point list is already fresh enough to use.
+    pub fn request_wifi_scan(&self, min_gap: std::time::Duration) -> Result<bool> {
+        let devices = self.wireless_device_paths()?;
+
+        if devices.is_empty() {
+            return Err(crate::error::NetworkError::OperationError(
+                "No wireless device available for scanning".to_string(),
+            ));
+        }
+
+        if !self.get_wireless_enabled()? {
+            return Ok(false);
+        }
+
+        let now = NetworkManagerHelpers::boottime_ms();
+        let mut requested = false;
+
+        for device_path in &devices {
+            let wireless_proxy = zbus::blocking::Proxy::new(
+                &self.connection,
+                "org.freedesktop.NetworkManager",
+                device_path.as_str(),
+                "org.freedesktop.NetworkManager.Device.Wireless",
+            )?;
+
+            // -1 when the device never scanned; missing on NetworkManager
+            // older than 1.12, where we cannot know and just ask.
+            let last_scan: i64 = wireless_proxy.get_property("LastScan").unwrap_or(-1);
+            if let (Some(now), true) = (now, last_scan >= 0) {
+                if now - last_scan < min_gap.as_millis() as i64 {
+                    continue;
+                }
+            }
+
+            let options: HashMap<String, zbus::zvariant::OwnedValue> = HashMap::new();
+            match wireless_proxy.call::<_, _, ()>("RequestScan", &(options,)) {
+                Ok(()) => requested = true,
+                Err(e) => log::debug!("RequestScan refused on {}: {}", device_path.as_str(), e),
+            }
+        }
+
+        Ok(requested)
+    }
+
+    /// Connect to a WiFi network
+    pub fn connect_to_wifi(&self, config: WiFiConnectionConfig) -> Result<()> {
+        // Log connection attempt
+        log::debug!("connect_to_wifi called: ssid='{}' ssid_hex={:?} security={:?} username={:?}",
+                  config.ssid, config.ssid_hex, config.security_type, config.username);
+
+        let ssid_bytes = NetworkManagerHelpers::ssid_bytes(&config.ssid, config.ssid_hex.as_deref())?;
+
+        // Create connection settings
+        let mut connection_settings = HashMap::new();
+        let mut wifi_settings = HashMap::new();
+        let mut security_settings = HashMap::new();
+
+        // Set connection name and type
+        let mut connection = HashMap::new();
+        connection.insert("id".to_string(), Value::from(config.ssid.clone()));
+        connection.insert("type".to_string(), Value::from("802-11-wireless"));
+        connection_settings.insert("connection".to_string(), connection);
+
+        // Set WiFi settings
+        wifi_settings.insert("ssid".to_string(), Value::from(ssid_bytes));
+        wifi_settings.insert("mode".to_string(), Value::from("infrastructure"));
+
+        // Set security settings based on security type
+        match config.security_type {
+            WiFiSecurityType::None => {
+                // No security settings needed
+            }
+            WiFiSecurityType::Wep => {
+                security_settings.insert("key-mgmt".to_string(), Value::from("none"));
+                if let Some(password) = config.password.clone() {
+                    security_settings.insert("wep-key0".to_string(), Value::from(password));
                 }
             }
             WiFiSecurityType::WpaPsk => {
@@ -782,6 +1674,13 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
             }
         }
 
+        if let Some(policy) = &config.mac_address_policy {
+            wifi_settings.insert(
+                "assigned-mac-address".to_string(),
+                Value::from(NetworkManagerHelpers::mac_policy_to_setting(policy)?),
+            );
+        }
+
         connection_settings.insert("802-11-wireless".to_string(), wifi_settings);
         if !security_settings.is_empty() {
             connection_settings.insert("802-11-wireless-security".to_string(), security_settings);
@@ -820,136 +1719,1330 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                 return Err(e.into());
             }
         }
-
-        log::debug!("connect_to_wifi finished for ssid='{}'", config.ssid);
-
-        Ok(())
+
+        log::debug!("connect_to_wifi finished for ssid='{}'", config.ssid);
+
+        Ok(())
+    }
+
+    /// Toggle network state
+    pub fn toggle_network_state(&self, enabled: bool) -> Result<bool> {
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+
+        nm_proxy.set_property("NetworkingEnabled", enabled)?;
+
+        let current_state: bool = nm_proxy.get_property("NetworkingEnabled")?;
+        Ok(current_state)
+    }
+
+    /// Get wireless enabled state
+    pub fn get_wireless_enabled(&self) -> Result<bool> {
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+        Ok(nm_proxy.get_property("WirelessEnabled")?)
+    }
+
+    /// Set wireless enabled state
+    pub fn set_wireless_enabled(&self, enabled: bool) -> Result<()> {
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+        nm_proxy.set_property("WirelessEnabled", enabled)?;
+        Ok(())
+    }
+
+    /// Check if wireless device is available
+    pub fn is_wireless_available(&self) -> Result<bool> {
+        Ok(!self.wireless_device_paths()?.is_empty())
+    }
+
+    /// Listen for network changes
+    pub fn listen_network_changes(&self) -> Result<mpsc::Receiver<NetworkInfo>> {
+        let (tx, rx) = mpsc::channel();
+        let connection_clone = self.connection.clone();
+        let app_handle = self.app.clone();
+
+        // Setting a connection metered, or NetworkManager changing its guess,
+        // raises no StateChanged; the global `Metered` property follows the
+        // primary connection's device, so that is what gets watched. A
+        // connectivity check that finds a portal or loses the internet
+        // only changes `Connectivity`, watched here too.
+        let metered_tx = tx.clone();
+        let metered_nm = VSKNetworkManager {
+            connection: self.connection.clone(),
+            proxy: self.proxy.clone(),
+            app: self.app.clone(),
+        };
+        std::thread::spawn(move || {
+            let Ok(changes) = metered_nm.proxy.receive_properties_changed() else {
+                return;
+            };
+            for change in changes {
+                let Ok(args) = change.args() else {
+                    continue;
+                };
+                let changed = args.changed_properties();
+                if args.interface_name().as_str() != "org.freedesktop.NetworkManager"
+                    || !(changed.contains_key("Metered") || changed.contains_key("Connectivity"))
+                {
+                    continue;
+                }
+                if let Ok(network_info) = metered_nm.get_current_network_state() {
+                    if metered_tx.send(network_info).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        std::thread::spawn(move || {
+            if let Ok(proxy) = zbus::blocking::Proxy::new(
+                &connection_clone,
+                "org.freedesktop.NetworkManager",
+                "/org/freedesktop/NetworkManager",
+                "org.freedesktop.NetworkManager",
+            ) {
+                if let Ok(mut signal) = proxy.receive_signal("StateChanged") {
+                    let proxy_props = zbus::blocking::fdo::PropertiesProxy::builder(
+                        &connection_clone,
+                    )
+                    .destination("org.freedesktop.NetworkManager")
+                    .unwrap()
+                    .path("/org/freedesktop/NetworkManager")
+                    .unwrap()
+                    .build()
+                    .unwrap();
+
+                    while let Some(_msg) = signal.next() {
+                        let network_manager = VSKNetworkManager {
+                            connection: connection_clone.clone(),
+                            proxy: proxy_props.clone(),
+                            app: app_handle.clone(),
+                        };
+
+                        if let Ok(network_info) =
+                            network_manager.get_current_network_state()
+                        {
+                            if tx.send(network_info).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Disconnect from the current WiFi network.
+    ///
+    /// Deactivates what the wireless device is running. Taking the first
+    /// active connection instead dropped the wired link or a VPN whenever
+    /// NetworkManager happened to list it first.
+    pub fn disconnect_from_wifi(&self) -> Result<()> {
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+
+        for device_path in self.wireless_device_paths()? {
+            let props =
+                self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")?;
+            if let Some(active) = Self::extract_path_from_dict(&props, "ActiveConnection") {
+                nm_proxy.call::<_, _, ()>("DeactivateConnection", &(&active,))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn device_proxy(
+        &self,
+        device_path: &zbus::zvariant::OwnedObjectPath,
+    ) -> Result<zbus::blocking::Proxy<'static>> {
+        Ok(zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            device_path.to_owned(),
+            "org.freedesktop.NetworkManager.Device",
+        )?)
+    }
+
+    /// Takes a device down with `Device.Disconnect`. Unlike deactivating the
+    /// connection, this also keeps NetworkManager from autoconnecting the
+    /// device again until something is activated on it by hand.
+    pub fn disconnect_device(&self, interface: &str) -> Result<()> {
+        let device_path = self.device_path_by_interface(interface)?;
+        self.device_proxy(&device_path)?.call::<_, _, ()>("Disconnect", &())?;
+        Ok(())
+    }
+
+    /// Hands a device to NetworkManager or takes it away. Not persistent: a
+    /// restart of NetworkManager goes back to its configuration.
+    pub fn set_device_managed(&self, interface: &str, managed: bool) -> Result<()> {
+        let device_path = self.device_path_by_interface(interface)?;
+        self.device_proxy(&device_path)?.set_property("Managed", managed)?;
+        Ok(())
+    }
+
+    /// Whether NetworkManager may activate connections on the device on its own.
+    pub fn set_device_autoconnect(&self, interface: &str, autoconnect: bool) -> Result<()> {
+        let device_path = self.device_path_by_interface(interface)?;
+        self.device_proxy(&device_path)?.set_property("Autoconnect", autoconnect)?;
+        Ok(())
+    }
+
+    /// Device to bring a saved wired connection up on: the one it is bound
+    /// to by `interface-name`, else the first Ethernet device NetworkManager
+    /// lists it as available on.
+    fn wired_device_for_connection(
+        &self,
+        conn_path: &zbus::zvariant::OwnedObjectPath,
+        interface_name: Option<&str>,
+    ) -> Result<zbus::zvariant::OwnedObjectPath> {
+        if let Some(interface) = interface_name.filter(|name| !name.is_empty()) {
+            return self.device_path_by_interface(interface);
+        }
+        for device_path in self.device_paths()? {
+            let Ok(props) =
+                self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")
+            else {
+                continue;
+            };
+            let is_ethernet = NetworkManagerHelpers::device_type_from_nm(
+                Self::extract_u32_from_dict(&props, "DeviceType").unwrap_or(0),
+            ) == DeviceType::Ethernet;
+            if is_ethernet
+                && Self::extract_paths_from_dict(&props, "AvailableConnections").contains(conn_path)
+            {
+                return Ok(device_path);
+            }
+        }
+        Err(crate::error::NetworkError::DeviceNotFound(
+            "no wired device can use this connection".to_string(),
+        ))
+    }
+
+    /// Carrier, speed and duplex of an Ethernet device, and what its active
+    /// profile asks of the port.
+    fn wired_link_info(&self, device_path: &zbus::zvariant::OwnedObjectPath) -> Result<WiredLinkInfo> {
+        let props = self.get_all_properties(device_path, "org.freedesktop.NetworkManager.Device")?;
+        let wired = self.get_all_properties(device_path, "org.freedesktop.NetworkManager.Device.Wired")?;
+        let interface = Self::extract_string_from_dict(&props, "Interface").unwrap_or_default();
+        let carrier = Self::extract_bool_from_dict(&wired, "Carrier").unwrap_or(false);
+
+        // Speed and duplex keep their last value on some drivers after the
+        // cable is pulled.
+        let duplex = if carrier {
+            std::fs::read_to_string(
+                std::path::Path::new(crate::network_stats::SYSFS_NET_ROOT)
+                    .join(&interface)
+                    .join("duplex"),
+            )
+            .ok()
+            .and_then(|value| NetworkManagerHelpers::duplex_from_sysfs(&value))
+        } else {
+            None
+        };
+        let autoneg = Self::extract_path_from_dict(&props, "ActiveConnection")
+            .and_then(|active| {
+                self.get_all_properties(&active, "org.freedesktop.NetworkManager.Connection.Active")
+                    .ok()
+            })
+            .and_then(|active| Self::extract_path_from_dict(&active, "Connection"))
+            .and_then(|conn_path| self.get_connection_settings(&conn_path).ok())
+            .and_then(|settings| {
+                settings
+                    .get("802-3-ethernet")
+                    .and_then(NetworkManagerHelpers::autoneg_from_ethernet_section)
+            });
+
+        Ok(WiredLinkInfo {
+            interface,
+            carrier,
+            speed_mbps: Self::extract_u32_from_dict(&wired, "Speed").filter(|speed| carrier && *speed > 0),
+            duplex,
+            autoneg,
+        })
+    }
+
+    /// Link details of an Ethernet port, plugged in or not: `interface`, or
+    /// the first Ethernet device.
+    pub fn get_wired_link_info(&self, interface: Option<&str>) -> Result<WiredLinkInfo> {
+        let device_path = match interface {
+            Some(interface) => self.device_path_by_interface(interface)?,
+            None => self
+                .device_paths()?
+                .into_iter()
+                .find(|path| {
+                    self.get_all_properties(path, "org.freedesktop.NetworkManager.Device")
+                        .ok()
+                        .and_then(|props| Self::extract_u32_from_dict(&props, "DeviceType"))
+                        .map(NetworkManagerHelpers::device_type_from_nm)
+                        == Some(DeviceType::Ethernet)
+                })
+                .ok_or_else(|| {
+                    crate::error::NetworkError::DeviceNotFound("no Ethernet device".to_string())
+                })?,
+        };
+        self.wired_link_info(&device_path)
+    }
+
+    /// One event each time a cable is plugged into or pulled from any
+    /// Ethernet port, including ports that show up later (USB adapters,
+    /// docks). Watches `Carrier` with a single match rule rather than a
+    /// subscription per device.
+    pub fn listen_cable_changes(&self) -> Result<mpsc::Receiver<CableEvent>> {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender("org.freedesktop.NetworkManager")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .path_namespace("/org/freedesktop/NetworkManager/Devices")?
+            .add_arg("org.freedesktop.NetworkManager.Device.Wired")?
+            .build();
+        let messages = zbus::blocking::MessageIterator::for_match_rule(rule, &self.connection, None)?;
+        let network_manager = VSKNetworkManager {
+            connection: self.connection.clone(),
+            proxy: self.proxy.clone(),
+            app: self.app.clone(),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for msg in messages {
+                let Ok(msg) = msg else {
+                    continue;
+                };
+                let Some(path) = msg.header().path().map(|path| path.to_owned()) else {
+                    continue;
+                };
+                let Ok((_, changed, _)) = msg
+                    .body()
+                    .deserialize::<(String, HashMap<String, zbus::zvariant::OwnedValue>, Vec<String>)>()
+                else {
+                    continue;
+                };
+                let Some(plugged) = Self::extract_bool_from_dict(&changed, "Carrier") else {
+                    continue;
+                };
+                let event = CableEvent {
+                    interface: network_manager.device_interface(&path.into()).unwrap_or_default(),
+                    plugged,
+                };
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Follows a device through an activation, one event per state change.
+    ///
+    /// The subscription is made before `activate` runs so that a fast
+    /// failure is not missed. The watch ends once the device is up, has
+    /// failed, or has fallen back to disconnected after trying; once another
+    /// profile has taken the device over; and at the latest after
+    /// `ACTIVATION_TIMEOUT`, so a device that never settles, goes away or
+    /// outlives a NetworkManager restart does not keep a thread and its
+    /// D-Bus match for the rest of the process.
+    fn watch_activation<F>(
+        &self,
+        device_path: &zbus::zvariant::OwnedObjectPath,
+        uuid: String,
+        activate: F,
+    ) -> Result<mpsc::Receiver<ConnectionActivation>>
+    where
+        F: FnOnce() -> Result<()>,
+    {
+        use futures_lite::StreamExt;
+
+        let interface = self.device_interface(device_path).unwrap_or_default();
+        let device_proxy = self.device_proxy(device_path)?.into_inner();
+        let mut signal = async_io::block_on(device_proxy.receive_signal("StateChanged"))?;
+        activate()?;
+
+        let network_manager = VSKNetworkManager {
+            connection: self.connection.clone(),
+            proxy: self.proxy.clone(),
+            app: self.app.clone(),
+        };
+        let device_path = device_path.clone();
+        let deadline = std::time::Instant::now() + ACTIVATION_TIMEOUT;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut started = false;
+            loop {
+                let next = async_io::block_on(futures_lite::future::or(signal.next(), async {
+                    async_io::Timer::at(deadline).await;
+                    None
+                }));
+                let Some(msg) = next else {
+                    break;
+                };
+                let Ok((new_state, _old_state, reason)) =
+                    msg.body().deserialize::<(u32, u32, u32)>()
+                else {
+                    continue;
+                };
+                let state = NetworkManagerHelpers::device_state_from_nm(new_state);
+                match state {
+                    DeviceState::Prepare
+                    | DeviceState::Config
+                    | DeviceState::NeedAuth
+                    | DeviceState::IpConfig
+                    | DeviceState::IpCheck
+                    | DeviceState::Secondaries => started = true,
+                    _ => {}
+                }
+                // Before it starts, the device may still be taking down
+                // whatever it ran before.
+                if started
+                    && network_manager
+                        .device_active_uuid(&device_path)
+                        .is_some_and(|active| active != uuid)
+                {
+                    break;
+                }
+
+                let activation = ConnectionActivation {
+                    uuid: uuid.clone(),
+                    interface: interface.clone(),
+                    state,
+                    reason: NetworkManagerHelpers::device_state_reason_name(reason).to_string(),
+                    reason_code: reason,
+                    failed: state == DeviceState::Failed,
+                };
+                if tx.send(activation).is_err() {
+                    break;
+                }
+
+                match state {
+                    DeviceState::Activated | DeviceState::Failed => break,
+                    DeviceState::Disconnected | DeviceState::Unavailable if started => break,
+                    _ => {}
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// UUID of the connection active on a device, if it has one.
+    fn device_active_uuid(&self, device_path: &zbus::zvariant::OwnedObjectPath) -> Option<String> {
+        let props = self
+            .get_all_properties(device_path, "org.freedesktop.NetworkManager.Device")
+            .ok()?;
+        let active = Self::extract_path_from_dict(&props, "ActiveConnection")?;
+        let active_props = self
+            .get_all_properties(&active, "org.freedesktop.NetworkManager.Connection.Active")
+            .ok()?;
+        Self::extract_string_from_dict(&active_props, "Uuid")
+    }
+
+    /// What `network-changed` carries for a step of an activation: the
+    /// device being brought up and the profile going onto it, which need
+    /// not be the primary connection.
+    pub fn activation_network_info(&self, activation: ConnectionActivation) -> Result<NetworkInfo> {
+        let device_path = self.device_path_by_interface(&activation.interface)?;
+        let props = self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")?;
+        let conn_path = self.connection_path_by_uuid(&activation.uuid)?;
+        let name = self
+            .get_connection_settings(&conn_path)?
+            .get("connection")
+            .and_then(|connection| Self::extract_string_from_dict(connection, "id"))
+            .unwrap_or_else(|| activation.uuid.clone());
+
+        let device_type = Self::extract_u32_from_dict(&props, "DeviceType")
+            .map(NetworkManagerHelpers::device_type_from_nm)
+            .unwrap_or(DeviceType::Unknown);
+        let connection_type = match device_type {
+            DeviceType::Ethernet => "Ethernet",
+            DeviceType::Wifi => "WiFi",
+            DeviceType::Bluetooth => "Bluetooth",
+            DeviceType::Modem => "Modem",
+            _ => "Unknown",
+        };
+        let is_connected = activation.state == DeviceState::Activated;
+
+        let mut info = NetworkInfo {
+            name: name.clone(),
+            ssid: name,
+            uuid: Some(activation.uuid.clone()),
+            connection_type: connection_type.to_string(),
+            icon: Self::get_icon_for(connection_type, is_connected),
+            mac_address: Self::extract_string_from_dict(&props, "HwAddress")
+                .unwrap_or_else(|| "00:00:00:00:00:00".to_string()),
+            is_connected,
+            ..NetworkInfo::default()
+        };
+        if let Some(config_path) = Self::extract_path_from_dict(&props, "Ip4Config") {
+            info.ipv4_addresses = self.ip_config_addresses(&config_path, false);
+        }
+        if let Some(config_path) = Self::extract_path_from_dict(&props, "Ip6Config") {
+            info.ipv6_addresses = self.ip_config_addresses(&config_path, true);
+        }
+        if let Some(first) = info.ipv4_addresses.first() {
+            info.ip_address = first.address.clone();
+        }
+        if device_type == DeviceType::Ethernet {
+            info.wired = self.wired_link_info(&device_path).ok();
+        }
+        info.activation = Some(activation);
+        Ok(info)
+    }
+
+    /// Creates or updates a wired connection that authenticates with 802.1X
+    /// and brings it up.
+    ///
+    /// With `uuid`, the `802-1x` section of that Ethernet profile is replaced
+    /// and the rest is kept. Otherwise a new profile bound to `interface` is
+    /// added. Returns the profile's UUID and the activation progress, which
+    /// is where a rejected identity or certificate shows up.
+    pub fn configure_wired_8021x(
+        &self,
+        interface: Option<&str>,
+        uuid: Option<&str>,
+        config: Eap8021xConfig,
+    ) -> Result<(String, mpsc::Receiver<ConnectionActivation>)> {
+        let section = NetworkManagerHelpers::eap_8021x_section(&config)?;
+        let nm_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+        )?;
+
+        if let Some(uuid) = uuid {
+            let mut bound_interface: Option<String> = None;
+            self.update_connection_settings(uuid, |settings| {
+                let connection = settings.entry("connection".to_string()).or_default();
+                let connection_type = connection
+                    .get("type")
+                    .and_then(|v| v.downcast_ref::<String>().ok())
+                    .unwrap_or_default();
+                if connection_type != "802-3-ethernet" {
+                    return Err(crate::error::NetworkError::InvalidSettings(format!(
+                        "802.1X needs an 802-3-ethernet connection, not {}",
+                        connection_type
+                    )));
+                }
+                bound_interface = connection
+                    .get("interface-name")
+                    .and_then(|v| v.downcast_ref::<String>().ok());
+                if let Some(id) = &config.id {
+                    connection.insert("id".to_string(), Value::from(id.clone()));
+                }
+                settings.insert("802-1x".to_string(), section);
+                Ok(())
+            })?;
+
+            let conn_path = self.connection_path_by_uuid(uuid)?;
+            let device_path = self.wired_device_for_connection(
+                &conn_path,
+                interface.or(bound_interface.as_deref()),
+            )?;
+            let rx = self.watch_activation(&device_path, uuid.to_string(), || {
+                let any_path = zbus::zvariant::OwnedObjectPath::try_from("/").unwrap();
+                let _active: zbus::zvariant::OwnedObjectPath =
+                    nm_proxy.call("ActivateConnection", &(&conn_path, &device_path, &any_path))?;
+                Ok(())
+            })?;
+            return Ok((uuid.to_string(), rx));
+        }
+
+        let interface = interface.ok_or_else(|| {
+            crate::error::NetworkError::InvalidSettings(
+                "an interface or a connection UUID is required".to_string(),
+            )
+        })?;
+        let device_path = self.device_path_by_interface(interface)?;
+
+        let new_uuid = Uuid::new_v4().to_string();
+        let mut connection_section: HashMap<String, Value> = HashMap::new();
+        connection_section.insert(
+            "id".to_string(),
+            Value::from(
+                config
+                    .id
+                    .clone()
+                    .unwrap_or_else(|| format!("Wired 802.1X ({})", interface)),
+            ),
+        );
+        connection_section.insert("uuid".to_string(), Value::from(new_uuid.clone()));
+        connection_section.insert("type".to_string(), Value::from("802-3-ethernet"));
+        connection_section.insert("interface-name".to_string(), Value::from(interface.to_string()));
+        connection_section.insert("autoconnect".to_string(), Value::from(true));
+
+        let mut ipv4_section: HashMap<String, Value> = HashMap::new();
+        ipv4_section.insert("method".to_string(), Value::from("auto"));
+        let mut ipv6_section: HashMap<String, Value> = HashMap::new();
+        ipv6_section.insert("method".to_string(), Value::from("auto"));
+
+        let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+        settings.insert("connection".to_string(), connection_section);
+        settings.insert("802-3-ethernet".to_string(), HashMap::new());
+        settings.insert("802-1x".to_string(), section);
+        settings.insert("ipv4".to_string(), ipv4_section);
+        settings.insert("ipv6".to_string(), ipv6_section);
+
+        let rx = self.watch_activation(&device_path, new_uuid.clone(), || {
+            let any_path = zbus::zvariant::OwnedObjectPath::try_from("/").unwrap();
+            let _paths: (zbus::zvariant::OwnedObjectPath, zbus::zvariant::OwnedObjectPath) =
+                nm_proxy.call("AddAndActivateConnection", &(settings, &device_path, &any_path))?;
+            Ok(())
+        })?;
+        Ok((new_uuid, rx))
+    }
+
+    fn add_connection(&self, settings: HashMap<String, HashMap<String, Value>>) -> Result<()> {
+        let settings_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager/Settings",
+            "org.freedesktop.NetworkManager.Settings",
+        )?;
+        let _created_path: zbus::zvariant::OwnedObjectPath =
+            settings_proxy.call("AddConnection", &(settings,))?;
+        Ok(())
+    }
+
+    fn delete_connection(&self, conn_path: &zbus::zvariant::OwnedObjectPath) -> Result<()> {
+        let conn_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            conn_path.as_str(),
+            "org.freedesktop.NetworkManager.Settings.Connection",
+        )?;
+        conn_proxy.call::<_, _, ()>("Delete", &())?;
+        Ok(())
+    }
+
+    /// Port profiles of a bridge or bond. `connection.master` may name the
+    /// master by UUID or by interface, so `masters` holds every name it
+    /// goes by.
+    fn port_profiles(&self, masters: &[&str]) -> Result<Vec<PortProfile>> {
+        let mut ports = Vec::new();
+        for conn_path in self.list_connection_paths()? {
+            let Ok(settings) = self.get_connection_settings(&conn_path) else {
+                continue;
+            };
+            let Some(connection) = settings.get("connection") else {
+                continue;
+            };
+            let Some(master) =
+                Self::extract_string_from_dict(connection, "master").filter(|m| !m.is_empty())
+            else {
+                continue;
+            };
+            if masters.contains(&master.as_str()) {
+                ports.push(PortProfile {
+                    uuid: Self::extract_string_from_dict(connection, "uuid").unwrap_or_default(),
+                    interface: Self::extract_string_from_dict(connection, "interface-name")
+                        .unwrap_or_default(),
+                    path: conn_path,
+                    master,
+                });
+            }
+        }
+        Ok(ports)
+    }
+
+    /// Makes the port profiles of a bridge or bond match `ports`: one
+    /// Ethernet profile per interface pointing at the master, added or
+    /// deleted as needed. Ports are found under the master's UUID, its
+    /// interface and, after a rename, `previous_interface`; the ones kept
+    /// that named it by interface are pointed at the UUID, which survives
+    /// the next rename. Nothing else in them is touched.
+    fn sync_port_profiles(
+        &self,
+        master_uuid: &str,
+        master_interface: &str,
+        previous_interface: Option<&str>,
+        slave_type: &str,
+        ports: &[String],
+    ) -> Result<()> {
+        let mut masters = vec![master_uuid, master_interface];
+        masters.extend(previous_interface);
+        let existing = self.port_profiles(&masters)?;
+
+        for port in &existing {
+            if !ports.contains(&port.interface) {
+                self.delete_connection(&port.path)?;
+            } else if port.master != master_uuid {
+                self.update_connection_settings(&port.uuid, |settings| {
+                    settings
+                        .entry("connection".to_string())
+                        .or_default()
+                        .insert("master".to_string(), Value::from(master_uuid.to_string()));
+                    Ok(())
+                })?;
+            }
+        }
+
+        for port in ports {
+            if existing.iter().any(|existing| &existing.interface == port) {
+                continue;
+            }
+            let mut connection_section: HashMap<String, Value> = HashMap::new();
+            connection_section.insert(
+                "id".to_string(),
+                Value::from(format!("{} port {}", master_interface, port)),
+            );
+            connection_section.insert("uuid".to_string(), Value::from(Uuid::new_v4().to_string()));
+            connection_section.insert("type".to_string(), Value::from("802-3-ethernet"));
+            connection_section.insert("interface-name".to_string(), Value::from(port.clone()));
+            connection_section.insert("master".to_string(), Value::from(master_uuid.to_string()));
+            connection_section.insert("slave-type".to_string(), Value::from(slave_type.to_string()));
+            connection_section.insert("autoconnect".to_string(), Value::from(true));
+
+            let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+            settings.insert("connection".to_string(), connection_section);
+            settings.insert("802-3-ethernet".to_string(), HashMap::new());
+            self.add_connection(settings)?;
+        }
+        Ok(())
+    }
+
+    fn virtual_interface_ports(kind: &VirtualInterfaceKind) -> Option<&[String]> {
+        match kind {
+            VirtualInterfaceKind::Vlan(_) => None,
+            VirtualInterfaceKind::Bridge(bridge) => Some(&bridge.ports),
+            VirtualInterfaceKind::Bond(bond) => Some(&bond.ports),
+        }
+    }
+
+    fn virtual_interface_profile_from_settings(
+        settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>,
+        ports: Vec<String>,
+    ) -> Option<VirtualInterfaceProfile> {
+        let mut kind = NetworkManagerHelpers::virtual_interface_kind_from_settings(settings)?;
+        match &mut kind {
+            VirtualInterfaceKind::Vlan(_) => {}
+            VirtualInterfaceKind::Bridge(bridge) => bridge.ports = ports,
+            VirtualInterfaceKind::Bond(bond) => bond.ports = ports,
+        }
+
+        let connection = settings.get("connection")?;
+        let uuid = Self::extract_string_from_dict(connection, "uuid")?;
+        Some(VirtualInterfaceProfile {
+            id: Self::extract_string_from_dict(connection, "id").unwrap_or_else(|| uuid.clone()),
+            uuid,
+            interface_name: Self::extract_string_from_dict(connection, "interface-name")
+                .unwrap_or_default(),
+            autoconnect: Self::extract_bool_from_dict(connection, "autoconnect").unwrap_or(true),
+            kind,
+        })
+    }
+
+    /// Saved VLAN, bridge and bond profiles, with the ports attached to each.
+    pub fn list_virtual_interfaces(&self) -> Result<Vec<VirtualInterfaceProfile>> {
+        let mut all_settings = Vec::new();
+        for conn_path in self.list_connection_paths()? {
+            if let Ok(settings) = self.get_connection_settings(&conn_path) {
+                all_settings.push(settings);
+            }
+        }
+
+        let connection_string = |settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>, key: &str| {
+            settings
+                .get("connection")
+                .and_then(|connection| Self::extract_string_from_dict(connection, key))
+        };
+
+        let mut profiles = Vec::new();
+        for settings in &all_settings {
+            let Some(uuid) = connection_string(settings, "uuid") else {
+                continue;
+            };
+            // A VLAN may leave its name to NetworkManager. `master` names a
+            // master by UUID or interface only, so without a name the UUID
+            // is all there is to match.
+            let interface =
+                connection_string(settings, "interface-name").filter(|name| !name.is_empty());
+            let mut ports: Vec<String> = all_settings
+                .iter()
+                .filter(|port| {
+                    let master = connection_string(port, "master");
+                    master.as_deref() == Some(uuid.as_str())
+                        || (master.is_some() && master.as_deref() == interface.as_deref())
+                })
+                .filter_map(|port| connection_string(port, "interface-name"))
+                .collect();
+            ports.sort();
+
+            if let Some(profile) = Self::virtual_interface_profile_from_settings(settings, ports) {
+                profiles.push(profile);
+            }
+        }
+
+        profiles.sort_by(|a, b| a.interface_name.cmp(&b.interface_name));
+        Ok(profiles)
+    }
+
+    /// Adds a VLAN, bridge or bond profile, and a port profile for each
+    /// interface a bridge or bond should take.
+    pub fn create_virtual_interface(
+        &self,
+        config: VirtualInterfaceConfig,
+    ) -> Result<VirtualInterfaceProfile> {
+        NetworkManagerHelpers::validate_virtual_interface_config(&config)?;
+        let connection_type = NetworkManagerHelpers::virtual_interface_type(&config.kind);
+
+        let uuid = Uuid::new_v4().to_string();
+        let id = config.id.clone().unwrap_or_else(|| config.interface_name.clone());
+        let autoconnect = config.autoconnect.unwrap_or(true);
+
+        let mut connection_section: HashMap<String, Value> = HashMap::new();
+        connection_section.insert("id".to_string(), Value::from(id.clone()));
+        connection_section.insert("uuid".to_string(), Value::from(uuid.clone()));
+        connection_section.insert("type".to_string(), Value::from(connection_type));
+        connection_section.insert(
+            "interface-name".to_string(),
+            Value::from(config.interface_name.clone()),
+        );
+        connection_section.insert("autoconnect".to_string(), Value::from(autoconnect));
+        if Self::virtual_interface_ports(&config.kind).is_some() {
+            // Otherwise the master comes up without waiting for its ports
+            // and can stay without carrier.
+            connection_section.insert("autoconnect-slaves".to_string(), Value::from(1i32));
+        }
+
+        let mut ipv4_section: HashMap<String, Value> = HashMap::new();
+        ipv4_section.insert("method".to_string(), Value::from("auto"));
+        let mut ipv6_section: HashMap<String, Value> = HashMap::new();
+        ipv6_section.insert("method".to_string(), Value::from("auto"));
+
+        let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+        settings.insert("connection".to_string(), connection_section);
+        settings.insert(
+            connection_type.to_string(),
+            NetworkManagerHelpers::virtual_interface_section(&config.kind),
+        );
+        settings.insert("ipv4".to_string(), ipv4_section);
+        settings.insert("ipv6".to_string(), ipv6_section);
+        self.add_connection(settings)?;
+
+        if let Some(ports) = Self::virtual_interface_ports(&config.kind) {
+            self.sync_port_profiles(&uuid, &config.interface_name, None, connection_type, ports)?;
+        }
+
+        Ok(VirtualInterfaceProfile {
+            id,
+            uuid,
+            interface_name: config.interface_name,
+            autoconnect,
+            kind: config.kind,
+        })
+    }
+
+    /// Rewrites a VLAN, bridge or bond profile and brings its port profiles
+    /// in line. The kind cannot change; settings the model does not cover,
+    /// IP configuration included, are kept.
+    pub fn update_virtual_interface(
+        &self,
+        uuid: &str,
+        config: VirtualInterfaceConfig,
+    ) -> Result<VirtualInterfaceProfile> {
+        NetworkManagerHelpers::validate_virtual_interface_config(&config)?;
+        let connection_type = NetworkManagerHelpers::virtual_interface_type(&config.kind);
+        let section = NetworkManagerHelpers::virtual_interface_section(&config.kind);
+        let mut previous_interface = String::new();
+
+        self.update_connection_settings(uuid, |settings| {
+            let connection = settings.entry("connection".to_string()).or_default();
+            let existing_type = connection
+                .get("type")
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .unwrap_or_default();
+            if existing_type != connection_type {
+                return Err(crate::error::NetworkError::InvalidSettings(format!(
+                    "{} connection cannot become a {}",
+                    existing_type, connection_type
+                )));
+            }
+            previous_interface = connection
+                .get("interface-name")
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .unwrap_or_default();
+
+            connection.insert(
+                "interface-name".to_string(),
+                Value::from(config.interface_name.clone()),
+            );
+            if let Some(id) = &config.id {
+                connection.insert("id".to_string(), Value::from(id.clone()));
+            }
+            if let Some(autoconnect) = config.autoconnect {
+                connection.insert("autoconnect".to_string(), Value::from(autoconnect));
+            }
+
+            let type_section = settings.entry(connection_type.to_string()).or_default();
+            let existing_options = type_section
+                .get("options")
+                .map(NetworkManagerHelpers::string_dict)
+                .unwrap_or_default();
+            for key in NetworkManagerHelpers::virtual_interface_keys(&config.kind) {
+                type_section.remove(*key);
+            }
+            type_section.extend(section);
+            if let VirtualInterfaceKind::Bond(bond) = &config.kind {
+                type_section.insert(
+                    "options".to_string(),
+                    Value::from(NetworkManagerHelpers::bond_options(bond, existing_options)),
+                );
+            }
+            Ok(())
+        })?;
+
+        if let Some(ports) = Self::virtual_interface_ports(&config.kind) {
+            self.sync_port_profiles(
+                uuid,
+                &config.interface_name,
+                Some(&previous_interface),
+                connection_type,
+                ports,
+            )?;
+        }
+
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let settings = self.get_connection_settings(&conn_path)?;
+        let mut ports: Vec<String> = self
+            .port_profiles(&[uuid, &config.interface_name])?
+            .into_iter()
+            .map(|port| port.interface)
+            .collect();
+        ports.sort();
+        Self::virtual_interface_profile_from_settings(&settings, ports)
+            .ok_or_else(|| crate::error::NetworkError::ConnectionNotFound(uuid.to_string()))
+    }
+
+    /// Deletes a VLAN, bridge or bond profile together with its port profiles.
+    pub fn delete_virtual_interface(&self, uuid: &str) -> Result<()> {
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let settings = self.get_connection_settings(&conn_path)?;
+        let connection = settings.get("connection");
+        let connection_type = connection
+            .and_then(|c| Self::extract_string_from_dict(c, "type"))
+            .unwrap_or_default();
+        if !matches!(connection_type.as_str(), "vlan" | "bridge" | "bond") {
+            return Err(crate::error::NetworkError::InvalidSettings(format!(
+                "{} is a {} connection, not a vlan, bridge or bond",
+                uuid, connection_type
+            )));
+        }
+
+        let interface = connection
+            .and_then(|c| Self::extract_string_from_dict(c, "interface-name"))
+            .unwrap_or_default();
+        for port in self.port_profiles(&[uuid, &interface])? {
+            self.delete_connection(&port.path)?;
+        }
+        self.delete_connection(&conn_path)
+    }
+
+    /// Paired Bluetooth devices NetworkManager can tether through. BlueZ
+    /// pairs them; NetworkManager only lists the ones offering NAP or DUN.
+    pub fn list_bluetooth_devices(&self) -> Result<Vec<BluetoothDeviceInfo>> {
+        let mut devices = Vec::new();
+        for device_path in self.device_paths()? {
+            let Ok(props) =
+                self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")
+            else {
+                continue;
+            };
+            if NetworkManagerHelpers::device_type_from_nm(
+                Self::extract_u32_from_dict(&props, "DeviceType").unwrap_or(0),
+            ) != DeviceType::Bluetooth
+            {
+                continue;
+            }
+            let Ok(bluetooth) = self
+                .get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device.Bluetooth")
+            else {
+                continue;
+            };
+            let device = self.device_info(&device_path)?;
+            let address = Self::extract_string_from_dict(&bluetooth, "HwAddress")
+                .unwrap_or_else(|| device.interface.clone());
+
+            devices.push(BluetoothDeviceInfo {
+                name: Self::extract_string_from_dict(&bluetooth, "Name")
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| address.clone()),
+                address,
+                capabilities: NetworkManagerHelpers::bluetooth_capabilities(
+                    Self::extract_u32_from_dict(&bluetooth, "BtCapabilities").unwrap_or(0),
+                ),
+                state: device.state,
+                active_connection: device.active_connection,
+                available_connections: device.available_connections,
+            });
+        }
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(devices)
+    }
+
+    /// NetworkManager device of the paired phone with `address`.
+    fn bluetooth_device_path(&self, address: &str) -> Result<zbus::zvariant::OwnedObjectPath> {
+        for device_path in self.device_paths()? {
+            let Ok(bluetooth) = self
+                .get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device.Bluetooth")
+            else {
+                continue;
+            };
+            if Self::extract_string_from_dict(&bluetooth, "HwAddress")
+                .is_some_and(|hw| hw.eq_ignore_ascii_case(address))
+            {
+                return Ok(device_path);
+            }
+        }
+        Err(crate::error::NetworkError::DeviceNotFound(address.to_string()))
+    }
+
+    /// A saved tether for this phone and type, so connecting twice does not
+    /// leave two identical profiles behind.
+    fn find_bluetooth_profile(
+        &self,
+        bdaddr: &[u8],
+        tether_type: BluetoothTetherType,
+    ) -> Result<Option<(zbus::zvariant::OwnedObjectPath, String)>> {
+        let wanted_type = NetworkManagerHelpers::bluetooth_type_to_setting(tether_type);
+        for conn_path in self.list_connection_paths()? {
+            let Ok(settings) = self.get_connection_settings(&conn_path) else {
+                continue;
+            };
+            let Some(bluetooth) = settings.get("bluetooth") else {
+                continue;
+            };
+            let same_phone = bluetooth
+                .get("bdaddr")
+                .and_then(|v| v.try_clone().ok())
+                .and_then(|v| Vec::<u8>::try_from(v).ok())
+                .is_some_and(|address| address == bdaddr);
+            let same_type =
+                Self::extract_string_from_dict(bluetooth, "type").as_deref() == Some(wanted_type);
+            if same_phone && same_type {
+                if let Some(uuid) = settings
+                    .get("connection")
+                    .and_then(|connection| Self::extract_string_from_dict(connection, "uuid"))
+                {
+                    return Ok(Some((conn_path, uuid)));
+                }
+            }
+        }
+        Ok(None)
     }
 
-    /// Toggle network state
-    pub fn toggle_network_state(&self, enabled: bool) -> Result<bool> {
+    /// Tethers through a paired phone: reuses the saved profile for that
+    /// phone and type, or adds one, and activates it. Returns the profile's
+    /// UUID and the activation progress.
+    pub fn connect_bluetooth_tether(
+        &self,
+        config: BluetoothTetherConfig,
+    ) -> Result<(String, mpsc::Receiver<ConnectionActivation>)> {
+        let sections = NetworkManagerHelpers::bluetooth_tether_sections(&config)?;
+        let bdaddr = NetworkManagerHelpers::mac_to_bytes(&config.address)?;
+        let device_path = self.bluetooth_device_path(&config.address)?;
         let nm_proxy = zbus::blocking::Proxy::new(
             &self.connection,
             "org.freedesktop.NetworkManager",
             "/org/freedesktop/NetworkManager",
             "org.freedesktop.NetworkManager",
         )?;
+        let any_path = zbus::zvariant::OwnedObjectPath::try_from("/").unwrap();
 
-        nm_proxy.set_property("NetworkingEnabled", enabled)?;
+        if let Some((conn_path, uuid)) = self.find_bluetooth_profile(&bdaddr, config.tether_type)? {
+            let rx = self.watch_activation(&device_path, uuid.clone(), || {
+                let _active: zbus::zvariant::OwnedObjectPath =
+                    nm_proxy.call("ActivateConnection", &(&conn_path, &device_path, &any_path))?;
+                Ok(())
+            })?;
+            return Ok((uuid, rx));
+        }
 
-        let current_state: bool = nm_proxy.get_property("NetworkingEnabled")?;
-        Ok(current_state)
+        let device_name = self
+            .get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device.Bluetooth")
+            .ok()
+            .and_then(|props| Self::extract_string_from_dict(&props, "Name"))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| config.address.clone());
+
+        let uuid = Uuid::new_v4().to_string();
+        let mut connection_section: HashMap<String, Value> = HashMap::new();
+        connection_section.insert(
+            "id".to_string(),
+            Value::from(config.id.clone().unwrap_or_else(|| format!("{} Network", device_name))),
+        );
+        connection_section.insert("uuid".to_string(), Value::from(uuid.clone()));
+        connection_section.insert("type".to_string(), Value::from("bluetooth"));
+        // A tether costs the phone battery and often data; it is brought up
+        // by hand, like the phone's own hotspot.
+        connection_section.insert("autoconnect".to_string(), Value::from(false));
+
+        let mut ipv4_section: HashMap<String, Value> = HashMap::new();
+        ipv4_section.insert("method".to_string(), Value::from("auto"));
+        let mut ipv6_section: HashMap<String, Value> = HashMap::new();
+        ipv6_section.insert("method".to_string(), Value::from("auto"));
+
+        let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+        settings.insert("connection".to_string(), connection_section);
+        settings.extend(sections);
+        settings.insert("ipv4".to_string(), ipv4_section);
+        settings.insert("ipv6".to_string(), ipv6_section);
+
+        let rx = self.watch_activation(&device_path, uuid.clone(), || {
+            let _paths: (zbus::zvariant::OwnedObjectPath, zbus::zvariant::OwnedObjectPath) =
+                nm_proxy.call("AddAndActivateConnection", &(settings, &device_path, &any_path))?;
+            Ok(())
+        })?;
+        Ok((uuid, rx))
     }
 
-    /// Get wireless enabled state
-    pub fn get_wireless_enabled(&self) -> Result<bool> {
-        let nm_proxy = zbus::blocking::Proxy::new(
-            &self.connection,
-            "org.freedesktop.NetworkManager",
-            "/org/freedesktop/NetworkManager",
-            "org.freedesktop.NetworkManager",
-        )?;
-        Ok(nm_proxy.get_property("WirelessEnabled")?)
+    /// Every modem ModemManager exports, with the properties of each of its
+    /// interfaces. No ModemManager on the bus means no modems, not an error:
+    /// most machines have neither.
+    fn managed_modems(&self) -> Vec<ModemObject> {
+        let Ok(object_manager) = zbus::blocking::fdo::ObjectManagerProxy::builder(&self.connection)
+            .destination("org.freedesktop.ModemManager1")
+            .and_then(|builder| builder.path("/org/freedesktop/ModemManager1"))
+            .and_then(|builder| builder.build())
+        else {
+            return Vec::new();
+        };
+        let Ok(objects) = object_manager.get_managed_objects() else {
+            return Vec::new();
+        };
+
+        let mut modems: Vec<_> = objects
+            .into_iter()
+            .filter(|(_, interfaces)| {
+                interfaces
+                    .keys()
+                    .any(|name| name.as_str() == "org.freedesktop.ModemManager1.Modem")
+            })
+            .map(|(path, interfaces)| {
+                let interfaces = interfaces
+                    .into_iter()
+                    .map(|(name, props)| (name.to_string(), props))
+                    .collect();
+                (path.to_string(), interfaces)
+            })
+            .collect();
+        modems.sort_by(|a, b| a.0.cmp(&b.0));
+        modems
     }
 
-    /// Set wireless enabled state
-    pub fn set_wireless_enabled(&self, enabled: bool) -> Result<()> {
-        let nm_proxy = zbus::blocking::Proxy::new(
-            &self.connection,
-            "org.freedesktop.NetworkManager",
-            "/org/freedesktop/NetworkManager",
-            "org.freedesktop.NetworkManager",
-        )?;
-        nm_proxy.set_property("WirelessEnabled", enabled)?;
-        Ok(())
+    /// NetworkManager's interface for each modem, keyed by the ModemManager
+    /// object path, which NetworkManager reports as the device's `Udi`.
+    fn modem_interfaces(&self) -> HashMap<String, String> {
+        let mut interfaces = HashMap::new();
+        let Ok(device_paths) = self.device_paths() else {
+            return interfaces;
+        };
+        for device_path in device_paths {
+            let Ok(props) =
+                self.get_all_properties(&device_path, "org.freedesktop.NetworkManager.Device")
+            else {
+                continue;
+            };
+            if NetworkManagerHelpers::device_type_from_nm(
+                Self::extract_u32_from_dict(&props, "DeviceType").unwrap_or(0),
+            ) != DeviceType::Modem
+            {
+                continue;
+            }
+            if let (Some(udi), Some(interface)) = (
+                Self::extract_string_from_dict(&props, "Udi"),
+                Self::extract_string_from_dict(&props, "Interface"),
+            ) {
+                interfaces.insert(udi, interface);
+            }
+        }
+        interfaces
     }
 
-    /// Check if wireless device is available
-    pub fn is_wireless_available(&self) -> Result<bool> {
-        Ok(!self.wireless_device_paths()?.is_empty())
+    fn modem_id(path: &str) -> String {
+        path.rsplit('/').next().unwrap_or(path).to_string()
     }
 
-    /// Listen for network changes
-    pub fn listen_network_changes(&self) -> Result<mpsc::Receiver<NetworkInfo>> {
-        let (tx, rx) = mpsc::channel();
-        let connection_clone = self.connection.clone();
-        let app_handle = self.app.clone();
+    /// `SignalQuality` is `(ub)`: percent, and whether it is recent.
+    fn modem_signal_quality(modem: &HashMap<String, zbus::zvariant::OwnedValue>) -> (u8, bool) {
+        match modem.get("SignalQuality").map(|v| -> &Value { v }) {
+            Some(Value::Structure(quality)) => match quality.fields() {
+                [Value::U32(percent), Value::Bool(recent)] => ((*percent).min(100) as u8, *recent),
+                _ => (0, false),
+            },
+            _ => (0, false),
+        }
+    }
 
-        std::thread::spawn(move || {
-            if let Ok(proxy) = zbus::blocking::Proxy::new(
-                &connection_clone,
-                "org.freedesktop.NetworkManager",
-                "/org/freedesktop/NetworkManager",
-                "org.freedesktop.NetworkManager",
-            ) {
-                if let Ok(mut signal) = proxy.receive_signal("StateChanged") {
-                    let proxy_props = zbus::blocking::fdo::PropertiesProxy::builder(
-                        &connection_clone,
+    /// Modems ModemManager knows about.
+    pub fn list_modems(&self) -> Result<Vec<ModemInfo>> {
+        let interfaces = self.modem_interfaces();
+
+        Ok(self
+            .managed_modems()
+            .into_iter()
+            .map(|(path, modem_interfaces)| {
+                let modem = &modem_interfaces["org.freedesktop.ModemManager1.Modem"];
+                let non_empty =
+                    |key: &str| Self::extract_string_from_dict(modem, key).filter(|s| !s.is_empty());
+                ModemInfo {
+                    id: Self::modem_id(&path),
+                    interface: interfaces.get(&path).cloned(),
+                    manufacturer: non_empty("Manufacturer"),
+                    model: non_empty("Model"),
+                    revision: non_empty("Revision"),
+                    equipment_identifier: non_empty("EquipmentIdentifier"),
+                    state: NetworkManagerHelpers::modem_state_from_mm(
+                        modem
+                            .get("State")
+                            .and_then(|v| v.downcast_ref::<i32>().ok())
+                            .unwrap_or(0),
+                    ),
+                    capabilities: NetworkManagerHelpers::modem_capability_names(
+                        Self::extract_u32_from_dict(modem, "CurrentCapabilities").unwrap_or(0),
+                    ),
+                    unlock_required: NetworkManagerHelpers::modem_lock_name(
+                        Self::extract_u32_from_dict(modem, "UnlockRequired").unwrap_or(0),
                     )
-                    .destination("org.freedesktop.NetworkManager")
-                    .unwrap()
-                    .path("/org/freedesktop/NetworkManager")
-                    .unwrap()
-                    .build()
-                    .unwrap();
-
-                    while let Some(_msg) = signal.next() {
-                        let network_manager = VSKNetworkManager {
-                            connection: connection_clone.clone(),
-                            proxy: proxy_props.clone(),
-                            app: app_handle.clone(),
-                        };
+                    .map(str::to_string),
+                    own_numbers: modem
+                        .get("OwnNumbers")
+                        .map(|v| NetworkManagerHelpers::strings_from_value(v))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect())
+    }
 
-                        if let Ok(network_info) =
-                            network_manager.get_current_network_state()
-                        {
-                            if tx.send(network_info).is_err() {
-                                break;
-                            }
-                        }
-                    }
+    /// Operator, access technology, signal and registration of a modem,
+    /// named by id or NetworkManager interface; the first modem without one.
+    pub fn get_modem_status(&self, modem: Option<&str>) -> Result<ModemStatus> {
+        let interfaces = self.modem_interfaces();
+        let (path, modem_interfaces) = self
+            .managed_modems()
+            .into_iter()
+            .find(|(path, _)| match modem {
+                Some(modem) => {
+                    Self::modem_id(path) == modem
+                        || interfaces.get(path).map(String::as_str) == Some(modem)
                 }
-            }
-        });
+                None => true,
+            })
+            .ok_or_else(|| {
+                crate::error::NetworkError::DeviceNotFound(
+                    modem.map(|m| format!("modem {}", m)).unwrap_or_else(|| "no modem".to_string()),
+                )
+            })?;
+
+        let props = &modem_interfaces["org.freedesktop.ModemManager1.Modem"];
+        let (signal_quality, signal_recent) = Self::modem_signal_quality(props);
+        let mut status = ModemStatus {
+            id: Self::modem_id(&path),
+            interface: interfaces.get(&path).cloned(),
+            state: NetworkManagerHelpers::modem_state_from_mm(
+                props.get("State").and_then(|v| v.downcast_ref::<i32>().ok()).unwrap_or(0),
+            ),
+            operator_name: None,
+            operator_code: None,
+            access_technologies: NetworkManagerHelpers::access_technology_names(
+                Self::extract_u32_from_dict(props, "AccessTechnologies").unwrap_or(0),
+            ),
+            signal_quality,
+            signal_recent,
+            registration_state: RegistrationState::Unknown,
+        };
 
-        Ok(rx)
-    }
+        if let Some(gpp) = modem_interfaces.get("org.freedesktop.ModemManager1.Modem.Modem3gpp") {
+            status.operator_name =
+                Self::extract_string_from_dict(gpp, "OperatorName").filter(|s| !s.is_empty());
+            status.operator_code =
+                Self::extract_string_from_dict(gpp, "OperatorCode").filter(|s| !s.is_empty());
+            status.registration_state = NetworkManagerHelpers::registration_state_from_3gpp(
+                Self::extract_u32_from_dict(gpp, "RegistrationState").unwrap_or(4),
+            );
+        } else if let Some(cdma) = modem_interfaces.get("org.freedesktop.ModemManager1.Modem.ModemCdma") {
+            // EV-DO is the data network; 1x only counts when it is all there is.
+            let evdo = NetworkManagerHelpers::registration_state_from_cdma(
+                Self::extract_u32_from_dict(cdma, "EvdoRegistrationState").unwrap_or(0),
+            );
+            status.registration_state = if evdo == RegistrationState::Unknown {
+                NetworkManagerHelpers::registration_state_from_cdma(
+                    Self::extract_u32_from_dict(cdma, "Cdma1xRegistrationState").unwrap_or(0),
+                )
+            } else {
+                evdo
+            };
+        }
 
-    /// Disconnect from the current WiFi network
-    pub fn disconnect_from_wifi(&self) -> Result<()> {
-        let nm_proxy = zbus::blocking::Proxy::new(
-            &self.connection,
-            "org.freedesktop.NetworkManager",
-            "/org/freedesktop/NetworkManager",
-            "org.freedesktop.NetworkManager",
-        )?;
+        Ok(status)
+    }
 
-        // Obtener las conexiones activas
-        let active_connections_variant: zbus::zvariant::OwnedValue = self.proxy.get(
-            InterfaceName::from_static_str_unchecked("org.freedesktop.NetworkManager"),
-            "ActiveConnections",
-        )?;
+    /// Adds a GSM or CDMA profile through Settings `AddConnection`. It is not
+    /// activated; with autoconnect on, NetworkManager brings it up once the
+    /// modem registers.
+    pub fn create_mobile_broadband_connection(
+        &self,
+        config: MobileBroadbandConfig,
+    ) -> Result<ConnectionRef> {
+        let (connection_type, section) = NetworkManagerHelpers::mobile_broadband_section(&config)?;
 
-        // Convertir el valor a un vector de ObjectPath
-        let active_connections = match active_connections_variant.downcast_ref() {
-            Ok(zbus::zvariant::Value::Array(arr)) => arr
-                .iter()
-                .filter_map(|v| match v {
-                    zbus::zvariant::Value::ObjectPath(path) => {
-                        Some(zbus::zvariant::OwnedObjectPath::from(path.to_owned()))
-                    }
-                    _ => None,
-                })
-                .collect::<Vec<zbus::zvariant::OwnedObjectPath>>(),
-            _ => Vec::new(),
-        };
+        let uuid = Uuid::new_v4().to_string();
+        let id = config.id.clone().unwrap_or_else(|| {
+            config
+                .apn
+                .clone()
+                .filter(|apn| !apn.is_empty())
+                .unwrap_or_else(|| "Mobile broadband".to_string())
+        });
 
-        if !active_connections.is_empty() {
-            nm_proxy.call::<_, _, ()>("DeactivateConnection", &(&active_connections[0],))?;
-            Ok(())
-        } else {
-            Ok(())
+        let mut connection_section: HashMap<String, Value> = HashMap::new();
+        connection_section.insert("id".to_string(), Value::from(id.clone()));
+        connection_section.insert("uuid".to_string(), Value::from(uuid.clone()));
+        connection_section.insert("type".to_string(), Value::from(connection_type));
+        connection_section.insert(
+            "autoconnect".to_string(),
+            Value::from(config.autoconnect.unwrap_or(true)),
+        );
+        if let Some(interface) = &config.interface {
+            connection_section.insert("interface-name".to_string(), Value::from(interface.clone()));
         }
+
+        let mut ipv4_section: HashMap<String, Value> = HashMap::new();
+        ipv4_section.insert("method".to_string(), Value::from("auto"));
+        let mut ipv6_section: HashMap<String, Value> = HashMap::new();
+        ipv6_section.insert("method".to_string(), Value::from("auto"));
+
+        let mut settings: HashMap<String, HashMap<String, Value>> = HashMap::new();
+        settings.insert("connection".to_string(), connection_section);
+        settings.insert(connection_type.to_string(), section);
+        settings.insert("ipv4".to_string(), ipv4_section);
+        settings.insert("ipv6".to_string(), ipv6_section);
+        self.add_connection(settings)?;
+
+        Ok(ConnectionRef {
+            id,
+            uuid,
+            connection_type: connection_type.to_string(),
+        })
     }
 
     /// Get the list of saved WiFi networks
@@ -1003,6 +3096,13 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
                         let mut network_info = NetworkInfo::default();
                         network_info.connection_type = "wifi".to_string();
 
+                        if let Some(uuid) = connection_dict.get("uuid") {
+                            let v_uuid: &zbus::zvariant::Value<'_> = uuid;
+                            if let Ok(uuid) = v_uuid.downcast_ref::<String>() {
+                                network_info.uuid = Some(uuid);
+                            }
+                        }
+
                         // Obtener el nombre de la conexión
                         if let Some(id) = connection_dict.get("id") {
                             let v_id: &zbus::zvariant::Value<'_> = id;
@@ -1022,7 +3122,10 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
 
                             if let Some(ssid) = wireless_dict.get("ssid") {
                                 let v_ssid: &zbus::zvariant::Value<'_> = ssid;
-                                network_info.ssid = NetworkManagerHelpers::ssid_from_value(v_ssid);
+                                if let Some(bytes) = NetworkManagerHelpers::ssid_bytes_from_value(v_ssid) {
+                                    network_info.ssid = NetworkManagerHelpers::ssid_display(&bytes);
+                                    network_info.ssid_hex = NetworkManagerHelpers::ssid_to_hex(&bytes);
+                                }
                             }
                         }
 
@@ -1070,8 +3173,365 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
         Ok(saved_networks)
     }
 
-    /// Delete a saved WiFi connection by SSID
-    pub fn delete_wifi_connection(&self, ssid: &str) -> Result<bool> {
+    /// MAC address policy of a saved Wi-Fi or ethernet connection, `None`
+    /// when it follows NetworkManager's default.
+    pub fn get_mac_address_policy(&self, uuid: &str) -> Result<Option<MacAddressPolicy>> {
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let settings = self.get_connection_settings(&conn_path)?;
+
+        let connection_type = settings
+            .get("connection")
+            .and_then(|dict| Self::extract_string_from_dict(dict, "type"))
+            .unwrap_or_default();
+        let section = Self::link_section_for(&connection_type).ok_or_else(|| {
+            crate::error::NetworkError::InvalidSettings(format!(
+                "{} connections have no MAC address",
+                connection_type
+            ))
+        })?;
+
+        Ok(settings
+            .get(section)
+            .and_then(|dict| Self::extract_string_from_dict(dict, "assigned-mac-address"))
+            .and_then(|value| NetworkManagerHelpers::mac_policy_from_setting(&value)))
+    }
+
+    /// Sets, or with `None` clears, the MAC address policy of a saved
+    /// connection. Takes effect the next time it is activated.
+    pub fn set_mac_address_policy(
+        &self,
+        uuid: &str,
+        policy: Option<MacAddressPolicy>,
+    ) -> Result<()> {
+        let setting = policy
+            .as_ref()
+            .map(NetworkManagerHelpers::mac_policy_to_setting)
+            .transpose()?;
+
+        self.update_connection_settings(uuid, |settings| {
+            let connection_type = settings
+                .get("connection")
+                .and_then(|dict| dict.get("type"))
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .unwrap_or_default();
+            let section = Self::link_section_for(&connection_type).ok_or_else(|| {
+                crate::error::NetworkError::InvalidSettings(format!(
+                    "{} connections have no MAC address",
+                    connection_type
+                ))
+            })?;
+
+            let link = settings.entry(section.to_string()).or_default();
+            // The old `ay` form wins over `assigned-mac-address` when both
+            // are present, so it has to go.
+            link.remove("cloned-mac-address");
+            match setting {
+                Some(setting) => {
+                    link.insert("assigned-mac-address".to_string(), Value::from(setting));
+                }
+                None => {
+                    link.remove("assigned-mac-address");
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Saved IPv4 and IPv6 settings of a connection.
+    pub fn get_ip_settings(&self, uuid: &str) -> Result<IpSettings> {
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let settings = self.get_connection_settings(&conn_path)?;
+
+        Ok(IpSettings {
+            ipv4: settings
+                .get("ipv4")
+                .map(|section| NetworkManagerHelpers::ip_family_settings_from_section(section, false)),
+            ipv6: settings
+                .get("ipv6")
+                .map(|section| NetworkManagerHelpers::ip_family_settings_from_section(section, true)),
+        })
+    }
+
+    /// The `proxy` section of a saved connection.
+    pub fn get_proxy_settings(&self, uuid: &str) -> Result<ProxySettings> {
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let settings = self.get_connection_settings(&conn_path)?;
+        Ok(NetworkManagerHelpers::proxy_settings_from_section(settings.get("proxy")))
+    }
+
+    /// Replaces the `proxy` section of a saved connection. NetworkManager
+    /// hands it to PacRunner the next time the connection comes up.
+    pub fn set_proxy_settings(&self, uuid: &str, proxy: ProxySettings) -> Result<()> {
+        let values = NetworkManagerHelpers::proxy_settings_to_values(&proxy)?;
+        self.update_connection_settings(uuid, |settings| {
+            settings.insert("proxy".to_string(), values);
+            Ok(())
+        })
+    }
+
+    /// Proxy configuration of the primary connection. With the auto method
+    /// and no PAC file configured, the URL the DHCP server announced through
+    /// WPAD is reported instead; NetworkManager only asks for it then.
+    pub fn get_effective_proxy(&self) -> Result<EffectiveProxy> {
+        let primary = self
+            .primary_connection_path()
+            .ok_or(crate::error::NetworkError::NoConnection)?;
+        let active =
+            self.get_all_properties(&primary, "org.freedesktop.NetworkManager.Connection.Active")?;
+        let uuid = Self::extract_string_from_dict(&active, "Uuid")
+            .ok_or(crate::error::NetworkError::NoConnection)?;
+        let settings = self.get_proxy_settings(&uuid)?;
+
+        let mut pac_url = None;
+        let mut wpad = false;
+        if settings.method == ProxyMethod::Auto {
+            pac_url = settings.pac_url.clone();
+            if pac_url.is_none() && settings.pac_script.is_none() {
+                pac_url = Self::extract_path_from_dict(&active, "Dhcp4Config")
+                    .and_then(|path| self.dhcp_options(&path, false).remove("wpad"))
+                    .filter(|url| !url.is_empty());
+                wpad = pac_url.is_some();
+            }
+        }
+
+        Ok(EffectiveProxy {
+            connection_id: Self::extract_string_from_dict(&active, "Id").unwrap_or_default(),
+            uuid,
+            interface: Self::extract_paths_from_dict(&active, "Devices")
+                .first()
+                .and_then(|device| self.device_interface(device)),
+            settings,
+            pac_url,
+            wpad,
+        })
+    }
+
+    /// Saves new IP settings on a connection, and with `reapply` pushes them
+    /// to the device it is active on without taking the link down.
+    ///
+    /// Both families are validated before anything is written.
+    pub fn update_ip_settings(&self, uuid: &str, ip: IpSettings, reapply: bool) -> Result<()> {
+        let mut sections = Vec::new();
+        if let Some(ipv4) = &ip.ipv4 {
+            sections.push(("ipv4", NetworkManagerHelpers::ip_family_settings_to_values(ipv4, false)?));
+        }
+        if let Some(ipv6) = &ip.ipv6 {
+            sections.push(("ipv6", NetworkManagerHelpers::ip_family_settings_to_values(ipv6, true)?));
+        }
+        if sections.is_empty() {
+            return Ok(());
+        }
+
+        self.update_connection_settings(uuid, |settings| {
+            for (name, values) in sections {
+                let section = settings.entry(name.to_string()).or_default();
+                // The deprecated forms win over the ones written here when
+                // both are sent, and GetSettings hands back both.
+                for key in ["addresses", "routes", "dns-data", "gateway", "route-metric"] {
+                    section.remove(key);
+                }
+                section.extend(values);
+            }
+            Ok(())
+        })?;
+
+        if reapply {
+            self.reapply_if_active(uuid)?;
+        }
+
+        Ok(())
+    }
+
+    /// Pushes the saved profile to the device it is active on, without
+    /// taking the link down. Nothing to do when the connection is not up.
+    fn reapply_if_active(&self, uuid: &str) -> Result<()> {
+        let device_path = match self.device_path_by_active_uuid(uuid) {
+            Ok(path) => path,
+            Err(crate::error::NetworkError::NoConnection) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let device_proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            "org.freedesktop.NetworkManager",
+            device_path.as_str(),
+            "org.freedesktop.NetworkManager.Device",
+        )?;
+        // Empty settings and version 0: reapply the saved profile as it
+        // is now, whatever version is applied.
+        device_proxy.call::<_, _, ()>(
+            "Reapply",
+            &(HashMap::<String, HashMap<String, Value>>::new(), 0u64, 0u32),
+        )?;
+        Ok(())
+    }
+
+    /// Autoconnect priority and route metrics of a saved connection.
+    pub fn get_connection_priority(&self, uuid: &str) -> Result<ConnectionPriority> {
+        let conn_path = self.connection_path_by_uuid(uuid)?;
+        let settings = self.get_connection_settings(&conn_path)?;
+        Ok(NetworkManagerHelpers::connection_priority_from_settings(&settings))
+    }
+
+    /// Replaces the autoconnect priority and route metrics of a saved
+    /// connection; an absent metric goes back to the per-type default. With
+    /// `reapply` the metrics reach the routes of an active connection right
+    /// away, which is what moves traffic from one link to another.
+    pub fn set_connection_priority(
+        &self,
+        uuid: &str,
+        priority: ConnectionPriority,
+        reapply: bool,
+    ) -> Result<()> {
+        NetworkManagerHelpers::validate_connection_priority(&priority)?;
+
+        self.update_connection_settings(uuid, |settings| {
+            settings
+                .entry("connection".to_string())
+                .or_default()
+                .insert(
+                    "autoconnect-priority".to_string(),
+                    Value::from(priority.autoconnect_priority),
+                );
+            for (name, metric) in [
+                ("ipv4", priority.ipv4_route_metric),
+                ("ipv6", priority.ipv6_route_metric),
+            ] {
+                // A family the profile leaves out only gets a section when
+                // there is a metric to put in it.
+                match (settings.get_mut(name), metric) {
+                    (Some(section), Some(metric)) => {
+                        section.insert("route-metric".to_string(), Value::from(metric));
+                    }
+                    (Some(section), None) => {
+                        section.remove("route-metric");
+                    }
+                    (None, Some(metric)) => {
+                        settings
+                            .entry(name.to_string())
+                            .or_default()
+                            .insert("route-metric".to_string(), Value::from(metric));
+                    }
+                    (None, None) => {}
+                }
+            }
+            Ok(())
+        })?;
+
+        if reapply {
+            self.reapply_if_active(uuid)?;
+        }
+        Ok(())
+    }
+
+    /// Ranks saved connections, most preferred first, by rewriting their
+    /// autoconnect priorities. Connections left out keep theirs. Every UUID
+    /// is checked before any profile is touched, and if a write still fails
+    /// the profiles already written get their old priority back, so the
+    /// order is never left half applied.
+    pub fn reorder_preferred_networks(&self, uuids: &[String]) -> Result<()> {
+        let priorities = NetworkManagerHelpers::priorities_for_order(uuids)?;
+        let mut previous = HashMap::new();
+        for uuid in uuids {
+            previous.insert(uuid.as_str(), self.get_connection_priority(uuid)?.autoconnect_priority);
+        }
+
+        let write = |uuid: &str, priority: i32| {
+            self.update_connection_settings(uuid, |settings| {
+                settings
+                    .entry("connection".to_string())
+                    .or_default()
+                    .insert("autoconnect-priority".to_string(), Value::from(priority));
+                Ok(())
+            })
+        };
+        for (written, (uuid, priority)) in priorities.iter().enumerate() {
+            if let Err(e) = write(uuid, *priority) {
+                for (uuid, _) in &priorities[..written] {
+                    if let Err(undo) = write(uuid, previous[uuid.as_str()]) {
+                        log::warn!("could not restore the priority of {}: {}", uuid, undo);
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// The default routes of every active connection, per family and lowest
+    /// metric first, which is the order the kernel uses them in.
+    pub fn get_default_route_order(&self) -> Result<DefaultRouteOrder> {
+        let mut order = DefaultRouteOrder::default();
+
+        for active_path in self.active_connection_paths()? {
+            // Connections can finish deactivating while being listed.
+            let Ok(props) = self.get_all_properties(
+                &active_path,
+                "org.freedesktop.NetworkManager.Connection.Active",
+            ) else {
+                continue;
+            };
+            let uuid = Self::extract_string_from_dict(&props, "Uuid").unwrap_or_default();
+            let connection_id = Self::extract_string_from_dict(&props, "Id").unwrap_or_default();
+
+            // The IP configuration of a VPN hangs off the active connection,
+            // not off the device it runs over.
+            for (key, ipv6) in [("Ip4Config", false), ("Ip6Config", true)] {
+                let Some(config_path) = Self::extract_path_from_dict(&props, key) else {
+                    continue;
+                };
+                let Ok(config) = self.ip_family_configuration(&config_path, ipv6) else {
+                    continue;
+                };
+                let Some(route) = NetworkManagerHelpers::default_route(&config.routes) else {
+                    continue;
+                };
+                let interface = Self::extract_paths_from_dict(&props, "Devices")
+                    .into_iter()
+                    .next()
+                    .and_then(|device| self.device_ip_interface(&device))
+                    .unwrap_or_default();
+
+                let entry = DefaultRoute {
+                    uuid: uuid.clone(),
+                    connection_id: connection_id.clone(),
+                    interface,
+                    gateway: route.next_hop.clone(),
+                    metric: route.metric.unwrap_or(0),
+                };
+                if ipv6 {
+                    order.ipv6.push(entry);
+                } else {
+                    order.ipv4.push(entry);
+                }
+            }
+        }
+
+        order.ipv4.sort_by_key(|route| route.metric);
+        order.ipv6.sort_by_key(|route| route.metric);
+        Ok(order)
+    }
+
+    /// Marks a saved connection as metered or not, or with `Unknown` leaves
+    /// it to NetworkManager's guess. Reapplied right away when the
+    /// connection is up, so `Device.Metered` and `metered-changed` follow.
+    pub fn set_connection_metered(&self, uuid: &str, metered: MeteredState) -> Result<()> {
+        let setting = NetworkManagerHelpers::metered_to_setting(metered)?;
+        self.update_connection_settings(uuid, |settings| {
+            settings
+                .entry("connection".to_string())
+                .or_default()
+                .insert("metered".to_string(), Value::from(setting));
+            Ok(())
+        })?;
+        self.reapply_if_active(uuid)
+    }
+
+    /// Delete a saved WiFi connection by SSID.
+    ///
+    /// Matches on the raw bytes: `ssid_hex` when given, `ssid` as UTF-8 otherwise.
+    pub fn delete_wifi_connection(&self, ssid: &str, ssid_hex: Option<&str>) -> Result<bool> {
+        let target = NetworkManagerHelpers::ssid_bytes(ssid, ssid_hex)?;
+
         // Crear un proxy para el servicio de configuración de NetworkManager
         let settings_proxy = zbus::blocking::Proxy::new(
             &self.connection,
@@ -1127,9 +3587,9 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
 
                             if let Some(ssid_value) = wireless_dict.get("ssid") {
                                 let v_ssid: &zbus::zvariant::Value<'_> = ssid_value;
-                                let conn_ssid_str = NetworkManagerHelpers::ssid_from_value(v_ssid);
+                                let conn_ssid = NetworkManagerHelpers::ssid_bytes_from_value(v_ssid);
                                 // Si el SSID coincide, eliminar la conexión
-                                if conn_ssid_str == ssid {
+                                if conn_ssid.as_deref() == Some(target.as_slice()) {
                                     conn_proxy.call::<_, _, ()>("Delete", &())?;
                                     return Ok(true);
                                 }
@@ -1316,7 +3776,7 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
             if ip4_path.as_str() != "/" {
                 let ip4_props = zbus::blocking::fdo::PropertiesProxy::builder(&self.connection)
                     .destination("org.freedesktop.NetworkManager")?
-                    .path(ip4_path)?
+                    .path(&ip4_path)?
                     .build()?;
 
                 let leer_ip = |propiedad: &'static str| {
@@ -1337,17 +3797,16 @@ impl<R: Runtime> VSKNetworkManager<'static, R> {
 
                 // La dirección dentro del túnel: es lo que alguien mira para
                 // saber con qué IP lo ve la red del otro lado.
-                if let Ok(addresses) = leer_ip("Addresses") {
-                    if let Ok(zbus::zvariant::Value::Array(arr)) = addresses.downcast_ref() {
-                        if let Some(zbus::zvariant::Value::Array(tupla)) = arr.first() {
-                            if let Some(zbus::zvariant::Value::U32(entero)) = tupla.first() {
-                                use std::net::Ipv4Addr;
-                                status.ip_address =
-                                    Some(Ipv4Addr::from(entero.to_be()).to_string());
-                            }
-                        }
-                    }
-                }
+                status.ipv4_addresses = self.ip_config_addresses(&ip4_path.into(), false);
+                status.ip_address = status.ipv4_addresses.first().map(|a| a.address.clone());
+            }
+        }
+
+        if let Ok(zbus::zvariant::Value::ObjectPath(ip6_path)) =
+            leer("Ip6Config")?.downcast_ref()
+        {
+            if ip6_path.as_str() != "/" {
+                status.ipv6_addresses = self.ip_config_addresses(&ip6_path.into(), true);
             }
         }
 
@@ -68,4 +68,16 @@ pub enum NetworkError {
 
     #[error("No active VPN connection")]
     VpnNotActive,
+
+    #[error("Invalid SSID: {0}")]
+    InvalidSsid(String),
+
+    #[error("Connection not found: {0}")]
+    ConnectionNotFound(String),
+
+    #[error("Invalid connection settings: {0}")]
+    InvalidSettings(String),
+
+    #[error("Device not found: {0}")]
+    DeviceNotFound(String),
 }
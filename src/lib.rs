@@ -3,12 +3,36 @@ use commands::{
     disconnect_from_wifi, disconnect_vpn, get_network_state, get_vpn_status,
     get_saved_wifi_networks, list_wifi_networks, rescan_wifi, toggle_network_state,
     get_wireless_enabled, list_vpn_profiles, set_wireless_enabled, is_wireless_available,
-    update_vpn_profile, get_network_stats, get_network_interfaces
+    update_vpn_profile, get_network_stats, get_network_interfaces, start_wifi_scanner,
+    stop_wifi_scanner, get_mac_address_policy, set_mac_address_policy, list_devices,
+    list_active_connections, get_ip_configuration, get_ip_settings, update_ip_settings,
+    disconnect_device, set_device_managed, set_device_autoconnect, configure_wired_8021x,
+    list_virtual_interfaces, create_virtual_interface, update_virtual_interface,
+    delete_virtual_interface, list_modems, get_modem_status, create_mobile_broadband_connection,
+    list_bluetooth_devices, connect_bluetooth_tether, get_proxy_settings, set_proxy_settings,
+    get_effective_proxy, set_connection_metered, get_connection_priority, set_connection_priority,
+    reorder_preferred_networks, get_default_route_order, get_wired_link_info,
+    start_bandwidth_sampler, stop_bandwidth_sampler, get_bandwidth_history, get_interface_statistics,
+    check_connectivity, set_connectivity_probe, get_data_usage, set_data_quota, ping, start_ping_monitor, stop_ping_monitor,
 };
 pub use models::{
-    NetworkInfo, VpnConnectionState, VpnCreateConfig, VpnEventPayload, VpnProfile, VpnStatus,
-    VpnUpdateConfig, WiFiConnectionConfig, WiFiSecurityType,
+    ActiveConnectionInfo, ActiveConnectionState, BondConfig, BondMode, BridgeConfig,
+    ConnectionActivation, ConnectionRef, VirtualInterfaceConfig, VirtualInterfaceKind,
+    VirtualInterfaceProfile, VlanConfig, MobileBroadbandConfig, MobileBroadbandType, ModemInfo,
+    ModemState, ModemStatus, RegistrationState, BluetoothDeviceInfo, BluetoothTetherConfig,
+    BluetoothTetherType, EffectiveProxy, ManualProxy, ProxyMethod, ProxySettings,
+    MeteredChangedEvent, MeteredState, ConnectionPriority, DefaultRoute, DefaultRouteOrder,
+    CableEvent, Duplex, WiredLinkInfo, BandwidthPoint, BandwidthSamplerConfig, NetworkStats,
+    ErrorRates, InterfaceCounters, InterfaceStatistics, DailyUsage, DataQuota,
+    DataQuotaWarningEvent, DataUsage, DataUsageRange, PingConfig, PingMethod, PingMonitorConfig,
+    PingResult, PingTarget, CaptivePortalEvent, ConnectivityProbeConfig, ConnectivitySource,
+    ConnectivityStatus,
+    ConnectivityState, Eap8021xConfig, EapMethod, IpAddressInfo, Phase2Auth,
+    DhcpLease, IpConfiguration, IpFamilyConfiguration, IpFamilySettings, IpMethod, IpSettings, RouteInfo, DeviceInfo, DeviceState, DeviceType, NetworkInfo, VpnConnectionState, VpnCreateConfig, VpnEventPayload, VpnProfile, VpnStatus,
+    KnownNetworkFilter, MacAddressPolicy, VpnUpdateConfig, WiFiConnectionConfig, WiFiSecurityType,
+    WifiNetworkQuery, WifiScanConfig, WifiSortKey,
 };
+use std::collections::{HashMap, HashSet};
 use std::result::Result;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
@@ -23,19 +47,38 @@ pub mod desktop;
 mod commands;
 pub mod error;
 pub mod models;
+mod bandwidth_sampler;
+mod connectivity;
+mod data_usage;
 mod nm_constants;
 mod nm_helpers;
 mod network_stats;
+mod ping;
+mod wifi_scanner;
 
 pub use crate::error::{NetworkError, Result as NetworkResult};
 
 pub struct NetworkManagerState<R: Runtime> {
     pub manager: Arc<RwLock<Option<crate::models::VSKNetworkManager<'static, R>>>>,
-    pub stats_tracker: Arc<RwLock<Option<crate::network_stats::NetworkStatsTracker>>>,
+    /// One tracker per interface, so switching between them does not lose
+    /// the totals of either.
+    pub stats_trackers: Arc<RwLock<HashMap<String, crate::network_stats::NetworkStatsTracker>>>,
+    /// What the background sampler has measured, per interface.
+    pub bandwidth_history: Arc<RwLock<HashMap<String, crate::bandwidth_sampler::BandwidthHistory>>>,
+    pub bandwidth_sampler: Arc<RwLock<Option<crate::bandwidth_sampler::BandwidthSampler>>>,
+    /// Keeps the previous reading of every interface for error rates.
+    pub interface_statistics: Arc<RwLock<crate::network_stats::InterfaceStatisticsReader>>,
+    /// Bytes per connection and day, loaded from the app data dir at setup.
+    pub data_usage: Arc<RwLock<Option<crate::data_usage::DataUsageLedger>>>,
+    pub ping_monitor: Arc<RwLock<Option<crate::ping::PingMonitor>>>,
+    /// Where the fallback connectivity probe looks.
+    pub connectivity_probe: Arc<RwLock<crate::models::ConnectivityProbeConfig>>,
     pub wifi_networks_cache: Arc<RwLock<Option<WifiNetworksCache>>>,
+    pub wifi_scanner: Arc<RwLock<Option<crate::wifi_scanner::WifiScanner>>>,
 }
 
 pub struct WifiNetworksCache {
+    /// Every access point, unfiltered; queries are applied on the way out.
     pub data: Vec<NetworkInfo>,
     pub fetched_at: Instant,
 }
@@ -44,8 +87,15 @@ impl<R: Runtime> Default for NetworkManagerState<R> {
     fn default() -> Self {
         Self {
             manager: Arc::new(RwLock::new(None)),
-            stats_tracker: Arc::new(RwLock::new(None)),
+            stats_trackers: Arc::new(RwLock::new(HashMap::new())),
+            bandwidth_history: Arc::new(RwLock::new(HashMap::new())),
+            bandwidth_sampler: Arc::new(RwLock::new(None)),
+            interface_statistics: Arc::new(RwLock::new(Default::default())),
+            data_usage: Arc::new(RwLock::new(None)),
+            ping_monitor: Arc::new(RwLock::new(None)),
+            connectivity_probe: Arc::new(RwLock::new(Default::default())),
             wifi_networks_cache: Arc::new(RwLock::new(None)),
+            wifi_scanner: Arc::new(RwLock::new(None)),
         }
     }
 }
@@ -62,6 +112,12 @@ pub fn spawn_network_change_emitter<R: tauri::Runtime>(
         }
     };
 
+    let initial = network_manager.get_current_network_state().ok();
+    let mut last_metered = initial.as_ref().map(|info| info.metered).unwrap_or_default();
+    let mut last_connectivity = initial
+        .map(|info| (info.uuid, info.connectivity))
+        .unwrap_or_default();
+
     std::thread::spawn(move || {
         use std::time::{Duration, Instant};
         use std::sync::mpsc::RecvTimeoutError;
@@ -79,6 +135,8 @@ pub fn spawn_network_change_emitter<R: tauri::Runtime>(
                     // Timeout reached, emit valid pending event
                     if let Some(info) = pending_event.take() {
                         let _ = app.emit("network-changed", &info);
+                        emit_metered_change(&app, &mut last_metered, &info);
+                        check_captive_portal(&app, &network_manager, &mut last_connectivity, &info);
                     }
                     if let Some(vpn_status) = pending_vpn_status.take() {
                         emit_vpn_events(&app, &network_manager, &mut last_vpn_status, vpn_status);
@@ -108,6 +166,8 @@ pub fn spawn_network_change_emitter<R: tauri::Runtime>(
                     Ok(info) => {
                         // Leading emission improves perceived latency for UI updates.
                         let _ = app.emit("network-changed", &info);
+                        emit_metered_change(&app, &mut last_metered, &info);
+                        check_captive_portal(&app, &network_manager, &mut last_connectivity, &info);
                         if let Ok(vpn_status) = network_manager.get_vpn_status() {
                             emit_vpn_events(&app, &network_manager, &mut last_vpn_status, vpn_status);
                         }
@@ -122,6 +182,118 @@ pub fn spawn_network_change_emitter<R: tauri::Runtime>(
     });
 }
 
+/// Forwards cable plug and unplug events to the frontend. Not debounced:
+/// a technician pulling a cable wants to see it at once.
+pub fn spawn_cable_emitter<R: tauri::Runtime>(
+    app: AppHandle<R>,
+    network_manager: &crate::models::VSKNetworkManager<'static, R>,
+) {
+    let rx = match network_manager.listen_cable_changes() {
+        Ok(rx) => rx,
+        Err(e) => {
+            eprintln!("No se pudo escuchar cambios de cable: {:?}", e);
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        for event in rx {
+            let _ = app.emit("cable-changed", &event);
+        }
+    });
+}
+
+/// Emits `metered-changed` when the primary connection's metered state is
+/// not the one last seen, whether the connection itself changed or its
+/// value did.
+fn emit_metered_change<R: tauri::Runtime>(
+    app: &AppHandle<R>,
+    last: &mut crate::models::MeteredState,
+    info: &crate::models::NetworkInfo,
+) {
+    if info.metered == *last {
+        return;
+    }
+    let event = crate::models::MeteredChangedEvent {
+        uuid: info.uuid.clone(),
+        metered: info.metered,
+        previous: *last,
+    };
+    *last = info.metered;
+    let _ = app.emit("metered-changed", &event);
+}
+
+/// Emits `captive-portal-detected` when NetworkManager starts reporting a
+/// portal, or, with its check turned off, when the plugin's probe finds
+/// one behind a connection that just came up. Finding the login page takes
+/// an HTTP request, so it runs on a thread of its own.
+fn check_captive_portal<R: tauri::Runtime>(
+    app: &AppHandle<R>,
+    network_manager: &crate::models::VSKNetworkManager<'static, R>,
+    last: &mut (Option<String>, ConnectivityState),
+    info: &crate::models::NetworkInfo,
+) {
+    let (last_uuid, last_connectivity) =
+        std::mem::replace(last, (info.uuid.clone(), info.connectivity));
+    let became_portal =
+        info.connectivity == ConnectivityState::Portal && last_connectivity != ConnectivityState::Portal;
+    let unchecked_new_connection = info.uuid.is_some()
+        && info.uuid != last_uuid
+        && !network_manager.connectivity_check_enabled();
+    if !became_portal && !unchecked_new_connection {
+        return;
+    }
+
+    let app = app.clone();
+    let (uuid, name) = (info.uuid.clone(), info.name.clone());
+    std::thread::spawn(move || {
+        let Some(state) = app.try_state::<NetworkManagerState<R>>() else {
+            return;
+        };
+        match state.check_connectivity() {
+            Ok(status) if status.state == ConnectivityState::Portal => {
+                let event = crate::models::CaptivePortalEvent {
+                    url: status.portal_url,
+                    uuid,
+                    name,
+                };
+                let _ = app.emit("captive-portal-detected", &event);
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("captive portal check failed: {}", e),
+        }
+    });
+}
+
+/// Forwards an activation watch to the frontend as `network-changed`
+/// events: the device being activated at each step, with the step in
+/// `activation`. A step whose device cannot be read is left out rather
+/// than sent as a network that went down. The thread ends with the watch.
+fn spawn_activation_emitter<R: tauri::Runtime>(
+    app: AppHandle<R>,
+    rx: std::sync::mpsc::Receiver<ConnectionActivation>,
+) {
+    std::thread::spawn(move || {
+        for activation in rx {
+            let Some(state) = app.try_state::<NetworkManagerState<R>>() else {
+                break;
+            };
+            let info = match state.manager.read() {
+                Ok(manager) => match manager.as_ref() {
+                    Some(manager) => manager.activation_network_info(activation),
+                    _none => Err(NetworkError::NotInitialized),
+                },
+                Err(_) => Err(NetworkError::LockError),
+            };
+            match info {
+                Ok(info) => {
+                    let _ = app.emit("network-changed", &info);
+                }
+                Err(e) => log::debug!("activation step not reported: {}", e),
+            }
+        }
+    });
+}
+
 fn resolve_active_vpn_profile<R: tauri::Runtime>(
     network_manager: &crate::models::VSKNetworkManager<'static, R>,
     status: &VpnStatus,
@@ -186,8 +358,15 @@ impl<R: Runtime> NetworkManagerState<R> {
     pub fn new(manager: Option<crate::models::VSKNetworkManager<'static, R>>) -> Self {
         Self {
             manager: Arc::new(RwLock::new(manager)),
-            stats_tracker: Arc::new(RwLock::new(None)),
+            stats_trackers: Arc::new(RwLock::new(HashMap::new())),
+            bandwidth_history: Arc::new(RwLock::new(HashMap::new())),
+            bandwidth_sampler: Arc::new(RwLock::new(None)),
+            interface_statistics: Arc::new(RwLock::new(Default::default())),
+            data_usage: Arc::new(RwLock::new(None)),
+            ping_monitor: Arc::new(RwLock::new(None)),
+            connectivity_probe: Arc::new(RwLock::new(Default::default())),
             wifi_networks_cache: Arc::new(RwLock::new(None)),
+            wifi_scanner: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -195,9 +374,11 @@ impl<R: Runtime> NetworkManagerState<R> {
         &self,
         force_refresh: bool,
         ttl_ms: Option<u64>,
+        query: &WifiNetworkQuery,
     ) -> Result<Vec<NetworkInfo>, NetworkError> {
         let ttl = Duration::from_millis(ttl_ms.unwrap_or(3000).clamp(250, 30000));
 
+        let mut cached = None;
         if !force_refresh {
             let cache = self
                 .wifi_networks_cache
@@ -205,48 +386,107 @@ impl<R: Runtime> NetworkManagerState<R> {
                 .map_err(|_| NetworkError::LockError)?;
             if let Some(cache_entry) = cache.as_ref() {
                 if cache_entry.fetched_at.elapsed() <= ttl {
-                    return Ok(cache_entry.data.clone());
+                    cached = Some(cache_entry.data.clone());
                 }
             }
         }
 
+        let access_points = match cached {
+            Some(access_points) => access_points,
+            None => self.refresh_access_points()?,
+        };
+
+        self.apply_wifi_query(access_points, query)
+    }
+
+    /// Reads every visible access point and replaces the cached scan with it.
+    /// Every path that learns about the air — a listing, a rescan, the
+    /// background scanner — goes through here, so the cache is never left
+    /// empty for the next caller to refill.
+    fn refresh_access_points(&self) -> Result<Vec<NetworkInfo>, NetworkError> {
         let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
-        let networks = match manager.as_ref() {
-            Some(manager) => manager.list_wifi_networks(),
+        let access_points = match manager.as_ref() {
+            Some(manager) => manager.list_access_points(),
             _none => Err(NetworkError::NotInitialized),
         }?;
+        drop(manager);
 
         let mut cache = self
             .wifi_networks_cache
             .write()
             .map_err(|_| NetworkError::LockError)?;
         *cache = Some(WifiNetworksCache {
-            data: networks.clone(),
+            data: access_points.clone(),
             fetched_at: Instant::now(),
         });
 
-        Ok(networks)
+        Ok(access_points)
     }
 
-    pub fn invalidate_wifi_networks_cache(&self) -> Result<(), NetworkError> {
-        let mut cache = self
-            .wifi_networks_cache
-            .write()
-            .map_err(|_| NetworkError::LockError)?;
-        *cache = None;
-        Ok(())
+    fn apply_wifi_query(
+        &self,
+        access_points: Vec<NetworkInfo>,
+        query: &WifiNetworkQuery,
+    ) -> Result<Vec<NetworkInfo>, NetworkError> {
+        // Saved connections are only read when the query asks about them.
+        let known = match query.known {
+            KnownNetworkFilter::All => None,
+            _ => Some(
+                self.get_saved_wifi_networks()?
+                    .into_iter()
+                    .map(|n| n.ssid_hex)
+                    .collect::<HashSet<String>>(),
+            ),
+        };
+
+        Ok(crate::nm_helpers::NetworkManagerHelpers::apply_wifi_query(
+            access_points,
+            query,
+            known.as_ref(),
+        ))
     }
 
     pub fn rescan_wifi(&self) -> Result<Vec<NetworkInfo>, NetworkError> {
         let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
         match manager.as_ref() {
-            Some(manager) => manager.rescan_wifi()?,
+            Some(manager) => manager.request_rescan()?,
             _none => return Err(NetworkError::NotInitialized),
         };
         drop(manager);
 
-        self.invalidate_wifi_networks_cache()?;
-        self.list_wifi_networks(true, None)
+        let access_points = self.refresh_access_points()?;
+        self.apply_wifi_query(access_points, &WifiNetworkQuery::default())
+    }
+
+    pub fn request_wifi_scan(&self, min_gap: Duration) -> Result<bool, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.request_wifi_scan(min_gap),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    /// Starts the background scanner, or changes its pace if it is running.
+    pub fn start_wifi_scanner(
+        &self,
+        app: AppHandle<R>,
+        config: WifiScanConfig,
+    ) -> Result<(), NetworkError> {
+        let mut scanner = self.wifi_scanner.write().map_err(|_| NetworkError::LockError)?;
+        if let Some(running) = scanner.as_ref() {
+            if running.configure(config.clone()).is_ok() {
+                return Ok(());
+            }
+        }
+        *scanner = Some(crate::wifi_scanner::WifiScanner::spawn(app, config));
+        Ok(())
+    }
+
+    pub fn stop_wifi_scanner(&self) -> Result<(), NetworkError> {
+        let mut scanner = self.wifi_scanner.write().map_err(|_| NetworkError::LockError)?;
+        // Dropping the handle is what tells the thread to stop.
+        *scanner = None;
+        Ok(())
     }
 
     pub fn connect_to_wifi(&self, config: WiFiConnectionConfig) -> Result<(), NetworkError> {
@@ -273,10 +513,301 @@ impl<R: Runtime> NetworkManagerState<R> {
         }
     }
 
-    pub fn delete_wifi_connection(&self, ssid: &str) -> Result<bool, NetworkError> {
+    pub fn delete_wifi_connection(
+        &self,
+        ssid: &str,
+        ssid_hex: Option<&str>,
+    ) -> Result<bool, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.delete_wifi_connection(ssid, ssid_hex),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_mac_address_policy(
+        &self,
+        uuid: &str,
+    ) -> Result<Option<MacAddressPolicy>, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_mac_address_policy(uuid),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn set_mac_address_policy(
+        &self,
+        uuid: &str,
+        policy: Option<MacAddressPolicy>,
+    ) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.set_mac_address_policy(uuid, policy),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn list_devices(&self) -> Result<Vec<DeviceInfo>, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.list_devices(),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn list_active_connections(&self) -> Result<Vec<ActiveConnectionInfo>, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.list_active_connections(),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_ip_configuration(
+        &self,
+        interface: Option<&str>,
+        uuid: Option<&str>,
+    ) -> Result<IpConfiguration, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_ip_configuration(interface, uuid),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_ip_settings(&self, uuid: &str) -> Result<IpSettings, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_ip_settings(uuid),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn update_ip_settings(
+        &self,
+        uuid: &str,
+        ip: IpSettings,
+        reapply: bool,
+    ) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.update_ip_settings(uuid, ip, reapply),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn disconnect_device(&self, interface: &str) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.disconnect_device(interface),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn set_device_managed(&self, interface: &str, managed: bool) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.set_device_managed(interface, managed),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn set_device_autoconnect(
+        &self,
+        interface: &str,
+        autoconnect: bool,
+    ) -> Result<(), NetworkError> {
         let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
         match manager.as_ref() {
-            Some(manager) => manager.delete_wifi_connection(ssid),
+            Some(manager) => manager.set_device_autoconnect(interface, autoconnect),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    /// Returns the profile's UUID right away; how the activation goes is
+    /// reported in `activation` of `network-changed` events.
+    pub fn configure_wired_8021x(
+        &self,
+        interface: Option<&str>,
+        uuid: Option<&str>,
+        config: Eap8021xConfig,
+    ) -> Result<String, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => {
+                let (uuid, rx) = manager.configure_wired_8021x(interface, uuid, config)?;
+                spawn_activation_emitter(manager.app.clone(), rx);
+                Ok(uuid)
+            }
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn list_virtual_interfaces(&self) -> Result<Vec<VirtualInterfaceProfile>, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.list_virtual_interfaces(),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn create_virtual_interface(
+        &self,
+        config: VirtualInterfaceConfig,
+    ) -> Result<VirtualInterfaceProfile, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.create_virtual_interface(config),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn update_virtual_interface(
+        &self,
+        uuid: &str,
+        config: VirtualInterfaceConfig,
+    ) -> Result<VirtualInterfaceProfile, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.update_virtual_interface(uuid, config),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn delete_virtual_interface(&self, uuid: &str) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.delete_virtual_interface(uuid),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn list_modems(&self) -> Result<Vec<ModemInfo>, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.list_modems(),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_modem_status(&self, modem: Option<&str>) -> Result<ModemStatus, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_modem_status(modem),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn create_mobile_broadband_connection(
+        &self,
+        config: MobileBroadbandConfig,
+    ) -> Result<ConnectionRef, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.create_mobile_broadband_connection(config),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn list_bluetooth_devices(&self) -> Result<Vec<BluetoothDeviceInfo>, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.list_bluetooth_devices(),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    /// Like `configure_wired_8021x`: the UUID now, the outcome in
+    /// `network-changed` events.
+    pub fn connect_bluetooth_tether(
+        &self,
+        config: BluetoothTetherConfig,
+    ) -> Result<String, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => {
+                let (uuid, rx) = manager.connect_bluetooth_tether(config)?;
+                spawn_activation_emitter(manager.app.clone(), rx);
+                Ok(uuid)
+            }
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_proxy_settings(&self, uuid: &str) -> Result<ProxySettings, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_proxy_settings(uuid),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn set_proxy_settings(&self, uuid: &str, settings: ProxySettings) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.set_proxy_settings(uuid, settings),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_effective_proxy(&self) -> Result<EffectiveProxy, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_effective_proxy(),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn set_connection_metered(&self, uuid: &str, metered: MeteredState) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.set_connection_metered(uuid, metered),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_wired_link_info(&self, interface: Option<&str>) -> Result<WiredLinkInfo, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_wired_link_info(interface),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_connection_priority(&self, uuid: &str) -> Result<ConnectionPriority, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_connection_priority(uuid),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn set_connection_priority(
+        &self,
+        uuid: &str,
+        priority: ConnectionPriority,
+        reapply: bool,
+    ) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.set_connection_priority(uuid, priority, reapply),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn reorder_preferred_networks(&self, uuids: &[String]) -> Result<(), NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.reorder_preferred_networks(uuids),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    pub fn get_default_route_order(&self) -> Result<DefaultRouteOrder, NetworkError> {
+        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+        match manager.as_ref() {
+            Some(manager) => manager.get_default_route_order(),
             _none => Err(NetworkError::NotInitialized),
         }
     }
@@ -313,53 +844,268 @@ impl<R: Runtime> NetworkManagerState<R> {
         }
     }
 
-    pub fn get_network_stats(&self) -> Result<crate::models::NetworkStats, NetworkError> {
-        let mut tracker = self.stats_tracker.write().map_err(|_| NetworkError::LockError)?;
-        
-        // Get active interface from network manager
-        let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
-        let current_interface = if let Some(manager) = manager.as_ref() {
-            let network_state = manager.get_current_network_state()
-                .map_err(|e| NetworkError::OperationError(e.to_string()))?;
-            
-            // Determine interface name from connection type
-            if network_state.connection_type == "WiFi" {
-                // Try common WiFi interface names
-                crate::network_stats::get_network_interfaces()
-                    .ok()
-                    .and_then(|interfaces| {
-                        interfaces.into_iter()
-                            .find(|i| i.starts_with("wl") || i.starts_with("wlan"))
-                    })
-                    .unwrap_or_else(|| "wlan0".to_string())
-            } else {
-                // Try common ethernet interface names
-                crate::network_stats::get_network_interfaces()
-                    .ok()
-                    .and_then(|interfaces| {
-                        interfaces.into_iter()
-                            .find(|i| i.starts_with("en") || i.starts_with("eth"))
-                    })
-                    .unwrap_or_else(|| "eth0".to_string())
+    /// Bandwidth stats for `interface`, for the interface carrying the
+    /// active connection `uuid`, or with neither for the primary
+    /// connection's, including a VPN tunnel when that is the primary.
+    pub fn get_network_stats(
+        &self,
+        interface: Option<&str>,
+        uuid: Option<&str>,
+    ) -> Result<crate::models::NetworkStats, NetworkError> {
+        let interface = {
+            let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+            match manager.as_ref() {
+                Some(manager) => manager.resolve_stats_interface(interface, uuid)?,
+                _none => return Err(NetworkError::NotInitialized),
             }
-        } else {
-            return Err(NetworkError::NotInitialized);
         };
-        drop(manager); // Release the read lock
-        
-        // Initialize or reinitialize tracker if interface changed or not initialized
-        let needs_reinit = tracker.is_none() || 
-            tracker.as_ref().map(|t| t.get_interface() != current_interface).unwrap_or(false);
-        
-        if needs_reinit {
-            *tracker = crate::network_stats::NetworkStatsTracker::new(current_interface).ok();
-        }
-        
-        // Get stats from tracker
-        match tracker.as_mut() {
-            Some(t) => t.get_stats().map_err(|e| NetworkError::from(e)),
+
+        let mut trackers = self.stats_trackers.write().map_err(|_| NetworkError::LockError)?;
+        if !trackers.contains_key(&interface) {
+            let tracker = crate::network_stats::NetworkStatsTracker::new(interface.clone())?;
+            trackers.insert(interface.clone(), tracker);
+        }
+        let result = match trackers.get_mut(&interface) {
+            Some(tracker) => tracker.get_stats(),
             None => Err(NetworkError::OperationError("Stats tracker not initialized".to_string())),
+        };
+        // An interface that went away (a tunnel torn down, a dongle pulled)
+        // starts over if it comes back.
+        if result.is_err() {
+            trackers.remove(&interface);
         }
+        result
+    }
+
+    /// Starts the background bandwidth sampler, or changes its pace and
+    /// interfaces if it is running.
+    pub fn start_bandwidth_sampler(
+        &self,
+        app: AppHandle<R>,
+        config: BandwidthSamplerConfig,
+    ) -> Result<(), NetworkError> {
+        // Each one is read under /sys/class/net.
+        for interface in &config.interfaces {
+            crate::nm_helpers::NetworkManagerHelpers::validate_interface_name(interface)?;
+        }
+        let mut sampler = self.bandwidth_sampler.write().map_err(|_| NetworkError::LockError)?;
+        if let Some(running) = sampler.as_ref() {
+            if running.configure(config.clone()).is_ok() {
+                return Ok(());
+            }
+        }
+        self.resize_bandwidth_history(crate::bandwidth_sampler::history_size(&config));
+        *sampler = Some(crate::bandwidth_sampler::BandwidthSampler::spawn(app, config));
+        Ok(())
+    }
+
+    /// Stops the sampler. The history gathered so far stays readable.
+    pub fn stop_bandwidth_sampler(&self) -> Result<(), NetworkError> {
+        let mut sampler = self.bandwidth_sampler.write().map_err(|_| NetworkError::LockError)?;
+        // Dropping the handle is what tells the thread to stop.
+        *sampler = None;
+        Ok(())
+    }
+
+    pub(crate) fn record_bandwidth(
+        &self,
+        stats: &[crate::models::NetworkStats],
+        timestamp: u64,
+        capacity: usize,
+    ) {
+        let Ok(mut history) = self.bandwidth_history.write() else {
+            return;
+        };
+        for current in stats {
+            history
+                .entry(current.interface.clone())
+                .or_insert_with(|| crate::bandwidth_sampler::BandwidthHistory::new(capacity))
+                .push(crate::models::BandwidthPoint {
+                    timestamp,
+                    download_speed: current.download_speed,
+                    upload_speed: current.upload_speed,
+                });
+        }
+    }
+
+    pub(crate) fn resize_bandwidth_history(&self, capacity: usize) {
+        if let Ok(mut history) = self.bandwidth_history.write() {
+            for entry in history.values_mut() {
+                entry.resize(capacity);
+            }
+        }
+    }
+
+    /// The sampler's points for `interface`, or for the interface carrying
+    /// the primary connection, from the last `window` seconds or all that
+    /// are kept. Empty when the sampler has not measured it.
+    pub fn get_bandwidth_history(
+        &self,
+        interface: Option<&str>,
+        window: Option<u64>,
+    ) -> Result<Vec<crate::models::BandwidthPoint>, NetworkError> {
+        let interface = {
+            let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+            match manager.as_ref() {
+                Some(manager) => manager.resolve_stats_interface(interface, None)?,
+                _none => return Err(NetworkError::NotInitialized),
+            }
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        let history = self.bandwidth_history.read().map_err(|_| NetworkError::LockError)?;
+        Ok(history
+            .get(&interface)
+            .map(|entry| entry.window(now, window))
+            .unwrap_or_default())
+    }
+
+    /// Full kernel counters of every interface, with the rate error and
+    /// drop counters grew at since the previous call.
+    pub fn get_interface_statistics(&self) -> Result<Vec<crate::models::InterfaceStatistics>, NetworkError> {
+        let mut reader = self.interface_statistics.write().map_err(|_| NetworkError::LockError)?;
+        reader.read_all()
+    }
+
+    /// Connectivity right now. NetworkManager checks when its check is on;
+    /// the plugin's probe answers when it is off, and finds the login page
+    /// when NetworkManager reports a portal, since it does not publish one.
+    pub fn check_connectivity(&self) -> Result<crate::models::ConnectivityStatus, NetworkError> {
+        let (checked, connected) = {
+            let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+            match manager.as_ref() {
+                Some(manager) => {
+                    let checked = if manager.connectivity_check_enabled() {
+                        Some(manager.check_connectivity()?)
+                    } else {
+                        None
+                    };
+                    (checked, manager.has_primary_connection())
+                }
+                _none => return Err(NetworkError::NotInitialized),
+            }
+        };
+        let probe = self
+            .connectivity_probe
+            .read()
+            .map_err(|_| NetworkError::LockError)?
+            .clone();
+
+        match checked {
+            Some(state) => Ok(crate::models::ConnectivityStatus {
+                state,
+                portal_url: match state {
+                    ConnectivityState::Portal => crate::connectivity::probe(&probe)?.portal_url,
+                    _ => None,
+                },
+                source: crate::models::ConnectivitySource::NetworkManager,
+            }),
+            None if !connected => Ok(crate::models::ConnectivityStatus {
+                state: ConnectivityState::None,
+                portal_url: None,
+                source: crate::models::ConnectivitySource::Probe,
+            }),
+            None => {
+                let outcome = crate::connectivity::probe(&probe)?;
+                Ok(crate::models::ConnectivityStatus {
+                    state: outcome.state,
+                    portal_url: outcome.portal_url,
+                    source: crate::models::ConnectivitySource::Probe,
+                })
+            }
+        }
+    }
+
+    /// Replaces the fallback probe's settings once the URL checks out.
+    pub fn set_connectivity_probe(
+        &self,
+        config: crate::models::ConnectivityProbeConfig,
+    ) -> Result<(), NetworkError> {
+        crate::connectivity::ProbeUrl::parse(&config.url)?;
+        *self.connectivity_probe.write().map_err(|_| NetworkError::LockError)? = config;
+        Ok(())
+    }
+
+    /// Bytes each connection moved over `range`, from the persistent
+    /// ledger; only `connection` when given, by UUID, SSID or name.
+    pub fn get_data_usage(
+        &self,
+        range: &crate::models::DataUsageRange,
+        connection: Option<&str>,
+    ) -> Result<Vec<crate::models::DataUsage>, NetworkError> {
+        let store = self.data_usage.read().map_err(|_| NetworkError::LockError)?;
+        match store.as_ref() {
+            Some(store) => store
+                .ledger
+                .usage(range, connection, crate::data_usage::Day::today()),
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    /// Sets or clears the monthly quota of `connection` and saves it.
+    pub fn set_data_quota(
+        &self,
+        connection: &str,
+        quota: Option<crate::models::DataQuota>,
+    ) -> Result<(), NetworkError> {
+        let mut store = self.data_usage.write().map_err(|_| NetworkError::LockError)?;
+        match store.as_mut() {
+            Some(store) => {
+                store.ledger.set_quota(connection, quota)?;
+                store.save()
+            }
+            _none => Err(NetworkError::NotInitialized),
+        }
+    }
+
+    /// Round trips to every target in `config`. The gateway and DNS
+    /// server are the primary connection's; a target that cannot be
+    /// resolved gets a result with its error rather than failing the rest.
+    pub fn ping(
+        &self,
+        config: &crate::models::PingConfig,
+    ) -> Result<Vec<crate::models::PingResult>, NetworkError> {
+        let needs_connection = config
+            .targets
+            .iter()
+            .any(|target| !matches!(target, crate::models::PingTarget::Host { .. }));
+        let ip = if needs_connection {
+            let manager = self.manager.read().map_err(|_| NetworkError::LockError)?;
+            match manager.as_ref() {
+                Some(manager) => manager.get_ip_configuration(None, None).ok(),
+                _none => return Err(NetworkError::NotInitialized),
+            }
+        } else {
+            None
+        };
+        let targets = crate::ping::resolve_targets(&config.targets, ip.as_ref());
+        Ok(crate::ping::ping(targets, config))
+    }
+
+    /// Starts the ping monitor, or changes its targets and pace if it is
+    /// running.
+    pub fn start_ping_monitor(
+        &self,
+        app: AppHandle<R>,
+        config: crate::models::PingMonitorConfig,
+    ) -> Result<(), NetworkError> {
+        let mut monitor = self.ping_monitor.write().map_err(|_| NetworkError::LockError)?;
+        if let Some(running) = monitor.as_ref() {
+            if running.configure(config.clone()).is_ok() {
+                return Ok(());
+            }
+        }
+        *monitor = Some(crate::ping::PingMonitor::spawn(app, config));
+        Ok(())
+    }
+
+    pub fn stop_ping_monitor(&self) -> Result<(), NetworkError> {
+        let mut monitor = self.ping_monitor.write().map_err(|_| NetworkError::LockError)?;
+        *monitor = None;
+        Ok(())
     }
 
     pub fn list_vpn_profiles(&self) -> Result<Vec<VpnProfile>, NetworkError> {
@@ -429,12 +1175,54 @@ pub fn init() -> TauriPlugin<tauri::Wry> {
             disconnect_from_wifi,
             get_saved_wifi_networks,
             rescan_wifi,
+            start_wifi_scanner,
+            stop_wifi_scanner,
             delete_wifi_connection,
+            get_mac_address_policy,
+            set_mac_address_policy,
+            list_devices,
+            list_active_connections,
+            get_ip_configuration,
+            get_ip_settings,
+            update_ip_settings,
+            disconnect_device,
+            set_device_managed,
+            set_device_autoconnect,
+            configure_wired_8021x,
+            list_virtual_interfaces,
+            create_virtual_interface,
+            update_virtual_interface,
+            delete_virtual_interface,
+            list_modems,
+            get_modem_status,
+            create_mobile_broadband_connection,
+            list_bluetooth_devices,
+            connect_bluetooth_tether,
+            get_proxy_settings,
+            set_proxy_settings,
+            get_effective_proxy,
+            set_connection_metered,
+            get_connection_priority,
+            set_connection_priority,
+            reorder_preferred_networks,
+            get_default_route_order,
+            get_wired_link_info,
             toggle_network_state,
             get_wireless_enabled,
             set_wireless_enabled,
             is_wireless_available,
             get_network_stats,
+            start_bandwidth_sampler,
+            stop_bandwidth_sampler,
+            get_bandwidth_history,
+            get_interface_statistics,
+            check_connectivity,
+            set_connectivity_probe,
+            get_data_usage,
+            set_data_quota,
+            ping,
+            start_ping_monitor,
+            stop_ping_monitor,
             get_network_interfaces,
             list_vpn_profiles,
             get_vpn_status,
@@ -455,6 +1243,19 @@ pub fn init() -> TauriPlugin<tauri::Wry> {
                 network_manager,
             )));
 
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    let ledger = crate::data_usage::DataUsageLedger::open(
+                        dir.join(crate::data_usage::LEDGER_FILE),
+                    );
+                    *app.state::<NetworkManagerState<tauri::Wry>>()
+                        .data_usage
+                        .write()
+                        .map_err(|_| NetworkError::LockError)? = Some(ledger);
+                }
+                Err(e) => eprintln!("No se pudo ubicar el registro de consumo de datos: {:?}", e),
+            }
+
             app.state::<NetworkManagerState<tauri::Wry>>()
                 .manager
                 .read()
@@ -468,6 +1269,8 @@ pub fn init() -> TauriPlugin<tauri::Wry> {
                             proxy: manager.proxy.clone(),
                             app: app.clone(),
                         };
+                    spawn_cable_emitter(app.clone(), &manager_static);
+                    crate::data_usage::spawn(app.clone());
                     spawn_network_change_emitter(app.clone(), manager_static);
                 });
 
@@ -1,17 +1,60 @@
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NetworkInfo {
     pub name: String,
+    /// SSID for display. Not guaranteed to be unique, see `ssid_hex`.
     pub ssid: String,
+    /// The raw SSID bytes in lowercase hex; empty when this is not Wi-Fi.
+    /// This is what identifies the network when connecting or deleting.
+    #[serde(default)]
+    pub ssid_hex: String,
+    /// UUID of the saved connection behind this entry, when there is one.
+    #[serde(default)]
+    pub uuid: Option<String>,
     pub connection_type: String,
     pub icon: String,
+    /// First IPv4 address, kept for callers that predate the lists below.
     pub ip_address: String,
+    /// Every IPv4 address on the device, from `IP4Config.AddressData`.
+    #[serde(default)]
+    pub ipv4_addresses: Vec<IpAddressInfo>,
+    /// Every IPv6 address on the device, link-local included.
+    #[serde(default)]
+    pub ipv6_addresses: Vec<IpAddressInfo>,
+    /// How far IPv4 reaches from this device, per NetworkManager's probe.
+    #[serde(default)]
+    pub ip4_connectivity: ConnectivityState,
+    #[serde(default)]
+    pub ip6_connectivity: ConnectivityState,
+    /// NetworkManager's overall verdict (`Connectivity`). Stays `Unknown`
+    /// or reads `Full` when its check is turned off; `check_connectivity`
+    /// probes on its own then.
+    #[serde(default)]
+    pub connectivity: ConnectivityState,
+    /// The address the device is using right now, which is not the one it
+    /// was made with when a randomization policy is in effect.
     pub mac_address: String,
+    /// The burnt-in address (`PermHwAddress`), when the device reports one.
+    #[serde(default)]
+    pub permanent_mac_address: Option<String>,
     pub signal_strength: u8,
     pub security_type: WiFiSecurityType,
     pub is_connected: bool,
+    /// Whether traffic on this device costs money, from the device's
+    /// `Metered` property: set on the connection, or guessed by
+    /// NetworkManager (phone hotspots, modems).
+    #[serde(default)]
+    pub metered: MeteredState,
+    /// Link details when the device is Ethernet.
+    #[serde(default)]
+    pub wired: Option<WiredLinkInfo>,
+    /// Set on the `network-changed` events sent while a connection the
+    /// plugin started comes up, one per step; `None` otherwise. Those events
+    /// describe the device being activated, not the primary connection.
+    #[serde(default)]
+    pub activation: Option<ConnectionActivation>,
 }
 
 impl Default for NetworkInfo {
@@ -19,18 +62,83 @@ impl Default for NetworkInfo {
         Self {
             name: String::from("Unknown"),
             ssid: String::from("Unknown"),
+            ssid_hex: String::new(),
+            uuid: None,
             connection_type: String::from("Unknown"),
             icon: String::from("network-offline-symbolic"), // icono por defecto
             ip_address: String::from("0.0.0.0"),
+            ipv4_addresses: Vec::new(),
+            ipv6_addresses: Vec::new(),
+            ip4_connectivity: ConnectivityState::Unknown,
+            ip6_connectivity: ConnectivityState::Unknown,
+            connectivity: ConnectivityState::Unknown,
             mac_address: String::from("00:00:00:00:00:00"),
+            permanent_mac_address: None,
             signal_strength: 0,
             security_type: WiFiSecurityType::None,
             is_connected: false,
+            metered: MeteredState::Unknown,
+            wired: None,
+            activation: None,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// An address with its prefix length, `192.168.1.20/24` or `fd00::5/64`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpAddressInfo {
+    pub address: String,
+    pub prefix: u8,
+}
+
+/// NetworkManager's `NMConnectivityState`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectivityState {
+    /// Not checked, or checking is turned off.
+    #[default]
+    Unknown,
+    /// No route to anywhere.
+    None,
+    /// Behind a captive portal that wants a login.
+    Portal,
+    /// Some network, but the internet is not reachable.
+    Limited,
+    Full,
+}
+
+/// NetworkManager's `NMMetered`. A connection can only be set to `Unknown`,
+/// `Yes` or `No`; the guesses are what NetworkManager concludes when the
+/// connection leaves it unknown.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum MeteredState {
+    #[default]
+    Unknown,
+    Yes,
+    No,
+    GuessYes,
+    GuessNo,
+}
+
+impl MeteredState {
+    /// True for `Yes` and `GuessYes`, which is what callers deciding
+    /// whether to hold back large transfers want.
+    pub fn is_metered(self) -> bool {
+        matches!(self, MeteredState::Yes | MeteredState::GuessYes)
+    }
+}
+
+/// Payload of `metered-changed`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MeteredChangedEvent {
+    /// Primary connection the value belongs to, if any.
+    pub uuid: Option<String>,
+    pub metered: MeteredState,
+    pub previous: MeteredState,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum WiFiSecurityType {
     None,
@@ -51,11 +159,172 @@ pub struct WiFiNetwork {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WiFiConnectionConfig {
     pub ssid: String,
+    /// Raw SSID bytes in hex, as reported in `NetworkInfo::ssid_hex`. When
+    /// set it is what gets sent to NetworkManager and `ssid` only names the
+    /// saved connection.
+    #[serde(default)]
+    pub ssid_hex: Option<String>,
     #[serde(default)]
     pub password: Option<String>,
     pub security_type: WiFiSecurityType,
     #[serde(default)]
     pub username: Option<String>,
+    /// MAC address to present on this network; NetworkManager's global
+    /// default when absent.
+    #[serde(default)]
+    pub mac_address_policy: Option<MacAddressPolicy>,
+}
+
+/// Which MAC address a connection presents: NetworkManager's
+/// `assigned-mac-address` (`cloned-mac-address` in nmcli).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MacAddressPolicy {
+    /// Keep whatever address the device has when the connection comes up.
+    Preserve,
+    /// The burnt-in address.
+    Permanent,
+    /// A new random address on every activation.
+    Random,
+    /// Random, but the same every time for this connection on this machine.
+    Stable,
+    /// This exact address, `aa:bb:cc:dd:ee:ff`.
+    Explicit(String),
+}
+
+/// Which networks `list_wifi_networks` returns and in what order.
+///
+/// Applied in Rust over the cached scan, so the frontend gets exactly the
+/// list it is going to draw.
+#[derive(Deserialize, Debug, Clone)]
+pub struct WifiNetworkQuery {
+    /// Drop anything weaker than this (0–100).
+    #[serde(default)]
+    pub min_signal: Option<u8>,
+    /// Keep only these security types.
+    #[serde(default)]
+    pub security_types: Option<Vec<WiFiSecurityType>>,
+    /// Keep access points that do not broadcast their SSID.
+    #[serde(default = "default_true")]
+    pub include_hidden: bool,
+    /// One entry per SSID, the strongest access point for it. Hidden
+    /// networks are each kept.
+    #[serde(default = "default_true")]
+    pub dedupe_by_ssid: bool,
+    #[serde(default)]
+    pub sort_by: WifiSortKey,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub known: KnownNetworkFilter,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for WifiNetworkQuery {
+    fn default() -> Self {
+        Self {
+            min_signal: None,
+            security_types: None,
+            include_hidden: true,
+            dedupe_by_ssid: true,
+            sort_by: WifiSortKey::default(),
+            limit: None,
+            known: KnownNetworkFilter::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum WifiSortKey {
+    /// Strongest first.
+    #[default]
+    Signal,
+    /// Alphabetical by SSID, ignoring case.
+    Ssid,
+    /// As NetworkManager lists them.
+    None,
+}
+
+/// Filter on whether a saved connection exists for the SSID.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum KnownNetworkFilter {
+    #[default]
+    All,
+    KnownOnly,
+    UnknownOnly,
+}
+
+/// Pace of the background Wi-Fi scanner.
+#[derive(Deserialize, Debug, Clone)]
+pub struct WifiScanConfig {
+    /// Whether the Wi-Fi list is on screen. Scans run at `active_interval_ms`
+    /// while it is and at `idle_interval_ms` while it is not.
+    #[serde(default = "default_panel_open")]
+    pub panel_open: bool,
+    #[serde(default = "default_active_interval_ms")]
+    pub active_interval_ms: u64,
+    /// `0` pauses scanning while the panel is closed.
+    #[serde(default = "default_idle_interval_ms")]
+    pub idle_interval_ms: u64,
+}
+
+fn default_panel_open() -> bool {
+    true
+}
+
+fn default_active_interval_ms() -> u64 {
+    15_000
+}
+
+fn default_idle_interval_ms() -> u64 {
+    300_000
+}
+
+impl Default for WifiScanConfig {
+    fn default() -> Self {
+        Self {
+            panel_open: default_panel_open(),
+            active_interval_ms: default_active_interval_ms(),
+            idle_interval_ms: default_idle_interval_ms(),
+        }
+    }
+}
+
+/// Pace and reach of the background bandwidth sampler.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BandwidthSamplerConfig {
+    #[serde(default = "default_sample_interval_ms")]
+    pub interval_ms: u64,
+    /// Points kept per interface; the oldest go first.
+    #[serde(default = "default_history_size")]
+    pub history_size: usize,
+    /// Interfaces to sample; every interface but loopback when empty,
+    /// including ones that appear later.
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+}
+
+fn default_sample_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_history_size() -> usize {
+    300
+}
+
+impl Default for BandwidthSamplerConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: default_sample_interval_ms(),
+            history_size: default_history_size(),
+            interfaces: Vec::new(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -101,6 +370,11 @@ pub struct VpnStatus {
     pub active_profile_uuid: Option<String>,
     pub active_profile_name: Option<String>,
     pub ip_address: Option<String>,
+    /// Addresses inside the tunnel, both families.
+    #[serde(default)]
+    pub ipv4_addresses: Vec<IpAddressInfo>,
+    #[serde(default)]
+    pub ipv6_addresses: Vec<IpAddressInfo>,
     pub gateway: Option<String>,
     pub since_unix_ms: Option<u64>,
     /// La interfaz por donde sale el túnel: sdwan0, tailscale0, wg0…
@@ -126,6 +400,8 @@ impl Default for VpnStatus {
             active_profile_uuid: None,
             active_profile_name: None,
             ip_address: None,
+            ipv4_addresses: Vec::new(),
+            ipv6_addresses: Vec::new(),
             gateway: None,
             since_unix_ms: None,
             interface: None,
@@ -187,6 +463,676 @@ pub struct VpnUpdateConfig {
     pub secrets: Option<std::collections::HashMap<String, String>>,
 }
 
+/// Kind of network device, from NetworkManager's `NMDeviceType`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeviceType {
+    Ethernet,
+    Wifi,
+    Bluetooth,
+    Modem,
+    Infiniband,
+    Bond,
+    Vlan,
+    Bridge,
+    Team,
+    Tun,
+    IpTunnel,
+    Macvlan,
+    Vxlan,
+    Veth,
+    Dummy,
+    Ppp,
+    WireGuard,
+    WifiP2p,
+    Loopback,
+    Generic,
+    Unknown,
+}
+
+/// Where a device is in its lifecycle, from NetworkManager's `NMDeviceState`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeviceState {
+    Unknown,
+    /// NetworkManager leaves this device alone.
+    Unmanaged,
+    /// Managed but cannot be used: no cable, radio off, missing firmware.
+    Unavailable,
+    Disconnected,
+    Prepare,
+    Config,
+    NeedAuth,
+    IpConfig,
+    IpCheck,
+    Secondaries,
+    Activated,
+    Deactivating,
+    Failed,
+}
+
+/// Enough of a connection profile to name it and act on it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionRef {
+    pub id: String,
+    pub uuid: String,
+    /// NetworkManager's setting type: `802-11-wireless`, `802-3-ethernet`, `vpn`…
+    pub connection_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Duplex {
+    Full,
+    Half,
+}
+
+/// The physical side of an Ethernet port.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WiredLinkInfo {
+    pub interface: String,
+    /// A cable is plugged in and the other end is up.
+    pub carrier: bool,
+    /// Negotiated speed in Mb/s (`Device.Wired.Speed`); absent without a link.
+    pub speed_mbps: Option<u32>,
+    /// From sysfs; absent without a link.
+    pub duplex: Option<Duplex>,
+    /// What the active profile asks for: `true` to negotiate, `false` when
+    /// it forces speed and duplex, absent when it leaves the port alone.
+    /// The kernel does not publish the negotiated mode through sysfs.
+    pub autoneg: Option<bool>,
+}
+
+/// Payload of `cable-changed`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CableEvent {
+    pub interface: String,
+    pub plugged: bool,
+}
+
+/// One network device as NetworkManager sees it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeviceInfo {
+    pub interface: String,
+    /// The interface that carries IP traffic, which differs from `interface`
+    /// for modems and PPP links.
+    pub ip_interface: Option<String>,
+    pub device_type: DeviceType,
+    pub state: DeviceState,
+    /// Why the device got into `state`, as an `NMDeviceStateReason` name
+    /// such as `carrier` or `no-secrets`.
+    pub state_reason: String,
+    pub state_reason_code: u32,
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+    pub firmware_version: Option<String>,
+    /// The address in use now.
+    pub hw_address: Option<String>,
+    /// The burnt-in address, for the types that report one.
+    pub permanent_hw_address: Option<String>,
+    pub mtu: u32,
+    pub managed: bool,
+    pub autoconnect: bool,
+    /// Link detected, for device types that have the notion.
+    pub carrier: Option<bool>,
+    /// Negotiated link speed in Mb/s: the wired speed or the Wi-Fi bitrate.
+    pub speed_mbps: Option<u32>,
+    pub active_connection: Option<ConnectionRef>,
+    /// Saved profiles that could be activated on this device right now.
+    pub available_connections: Vec<ConnectionRef>,
+}
+
+/// One entry of a routing table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    pub destination: String,
+    pub prefix: u8,
+    /// Absent for on-link routes.
+    pub next_hop: Option<String>,
+    pub metric: Option<u32>,
+}
+
+/// What one address family ended up with on a device, however it got there
+/// (DHCP, SLAAC, static settings).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct IpFamilyConfiguration {
+    pub addresses: Vec<IpAddressInfo>,
+    pub gateway: Option<String>,
+    pub nameservers: Vec<String>,
+    /// DNS domains this link is authoritative for.
+    pub domains: Vec<String>,
+    /// Search list appended to short host names.
+    pub searches: Vec<String>,
+    pub routes: Vec<RouteInfo>,
+}
+
+/// How a connection gets addresses for one family (`ipv4.method`).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IpMethod {
+    /// DHCP for IPv4; SLAAC, with DHCPv6 when the router asks, for IPv6.
+    Auto,
+    Manual,
+    LinkLocal,
+    /// Share this connection with others on the link (hotspot, NAT).
+    Shared,
+    Disabled,
+    /// IPv6 only: DHCPv6 without SLAAC.
+    Dhcp,
+    /// IPv6 only: leave whatever the kernel sets up alone.
+    Ignore,
+}
+
+/// The saved IP settings of one family, what a connection editor shows.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpFamilySettings {
+    pub method: IpMethod,
+    /// Required with `manual`; with `auto` they are added to the leased ones.
+    #[serde(default)]
+    pub addresses: Vec<IpAddressInfo>,
+    #[serde(default)]
+    pub gateway: Option<String>,
+    #[serde(default)]
+    pub dns: Vec<String>,
+    #[serde(default)]
+    pub dns_search: Vec<String>,
+    /// Metric of the routes this connection adds; NetworkManager's per-type
+    /// default when absent. Lower wins.
+    #[serde(default)]
+    pub route_metric: Option<i64>,
+    /// Use only `dns`, not the servers DHCP or router advertisements offer.
+    #[serde(default)]
+    pub ignore_auto_dns: bool,
+    #[serde(default)]
+    pub routes: Vec<RouteInfo>,
+}
+
+/// Saved IP settings of a connection. A family left out is not touched.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct IpSettings {
+    #[serde(default)]
+    pub ipv4: Option<IpFamilySettings>,
+    #[serde(default)]
+    pub ipv6: Option<IpFamilySettings>,
+}
+
+/// The live IP configuration of one device.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IpConfiguration {
+    pub interface: String,
+    pub ipv4: IpFamilyConfiguration,
+    pub ipv6: IpFamilyConfiguration,
+    /// The DHCPv4 lease; all empty when the address is not from DHCP.
+    pub dhcp4: DhcpLease,
+    pub dhcp6: DhcpLease,
+}
+
+/// A DHCP lease as NetworkManager reports it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DhcpLease {
+    /// Lease length in seconds.
+    pub lease_time: Option<u64>,
+    /// The server that handed the lease out: its address for DHCPv4, its
+    /// DUID for DHCPv6.
+    pub server: Option<String>,
+    /// When the lease runs out, in seconds since the Unix epoch.
+    pub expiry: Option<u64>,
+    /// Every option the server sent, as NetworkManager names them
+    /// (`domain_name`, `wpad`…).
+    pub options: std::collections::HashMap<String, String>,
+}
+
+/// How a saved connection ranks against the others: which one is tried
+/// first when several are available, and which one carries traffic when
+/// several are up.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionPriority {
+    /// `connection.autoconnect-priority`, -999 to 999. Higher is tried
+    /// first; 0 is the default.
+    #[serde(default)]
+    pub autoconnect_priority: i32,
+    /// `ipv4.route-metric`; NetworkManager's per-type default when absent
+    /// (100 for Ethernet, 600 for Wi-Fi…). Lower wins.
+    #[serde(default)]
+    pub ipv4_route_metric: Option<i64>,
+    #[serde(default)]
+    pub ipv6_route_metric: Option<i64>,
+}
+
+/// A default route installed right now, with the connection that added it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DefaultRoute {
+    pub uuid: String,
+    pub connection_id: String,
+    pub interface: String,
+    /// Absent for point-to-point links (VPNs, modems).
+    pub gateway: Option<String>,
+    pub metric: u32,
+}
+
+/// Default routes per family, lowest metric first: the first one is
+/// where traffic goes.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefaultRouteOrder {
+    pub ipv4: Vec<DefaultRoute>,
+    pub ipv6: Vec<DefaultRoute>,
+}
+
+/// From NetworkManager's `NMActiveConnectionState`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActiveConnectionState {
+    Unknown,
+    Activating,
+    Activated,
+    Deactivating,
+    Deactivated,
+}
+
+/// A connection that is up, or on its way up or down.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActiveConnectionInfo {
+    pub id: String,
+    pub uuid: String,
+    pub connection_type: String,
+    /// Interfaces the connection runs on.
+    pub devices: Vec<String>,
+    pub state: ActiveConnectionState,
+    /// Holds the IPv4 default route.
+    pub default: bool,
+    /// Holds the IPv6 default route.
+    pub default6: bool,
+    pub vpn: bool,
+    /// NetworkManager's `PrimaryConnection`: the one the desktop should
+    /// describe as "the" network.
+    pub is_primary: bool,
+    /// Interface of the bond or bridge this connection is a port of.
+    pub master: Option<String>,
+    /// Interfaces of the active connections that are ports of this one.
+    pub slaves: Vec<String>,
+}
+
+/// Overall modem state, from ModemManager's `MMModemState`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModemState {
+    Failed,
+    Unknown,
+    Initializing,
+    /// Waiting for a SIM PIN or PUK.
+    Locked,
+    Disabled,
+    Disabling,
+    Enabling,
+    Enabled,
+    Searching,
+    Registered,
+    Disconnecting,
+    Connecting,
+    Connected,
+}
+
+/// Network registration, from `MMModem3gppRegistrationState` or, for CDMA,
+/// `MMModemCdmaRegistrationState`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RegistrationState {
+    Unknown,
+    /// Not registered and not looking.
+    Idle,
+    Searching,
+    Denied,
+    Home,
+    Roaming,
+    /// Only emergency calls are possible.
+    EmergencyOnly,
+}
+
+/// A modem known to ModemManager.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModemInfo {
+    /// ModemManager's index, the last part of its object path. It changes
+    /// when the modem is replugged.
+    pub id: String,
+    /// NetworkManager's interface for the modem, such as `cdc-wdm0`, when
+    /// NetworkManager has picked it up.
+    pub interface: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub revision: Option<String>,
+    /// IMEI for 3GPP modems, ESN/MEID for CDMA ones.
+    pub equipment_identifier: Option<String>,
+    pub state: ModemState,
+    /// Families the modem is running now: `gsm-umts`, `lte`, `5gnr`, `cdma-evdo`…
+    pub capabilities: Vec<String>,
+    /// What the SIM waits for, such as `sim-pin`; `None` when unlocked.
+    pub unlock_required: Option<String>,
+    pub own_numbers: Vec<String>,
+}
+
+/// Live radio status of a modem.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModemStatus {
+    pub id: String,
+    pub interface: Option<String>,
+    pub state: ModemState,
+    pub operator_name: Option<String>,
+    /// MCC and MNC, as in `21407`.
+    pub operator_code: Option<String>,
+    /// Technologies in use now, fastest first: `5gnr`, `lte`, `hspa-plus`, `umts`, `edge`…
+    pub access_technologies: Vec<String>,
+    /// Signal quality, 0 to 100.
+    pub signal_quality: u8,
+    /// False when the value is a cached one ModemManager could not refresh.
+    pub signal_recent: bool,
+    pub registration_state: RegistrationState,
+}
+
+/// Which family of mobile network a profile is for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MobileBroadbandType {
+    /// GSM, UMTS, LTE and 5G: everything with a SIM and an APN.
+    #[default]
+    Gsm,
+    Cdma,
+}
+
+/// Input for a new mobile broadband profile.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MobileBroadbandConfig {
+    /// Profile name. Defaults to the APN, or "Mobile broadband".
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub network_type: MobileBroadbandType,
+    /// Access point name. Empty lets the modem pick one where the operator
+    /// allows it.
+    #[serde(default)]
+    pub apn: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// SIM PIN, 4 to 8 digits.
+    #[serde(default)]
+    pub pin: Option<String>,
+    /// Whether the profile may connect on a network other than the home one.
+    #[serde(default = "default_true")]
+    pub roaming_allowed: bool,
+    /// Bind the profile to this modem interface instead of any modem.
+    #[serde(default)]
+    pub interface: Option<String>,
+    #[serde(default)]
+    pub autoconnect: Option<bool>,
+}
+
+/// How a connection's proxy is found.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProxyMethod {
+    /// Direct connections.
+    None,
+    /// A PAC file from `pac_url` or `pac_script`, or found through WPAD.
+    Auto,
+    /// Fixed proxies per scheme.
+    Manual,
+}
+
+/// Fixed proxies, each as `host:port`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManualProxy {
+    #[serde(default)]
+    pub http: Option<String>,
+    #[serde(default)]
+    pub https: Option<String>,
+    #[serde(default)]
+    pub ftp: Option<String>,
+    /// Used for whatever has no proxy of its own.
+    #[serde(default)]
+    pub socks: Option<String>,
+    /// Hosts reached directly; shell patterns such as `*.corp.example`.
+    #[serde(default)]
+    pub ignore_hosts: Vec<String>,
+}
+
+/// The `proxy` section of a connection.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProxySettings {
+    pub method: ProxyMethod,
+    #[serde(default)]
+    pub pac_url: Option<String>,
+    /// A PAC script given inline instead of by URL.
+    #[serde(default)]
+    pub pac_script: Option<String>,
+    /// With `method: manual`, the proxies to use.
+    #[serde(default)]
+    pub manual: Option<ManualProxy>,
+    /// Only browsers should use the proxy, not the whole system.
+    #[serde(default)]
+    pub browser_only: bool,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            method: ProxyMethod::None,
+            pac_url: None,
+            pac_script: None,
+            manual: None,
+            browser_only: false,
+        }
+    }
+}
+
+/// Proxy configuration in force on the primary connection.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EffectiveProxy {
+    pub uuid: String,
+    pub connection_id: String,
+    pub interface: Option<String>,
+    pub settings: ProxySettings,
+    /// The PAC file to fetch: the configured one, or the one the DHCP server
+    /// announced through WPAD (option 252) when none is configured.
+    pub pac_url: Option<String>,
+    /// `pac_url` came from WPAD.
+    pub wpad: bool,
+}
+
+/// How a phone shares its connection over Bluetooth.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BluetoothTetherType {
+    /// Personal Area Network: the phone acts as a network access point.
+    Panu,
+    /// Dial-Up Networking: the phone acts as a modem and needs an APN.
+    Dun,
+}
+
+/// A paired Bluetooth device NetworkManager can tether through.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BluetoothDeviceInfo {
+    pub name: String,
+    pub address: String,
+    /// Tether types the device offers.
+    pub capabilities: Vec<BluetoothTetherType>,
+    pub state: DeviceState,
+    pub active_connection: Option<ConnectionRef>,
+    pub available_connections: Vec<ConnectionRef>,
+}
+
+/// Input for tethering through a paired device.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BluetoothTetherConfig {
+    /// Bluetooth address of the phone, as in `BluetoothDeviceInfo::address`.
+    pub address: String,
+    pub tether_type: BluetoothTetherType,
+    /// Name for a new profile. Defaults to "<device name> Network".
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Required for DUN, which dials like a modem.
+    #[serde(default)]
+    pub mobile_broadband: Option<MobileBroadbandConfig>,
+}
+
+/// 802.1Q VLAN on top of another interface.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VlanConfig {
+    /// VLAN tag, 1 to 4094.
+    pub id: u16,
+    /// Interface the tagged frames go out on.
+    pub parent: String,
+}
+
+/// Software bridge. Timer values are in seconds; absent ones keep
+/// NetworkManager's defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BridgeConfig {
+    #[serde(default)]
+    pub stp: bool,
+    #[serde(default)]
+    pub priority: Option<u16>,
+    #[serde(default)]
+    pub forward_delay: Option<u32>,
+    #[serde(default)]
+    pub hello_time: Option<u32>,
+    #[serde(default)]
+    pub max_age: Option<u32>,
+    /// Interfaces enslaved to the bridge.
+    #[serde(default)]
+    pub ports: Vec<String>,
+}
+
+/// Bonding driver mode.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BondMode {
+    BalanceRr,
+    ActiveBackup,
+    BalanceXor,
+    Broadcast,
+    #[serde(rename = "802.3ad")]
+    Ieee8023ad,
+    BalanceTlb,
+    BalanceAlb,
+}
+
+/// Link aggregation over several interfaces.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BondConfig {
+    pub mode: BondMode,
+    /// Link monitoring interval in milliseconds; 0 turns it off.
+    #[serde(default)]
+    pub miimon: Option<u32>,
+    /// Interfaces enslaved to the bond.
+    #[serde(default)]
+    pub ports: Vec<String>,
+}
+
+/// What kind of virtual interface a profile creates, with its settings.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum VirtualInterfaceKind {
+    Vlan(VlanConfig),
+    Bridge(BridgeConfig),
+    Bond(BondConfig),
+}
+
+/// Input for creating or updating a VLAN, bridge or bond profile.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VirtualInterfaceConfig {
+    /// Name of the interface NetworkManager creates, such as `br0`.
+    pub interface_name: String,
+    /// Profile name. Defaults to `interface_name`.
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub autoconnect: Option<bool>,
+    pub kind: VirtualInterfaceKind,
+}
+
+/// A saved VLAN, bridge or bond profile. For bridges and bonds, `ports`
+/// lists the interfaces that have a port profile pointing at it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VirtualInterfaceProfile {
+    pub id: String,
+    pub uuid: String,
+    pub interface_name: String,
+    pub autoconnect: bool,
+    pub kind: VirtualInterfaceKind,
+}
+
+/// Outer EAP method of an 802.1X profile.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EapMethod {
+    Peap,
+    Ttls,
+    Tls,
+}
+
+/// Inner authentication for PEAP and TTLS.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Phase2Auth {
+    Mschapv2,
+    Mschap,
+    Pap,
+    Chap,
+    Gtc,
+    Md5,
+}
+
+/// 802.1X settings for a wired port.
+///
+/// PEAP and TTLS authenticate with `identity` and `password`; TLS with a
+/// client certificate and its private key. Certificate and key paths must
+/// be absolute, since NetworkManager reads them itself, later.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Eap8021xConfig {
+    /// Name for a new profile. Defaults to "Wired 802.1X (<interface>)".
+    #[serde(default)]
+    pub id: Option<String>,
+    pub eap: EapMethod,
+    pub identity: String,
+    /// Outer identity sent in the clear by PEAP and TTLS.
+    #[serde(default)]
+    pub anonymous_identity: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Defaults to MSCHAPv2 for PEAP and TTLS.
+    #[serde(default)]
+    pub phase2_auth: Option<Phase2Auth>,
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// Trust the system CA store instead of, or besides, `ca_cert_path`.
+    #[serde(default)]
+    pub system_ca_certs: bool,
+    /// Accept only a server certificate for this domain or a subdomain.
+    #[serde(default)]
+    pub domain_suffix_match: Option<String>,
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub private_key_path: Option<String>,
+    #[serde(default)]
+    pub private_key_password: Option<String>,
+}
+
+/// A step of a connection being brought up on a device, carried in
+/// `NetworkInfo.activation` until it is up or has failed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionActivation {
+    pub uuid: String,
+    pub interface: String,
+    pub state: DeviceState,
+    /// `NMDeviceStateReason` name; on failure, why: `supplicant-failed`,
+    /// `supplicant-timeout`, `no-secrets`…
+    pub reason: String,
+    pub reason_code: u32,
+    pub failed: bool,
+}
+
 /// Network statistics for bandwidth monitoring
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NetworkStats {
@@ -194,6 +1140,12 @@ pub struct NetworkStats {
     pub download_speed: u64,
     /// Current upload speed in bytes per second
     pub upload_speed: u64,
+    /// Download speed averaged over the last few samples, steadier than
+    /// `download_speed` for display
+    #[serde(default)]
+    pub download_speed_avg: u64,
+    #[serde(default)]
+    pub upload_speed_avg: u64,
     /// Total bytes downloaded since connection
     pub total_downloaded: u64,
     /// Total bytes uploaded since connection
@@ -205,7 +1157,7 @@ pub struct NetworkStats {
 }
 
 /// Bandwidth data point for historical tracking
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct BandwidthPoint {
     /// Timestamp in seconds since epoch
     pub timestamp: u64,
@@ -215,6 +1167,321 @@ pub struct BandwidthPoint {
     pub upload_speed: u64,
 }
 
+/// The kernel's counters for one interface, from
+/// `/sys/class/net/<iface>/statistics`. A counter the driver does not
+/// provide reads as 0.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceCounters {
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    pub collisions: u64,
+    /// Multicast packets received.
+    pub multicast: u64,
+}
+
+/// How fast the error counters grow, per second, since the previous
+/// reading. A link that is going bad shows up here long before the totals
+/// look alarming.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ErrorRates {
+    pub rx_errors: f64,
+    pub tx_errors: f64,
+    pub rx_dropped: f64,
+    pub tx_dropped: f64,
+    pub collisions: f64,
+}
+
+/// Counters of one interface with the rate their error counters grow at;
+/// the rates are 0 on the first reading.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InterfaceStatistics {
+    pub interface: String,
+    #[serde(flatten)]
+    pub counters: InterfaceCounters,
+    pub error_rates: ErrorRates,
+}
+
+/// Which days `get_data_usage` adds up. Days are calendar days in the
+/// local time zone.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum DataUsageRange {
+    Today,
+    /// The current billing period of the connection's quota, or the
+    /// calendar month when it has none.
+    #[default]
+    Month,
+    /// The last `days` days, today included.
+    LastDays { days: u32 },
+    /// From `from` to `to`, both included, as `YYYY-MM-DD`.
+    Between { from: String, to: String },
+}
+
+/// A monthly allowance for one connection.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataQuota {
+    /// Bytes per billing period, download and upload together.
+    pub limit_bytes: u64,
+    /// Day of the month the period starts on, 1 to 28.
+    #[serde(default = "default_quota_reset_day")]
+    pub reset_day: u8,
+    /// Percentages of the limit that raise `data-quota-warning`, each once
+    /// per period.
+    #[serde(default = "default_quota_thresholds")]
+    pub thresholds: Vec<u8>,
+}
+
+fn default_quota_reset_day() -> u8 {
+    1
+}
+
+fn default_quota_thresholds() -> Vec<u8> {
+    vec![80, 100]
+}
+
+/// Bytes one connection moved on one day.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DailyUsage {
+    pub date: String,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+/// What one connection used over a range of days.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataUsage {
+    /// The ledger key: the SSID for Wi-Fi, so the history survives the
+    /// profile being deleted and recreated, and the UUID for the rest.
+    pub connection: String,
+    /// UUID of the profile last seen carrying the traffic.
+    pub uuid: String,
+    pub name: String,
+    /// First and last day of the range, `YYYY-MM-DD`.
+    pub from: String,
+    pub to: String,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub total_bytes: u64,
+    /// Only the days with traffic, oldest first.
+    pub days: Vec<DailyUsage>,
+    pub quota: Option<DataQuota>,
+}
+
+/// Payload of `data-quota-warning`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataQuotaWarningEvent {
+    pub connection: String,
+    pub name: String,
+    /// The percentage crossed.
+    pub threshold: u8,
+    pub used_bytes: u64,
+    pub limit_bytes: u64,
+    /// First day of the billing period, `YYYY-MM-DD`.
+    pub period_start: String,
+}
+
+/// How `ping` measures round trips.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PingMethod {
+    /// ICMP echo, or TCP connect when the system does not allow
+    /// unprivileged ICMP sockets.
+    #[default]
+    Auto,
+    /// ICMP echo through an unprivileged datagram socket. Needs the
+    /// user's group in `net.ipv4.ping_group_range`.
+    Icmp,
+    /// Time to open a TCP connection. A refused connection counts as a
+    /// reply: the host answered.
+    Tcp,
+}
+
+/// A host `ping` probes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum PingTarget {
+    /// Default gateway of the primary connection, IPv4 first. TCP falls
+    /// back to port 80.
+    Gateway,
+    /// First DNS server of the primary connection, IPv4 first. TCP falls
+    /// back to port 53.
+    Dns,
+    /// An address or host name. TCP falls back to `port`, 443 by default.
+    Host { host: String, port: Option<u16> },
+}
+
+/// What `ping` and the ping monitor probe, and how.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PingConfig {
+    #[serde(default = "default_ping_targets")]
+    pub targets: Vec<PingTarget>,
+    /// Probes per target, 1 to 100.
+    #[serde(default = "default_ping_count")]
+    pub count: u32,
+    /// Time between probes to one target, 200 to 10000 ms.
+    #[serde(default = "default_ping_interval_ms")]
+    pub interval_ms: u64,
+    /// How long to wait for each reply, 100 to 10000 ms.
+    #[serde(default = "default_ping_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub method: PingMethod,
+}
+
+fn default_ping_targets() -> Vec<PingTarget> {
+    vec![PingTarget::Gateway, PingTarget::Dns]
+}
+
+fn default_ping_count() -> u32 {
+    4
+}
+
+fn default_ping_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_ping_timeout_ms() -> u64 {
+    1_000
+}
+
+impl Default for PingConfig {
+    fn default() -> Self {
+        Self {
+            targets: default_ping_targets(),
+            count: default_ping_count(),
+            interval_ms: default_ping_interval_ms(),
+            timeout_ms: default_ping_timeout_ms(),
+            method: PingMethod::default(),
+        }
+    }
+}
+
+/// Settings of the continuous ping monitor: a `PingConfig` round every
+/// `every_ms`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PingMonitorConfig {
+    #[serde(flatten)]
+    pub probe: PingConfig,
+    /// Time from the start of one round to the start of the next, 1 s to
+    /// 1 h. A round that takes longer is followed by the next at once.
+    #[serde(default = "default_ping_every_ms")]
+    pub every_ms: u64,
+}
+
+fn default_ping_every_ms() -> u64 {
+    10_000
+}
+
+impl Default for PingMonitorConfig {
+    fn default() -> Self {
+        Self {
+            probe: PingConfig::default(),
+            every_ms: default_ping_every_ms(),
+        }
+    }
+}
+
+/// Round trips to one target. Times are in milliseconds and `None` when
+/// no reply came back.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PingResult {
+    /// `gateway`, `dns`, or the host as given.
+    pub target: String,
+    /// The address probed; empty when the target could not be resolved.
+    pub address: String,
+    /// The method actually used, never `auto`.
+    pub method: PingMethod,
+    pub sent: u32,
+    pub received: u32,
+    /// Share of probes without a reply, 0 to 100.
+    pub packet_loss: f64,
+    pub rtt_min_ms: Option<f64>,
+    pub rtt_avg_ms: Option<f64>,
+    pub rtt_max_ms: Option<f64>,
+    /// Mean difference between consecutive round trips.
+    pub jitter_ms: Option<f64>,
+    /// Why nothing was sent: no gateway, a name that does not resolve, a
+    /// socket that could not be opened.
+    pub error: Option<String>,
+}
+
+/// Where the plugin's own connectivity probe looks, for systems where
+/// NetworkManager's check is turned off. Plain HTTP only: a captive portal
+/// can only intercept what it can read.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConnectivityProbeConfig {
+    #[serde(default = "default_probe_url")]
+    pub url: String,
+    #[serde(default = "default_probe_status")]
+    pub expected_status: u16,
+    /// Text the response body must contain; `None` checks the status only.
+    #[serde(default = "default_probe_body")]
+    pub expected_body: Option<String>,
+    /// 500 to 30000 ms.
+    #[serde(default = "default_probe_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_probe_url() -> String {
+    "http://nmcheck.gnome.org/check_network_status.txt".to_string()
+}
+
+fn default_probe_status() -> u16 {
+    200
+}
+
+fn default_probe_body() -> Option<String> {
+    Some("NetworkManager is online".to_string())
+}
+
+fn default_probe_timeout_ms() -> u64 {
+    5_000
+}
+
+impl Default for ConnectivityProbeConfig {
+    fn default() -> Self {
+        Self {
+            url: default_probe_url(),
+            expected_status: default_probe_status(),
+            expected_body: default_probe_body(),
+            timeout_ms: default_probe_timeout_ms(),
+        }
+    }
+}
+
+/// Who answered `check_connectivity`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectivitySource {
+    NetworkManager,
+    /// The plugin's probe, because NetworkManager's check is off.
+    Probe,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConnectivityStatus {
+    pub state: ConnectivityState,
+    /// The portal's login page, when behind one and the probe found it.
+    pub portal_url: Option<String>,
+    pub source: ConnectivitySource,
+}
+
+/// Payload of `captive-portal-detected`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CaptivePortalEvent {
+    /// Where to log in; `None` when the portal did not say.
+    pub url: Option<String>,
+    /// The connection behind the portal.
+    pub uuid: Option<String>,
+    pub name: String,
+}
+
 // Removed duplicate init function
 
 #[derive(Clone, Debug)]
@@ -1,144 +1,417 @@
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use crate::error::Result;
-use crate::models::{NetworkStats, BandwidthPoint};
+use crate::models::{ErrorRates, InterfaceCounters, InterfaceStatistics, NetworkStats};
+
+/// Where the kernel publishes one directory per interface.
+pub const SYSFS_NET_ROOT: &str = "/sys/class/net";
+
+/// Calls closer together than this reuse the last speed instead of dividing
+/// a few bytes by a few microseconds.
+const MIN_RATE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Samples the moving average covers.
+const SMOOTHING_SAMPLES: usize = 5;
 
 /// Network statistics tracker
+///
+/// Timing is monotonic (`Instant`), so a clock change cannot make the
+/// elapsed time negative or huge, and it has sub-second resolution, so two
+/// calls within the same second still give a speed. Totals are summed from
+/// per-sample deltas rather than subtracted from a starting value, which
+/// keeps them growing across a counter reset.
 pub struct NetworkStatsTracker {
     interface: String,
     last_rx_bytes: u64,
     last_tx_bytes: u64,
-    last_check_time: u64,
-    start_rx_bytes: u64,
-    start_tx_bytes: u64,
-    start_time: u64,
+    last_sample: Instant,
+    total_rx_bytes: u64,
+    total_tx_bytes: u64,
+    download_speed: u64,
+    upload_speed: u64,
+    /// Last speeds, oldest first, as (download, upload).
+    recent: VecDeque<(u64, u64)>,
+    start: Instant,
 }
 
 impl NetworkStatsTracker {
     /// Create a new network stats tracker for the given interface
     pub fn new(interface: String) -> Result<Self> {
         let (rx_bytes, tx_bytes) = Self::read_interface_stats(&interface)?;
-        let now = Self::current_timestamp();
-        
-        Ok(Self {
+        Ok(Self::with_counters(interface, rx_bytes, tx_bytes, Instant::now()))
+    }
+
+    /// A tracker whose baseline is the given counters, read at `at`.
+    fn with_counters(interface: String, rx_bytes: u64, tx_bytes: u64, at: Instant) -> Self {
+        Self {
             interface,
             last_rx_bytes: rx_bytes,
             last_tx_bytes: tx_bytes,
-            last_check_time: now,
-            start_rx_bytes: rx_bytes,
-            start_tx_bytes: tx_bytes,
-            start_time: now,
-        })
+            last_sample: at,
+            total_rx_bytes: 0,
+            total_tx_bytes: 0,
+            download_speed: 0,
+            upload_speed: 0,
+            recent: VecDeque::with_capacity(SMOOTHING_SAMPLES),
+            start: at,
+        }
     }
-    
+
     /// Get current network statistics
     pub fn get_stats(&mut self) -> Result<NetworkStats> {
         let (rx_bytes, tx_bytes) = Self::read_interface_stats(&self.interface)?;
-        let now = Self::current_timestamp();
-        
-        // Calculate time delta in seconds
-        let time_delta = (now - self.last_check_time) as f64;
-        
-        // Calculate speeds (bytes per second)
-        let download_speed = if time_delta > 0.0 {
-            ((rx_bytes - self.last_rx_bytes) as f64 / time_delta) as u64
-        } else {
-            0
-        };
-        
-        let upload_speed = if time_delta > 0.0 {
-            ((tx_bytes - self.last_tx_bytes) as f64 / time_delta) as u64
-        } else {
-            0
-        };
-        
-        // Update last values
-        self.last_rx_bytes = rx_bytes;
-        self.last_tx_bytes = tx_bytes;
-        self.last_check_time = now;
-        
-        Ok(NetworkStats {
-            download_speed,
-            upload_speed,
-            total_downloaded: rx_bytes - self.start_rx_bytes,
-            total_uploaded: tx_bytes - self.start_tx_bytes,
-            connection_duration: now - self.start_time,
+        Ok(self.observe(rx_bytes, tx_bytes, Instant::now()))
+    }
+
+    /// Folds one reading of the counters into the tracker.
+    fn observe(&mut self, rx_bytes: u64, tx_bytes: u64, now: Instant) -> NetworkStats {
+        let elapsed = now.saturating_duration_since(self.last_sample);
+        if elapsed >= MIN_RATE_INTERVAL {
+            let rx_delta = counter_delta(self.last_rx_bytes, rx_bytes);
+            let tx_delta = counter_delta(self.last_tx_bytes, tx_bytes);
+            let seconds = elapsed.as_secs_f64();
+
+            self.download_speed = (rx_delta as f64 / seconds) as u64;
+            self.upload_speed = (tx_delta as f64 / seconds) as u64;
+            if self.recent.len() == SMOOTHING_SAMPLES {
+                self.recent.pop_front();
+            }
+            self.recent.push_back((self.download_speed, self.upload_speed));
+
+            self.total_rx_bytes = self.total_rx_bytes.saturating_add(rx_delta);
+            self.total_tx_bytes = self.total_tx_bytes.saturating_add(tx_delta);
+            self.last_rx_bytes = rx_bytes;
+            self.last_tx_bytes = tx_bytes;
+            self.last_sample = now;
+        }
+
+        let (download_speed_avg, upload_speed_avg) = self.average();
+        NetworkStats {
+            download_speed: self.download_speed,
+            upload_speed: self.upload_speed,
+            download_speed_avg,
+            upload_speed_avg,
+            total_downloaded: self.total_rx_bytes,
+            total_uploaded: self.total_tx_bytes,
+            connection_duration: now.saturating_duration_since(self.start).as_secs(),
             interface: self.interface.clone(),
-        })
+        }
+    }
+
+    fn average(&self) -> (u64, u64) {
+        if self.recent.is_empty() {
+            return (0, 0);
+        }
+        let count = self.recent.len() as u64;
+        let (download, upload) = self
+            .recent
+            .iter()
+            .fold((0u64, 0u64), |(d, u), (down, up)| (d.saturating_add(*down), u.saturating_add(*up)));
+        (download / count, upload / count)
     }
-    
+
     /// Get the current tracked interface name
     pub fn get_interface(&self) -> &str {
         &self.interface
     }
-    
-    /// Get bandwidth point for historical tracking
-    pub fn get_bandwidth_point(&mut self) -> Result<BandwidthPoint> {
-        let stats = self.get_stats()?;
-        
-        Ok(BandwidthPoint {
-            timestamp: Self::current_timestamp(),
-            download_speed: stats.download_speed,
-            upload_speed: stats.upload_speed,
-        })
-    }
-    
+
     /// Read interface statistics from /sys/class/net
     fn read_interface_stats(interface: &str) -> Result<(u64, u64)> {
-        let rx_path = format!("/sys/class/net/{}/statistics/rx_bytes", interface);
-        let tx_path = format!("/sys/class/net/{}/statistics/tx_bytes", interface);
-        
-        let rx_bytes = fs::read_to_string(&rx_path)
-            .map_err(|e| crate::error::NetworkError::OperationError(
-                format!("Failed to read rx_bytes for {}: {}", interface, e)
-            ))?
-            .trim()
-            .parse::<u64>()
-            .map_err(|e| crate::error::NetworkError::OperationError(
-                format!("Failed to parse rx_bytes: {}", e)
-            ))?;
-        
-        let tx_bytes = fs::read_to_string(&tx_path)
-            .map_err(|e| crate::error::NetworkError::OperationError(
-                format!("Failed to read tx_bytes for {}: {}", interface, e)
-            ))?
-            .trim()
-            .parse::<u64>()
-            .map_err(|e| crate::error::NetworkError::OperationError(
-                format!("Failed to parse tx_bytes: {}", e)
-            ))?;
-        
-        Ok((rx_bytes, tx_bytes))
-    }
-    
-    /// Get current timestamp in seconds
-    fn current_timestamp() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs()
+        let root = Path::new(SYSFS_NET_ROOT);
+        Ok((
+            read_counter(root, interface, "rx_bytes")?,
+            read_counter(root, interface, "tx_bytes")?,
+        ))
     }
 }
 
-/// Get list of available network interfaces
-pub fn get_network_interfaces() -> Result<Vec<String>> {
-    let net_path = "/sys/class/net";
-    let entries = fs::read_dir(net_path)
+/// Bytes counted between two readings. A counter that went backwards was
+/// reset (the interface was recreated, a driver reloaded) and has counted
+/// up from zero since, so its new value is the delta.
+pub(crate) fn counter_delta(previous: u64, current: u64) -> u64 {
+    current.checked_sub(previous).unwrap_or(current)
+}
+
+/// One counter of `<root>/<interface>/statistics`.
+fn read_counter(root: &Path, interface: &str, name: &str) -> Result<u64> {
+    let path = root.join(interface).join("statistics").join(name);
+    fs::read_to_string(&path)
+        .map_err(|e| crate::error::NetworkError::OperationError(
+            format!("Failed to read {} for {}: {}", name, interface, e)
+        ))?
+        .trim()
+        .parse::<u64>()
         .map_err(|e| crate::error::NetworkError::OperationError(
-            format!("Failed to read {}: {}", net_path, e)
+            format!("Failed to parse {}: {}", name, e)
+        ))
+}
+
+/// Every counter of an interface. Only `rx_bytes` is required: virtual
+/// drivers leave some of the others out, and those read as 0.
+pub fn read_interface_counters(root: &Path, interface: &str) -> Result<InterfaceCounters> {
+    let optional = |name: &str| read_counter(root, interface, name).unwrap_or(0);
+    Ok(InterfaceCounters {
+        rx_bytes: read_counter(root, interface, "rx_bytes")?,
+        tx_bytes: optional("tx_bytes"),
+        rx_packets: optional("rx_packets"),
+        tx_packets: optional("tx_packets"),
+        rx_errors: optional("rx_errors"),
+        tx_errors: optional("tx_errors"),
+        rx_dropped: optional("rx_dropped"),
+        tx_dropped: optional("tx_dropped"),
+        collisions: optional("collisions"),
+        multicast: optional("multicast"),
+    })
+}
+
+/// Interfaces under `root`, loopback left out, sorted by name.
+pub fn list_interfaces(root: &Path) -> Result<Vec<String>> {
+    let entries = fs::read_dir(root)
+        .map_err(|e| crate::error::NetworkError::OperationError(
+            format!("Failed to read {}: {}", root.display(), e)
         ))?;
-    
-    let mut interfaces = Vec::new();
-    for entry in entries {
-        if let Ok(entry) = entry {
-            if let Some(name) = entry.file_name().to_str() {
-                // Skip loopback interface
-                if name != "lo" {
-                    interfaces.push(name.to_string());
+
+    let mut interfaces: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        // Skip loopback interface
+        .filter(|name| name != "lo")
+        .collect();
+    interfaces.sort();
+    Ok(interfaces)
+}
+
+/// Get list of available network interfaces
+pub fn get_network_interfaces() -> Result<Vec<String>> {
+    list_interfaces(Path::new(SYSFS_NET_ROOT))
+}
+
+/// Reads the full counters of every interface and how fast their error
+/// counters grow. The sysfs root is a parameter so tests can point it at a
+/// fixture directory.
+pub struct InterfaceStatisticsReader {
+    root: PathBuf,
+    /// Last reading per interface, when it was taken, and the rates it gave.
+    previous: HashMap<String, (InterfaceCounters, Instant, ErrorRates)>,
+}
+
+impl Default for InterfaceStatisticsReader {
+    fn default() -> Self {
+        Self::with_root(SYSFS_NET_ROOT)
+    }
+}
+
+impl InterfaceStatisticsReader {
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Every interface, sorted by name. One that vanishes between listing
+    /// and reading is skipped.
+    pub fn read_all(&mut self) -> Result<Vec<InterfaceStatistics>> {
+        self.read_all_at(Instant::now())
+    }
+
+    fn read_all_at(&mut self, now: Instant) -> Result<Vec<InterfaceStatistics>> {
+        let interfaces = list_interfaces(&self.root)?;
+        self.previous.retain(|interface, _| interfaces.contains(interface));
+
+        let mut statistics = Vec::new();
+        for interface in interfaces {
+            let Ok(counters) = read_interface_counters(&self.root, &interface) else {
+                continue;
+            };
+            let error_rates = match self.previous.get(&interface) {
+                // Too soon for a meaningful rate: keep the last one and the
+                // baseline it was measured from.
+                Some((_, at, rates)) if now.saturating_duration_since(*at) < MIN_RATE_INTERVAL => {
+                    rates.clone()
+                }
+                Some((before, at, _)) => {
+                    let rates = error_rates(before, &counters, now.saturating_duration_since(*at));
+                    self.previous
+                        .insert(interface.clone(), (counters.clone(), now, rates.clone()));
+                    rates
+                }
+                None => {
+                    self.previous
+                        .insert(interface.clone(), (counters.clone(), now, ErrorRates::default()));
+                    ErrorRates::default()
                 }
+            };
+            statistics.push(InterfaceStatistics {
+                interface,
+                counters,
+                error_rates,
+            });
+        }
+        Ok(statistics)
+    }
+}
+
+/// Per-second growth of the error counters between two readings.
+fn error_rates(before: &InterfaceCounters, after: &InterfaceCounters, elapsed: Duration) -> ErrorRates {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 {
+        return ErrorRates::default();
+    }
+    let rate = |previous: u64, current: u64| counter_delta(previous, current) as f64 / seconds;
+    ErrorRates {
+        rx_errors: rate(before.rx_errors, after.rx_errors),
+        tx_errors: rate(before.tx_errors, after.tx_errors),
+        rx_dropped: rate(before.rx_dropped, after.rx_dropped),
+        tx_dropped: rate(before.tx_dropped, after.tx_dropped),
+        collisions: rate(before.collisions, after.collisions),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `(milliseconds since start, rx_bytes, tx_bytes)` readings.
+    fn feed(readings: &[(u64, u64, u64)]) -> Vec<NetworkStats> {
+        let start = Instant::now();
+        let (_, rx, tx) = readings[0];
+        let mut tracker = NetworkStatsTracker::with_counters("eth0".to_string(), rx, tx, start);
+        readings[1..]
+            .iter()
+            .map(|(ms, rx, tx)| tracker.observe(*rx, *tx, start + Duration::from_millis(*ms)))
+            .collect()
+    }
+
+    #[test]
+    fn sub_second_samples_give_a_speed() {
+        let stats = feed(&[(0, 0, 0), (500, 50_000, 5_000)]);
+        assert_eq!(stats[0].download_speed, 100_000);
+        assert_eq!(stats[0].upload_speed, 10_000);
+        assert_eq!(stats[0].total_downloaded, 50_000);
+    }
+
+    #[test]
+    fn calls_too_close_together_keep_the_last_speed() {
+        let stats = feed(&[(0, 0, 0), (1_000, 1_000, 0), (1_010, 1_500, 0), (2_000, 3_000, 0)]);
+        assert_eq!(stats[1].download_speed, 1_000);
+        assert_eq!(stats[1].total_downloaded, 1_000);
+        // The bytes of the skipped reading are counted in the next one.
+        assert_eq!(stats[2].download_speed, 2_000);
+        assert_eq!(stats[2].total_downloaded, 3_000);
+    }
+
+    #[test]
+    fn counter_reset_does_not_panic_or_go_backwards() {
+        let stats = feed(&[
+            (0, 10_000_000, 2_000_000),
+            (1_000, 10_004_000, 2_001_000),
+            // Interface recreated: counters start again from zero.
+            (2_000, 3_000, 500),
+            (3_000, 5_000, 1_500),
+        ]);
+        assert_eq!(stats[1].download_speed, 3_000);
+        assert_eq!(stats[1].upload_speed, 500);
+        assert_eq!(stats[2].download_speed, 2_000);
+        assert_eq!(stats[2].total_downloaded, 4_000 + 3_000 + 2_000);
+        assert_eq!(stats[2].total_uploaded, 1_000 + 500 + 1_000);
+    }
+
+    #[test]
+    fn average_smooths_over_the_last_samples() {
+        // One second apart: 1000, 1000, 1000, 1000, 6000 bytes.
+        let stats = feed(&[
+            (0, 0, 0),
+            (1_000, 1_000, 0),
+            (2_000, 2_000, 0),
+            (3_000, 3_000, 0),
+            (4_000, 4_000, 0),
+            (5_000, 10_000, 0),
+            (6_000, 11_000, 0),
+        ]);
+        assert_eq!(stats[4].download_speed, 6_000);
+        assert_eq!(stats[4].download_speed_avg, 2_000);
+        // The burst stays in the window for five samples.
+        assert_eq!(stats[5].download_speed_avg, 2_000);
+        assert_eq!(stats[5].connection_duration, 6);
+    }
+
+    /// A `/sys/class/net` lookalike in a directory of its own.
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir()
+                .join(format!("network-stats-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        fn set(&self, interface: &str, counters: &[(&str, u64)]) {
+            let dir = self.root.join(interface).join("statistics");
+            fs::create_dir_all(&dir).unwrap();
+            for (name, value) in counters {
+                fs::write(dir.join(name), format!("{}\n", value)).unwrap();
             }
         }
     }
-    
-    Ok(interfaces)
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn reads_every_counter_of_every_interface() {
+        let fixture = Fixture::new("counters");
+        fixture.set("lo", &[("rx_bytes", 1)]);
+        fixture.set("wlan0", &[("rx_bytes", 10), ("tx_bytes", 20), ("rx_packets", 3), ("multicast", 4)]);
+        fixture.set("eth0", &[("rx_bytes", 5), ("rx_errors", 2), ("collisions", 1)]);
+        // A directory without statistics is not an interface worth reporting.
+        fs::create_dir_all(fixture.root.join("bonding_masters")).unwrap();
+
+        let stats = InterfaceStatisticsReader::with_root(&fixture.root).read_all().unwrap();
+
+        let names: Vec<&str> = stats.iter().map(|s| s.interface.as_str()).collect();
+        assert_eq!(names, vec!["eth0", "wlan0"]);
+        assert_eq!(stats[0].counters.rx_errors, 2);
+        assert_eq!(stats[0].counters.collisions, 1);
+        assert_eq!(stats[0].counters.tx_bytes, 0);
+        assert_eq!(stats[1].counters.multicast, 4);
+        assert_eq!(stats[1].error_rates, ErrorRates::default());
+    }
+
+    #[test]
+    fn error_counters_get_a_rate_of_change() {
+        let fixture = Fixture::new("rates");
+        let mut reader = InterfaceStatisticsReader::with_root(&fixture.root);
+        let start = Instant::now();
+
+        fixture.set("eth0", &[("rx_bytes", 0), ("rx_errors", 100), ("tx_dropped", 7)]);
+        reader.read_all_at(start).unwrap();
+
+        fixture.set("eth0", &[("rx_errors", 150), ("tx_dropped", 9)]);
+        let stats = reader.read_all_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(stats[0].error_rates.rx_errors, 25.0);
+        assert_eq!(stats[0].error_rates.tx_dropped, 1.0);
+
+        // The driver was reloaded and counts from zero again.
+        fixture.set("eth0", &[("rx_errors", 4), ("tx_dropped", 0)]);
+        let stats = reader.read_all_at(start + Duration::from_secs(4)).unwrap();
+        assert_eq!(stats[0].error_rates.rx_errors, 2.0);
+        assert_eq!(stats[0].error_rates.tx_dropped, 0.0);
+    }
+
+    #[test]
+    fn counter_delta_is_reset_aware() {
+        assert_eq!(counter_delta(100, 150), 50);
+        assert_eq!(counter_delta(u64::MAX, 10), 10);
+        assert_eq!(counter_delta(0, 0), 0);
+    }
 }
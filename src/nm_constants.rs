@@ -12,6 +12,27 @@ pub const SEC_FLAGS_KEY_MGMT_PSK: u32 = 0x00000100;
 pub const SEC_FLAGS_KEY_MGMT_802_1X: u32 = 0x00000200;
 pub const SEC_FLAGS_KEY_MGMT_SAE: u32 = 0x01000000;
 
+// IEEE 802.11: an SSID is at most 32 bytes
+pub const SSID_MAX_LEN: usize = 32;
+
+// NMSettingsUpdate2Flags — persist the change to the connection's profile on disk
+pub const UPDATE2_FLAG_TO_DISK: u32 = 0x1;
+
+// NMBluetoothCapabilities — BtCapabilities of a Bluetooth device
+pub const NM_BT_CAPABILITY_DUN: u32 = 0x1;
+pub const NM_BT_CAPABILITY_NAP: u32 = 0x2;
+
+// NMMetered — Device.Metered and connection.metered (settings take only 0-2)
+pub const NM_METERED_UNKNOWN: u32 = 0;
+pub const NM_METERED_YES: u32 = 1;
+pub const NM_METERED_NO: u32 = 2;
+pub const NM_METERED_GUESS_YES: u32 = 3;
+pub const NM_METERED_GUESS_NO: u32 = 4;
+
+// NMSettingProxyMethod
+pub const NM_SETTING_PROXY_METHOD_NONE: i32 = 0;
+pub const NM_SETTING_PROXY_METHOD_AUTO: i32 = 1;
+
 // D-Bus interface names
 pub const IFACE_NM: &str = "org.freedesktop.NetworkManager";
 pub const IFACE_NM_ACCESS_POINT: &str = "org.freedesktop.NetworkManager.AccessPoint";
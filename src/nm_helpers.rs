@@ -1,7 +1,14 @@
 use zbus::zvariant::Value;
 use zbus::names::InterfaceName;
-use crate::error::Result;
-use crate::models::WiFiSecurityType;
+use crate::error::{NetworkError, Result};
+use crate::models::{
+    ActiveConnectionState, BluetoothTetherConfig, BluetoothTetherType, BondConfig, BondMode, BridgeConfig, ConnectivityState, DeviceState, DhcpLease, DeviceType, Eap8021xConfig, EapMethod,
+    IpAddressInfo, IpFamilySettings, IpMethod, ManualProxy, ConnectionPriority, Duplex, MeteredState, MobileBroadbandConfig, MobileBroadbandType,
+    ModemState, ProxyMethod, ProxySettings, RegistrationState,
+    RouteInfo, KnownNetworkFilter, MacAddressPolicy, NetworkInfo, Phase2Auth, WiFiSecurityType, WifiNetworkQuery,
+    WifiSortKey, VirtualInterfaceConfig, VirtualInterfaceKind, VlanConfig,
+};
+use std::collections::{HashMap, HashSet};
 use crate::nm_constants::*;
 
 pub struct NetworkManagerHelpers;
@@ -106,22 +113,2370 @@ impl NetworkManagerHelpers {
         })
     }
 
-    pub fn ssid_from_value(value: &Value<'_>) -> String {
+    /// Whether an access point hides its SSID. Some send nothing, others a
+    /// run of NUL bytes the length of the real name.
+    pub fn is_hidden_ssid(ssid_hex: &str) -> bool {
+        ssid_hex.bytes().all(|b| b == b'0')
+    }
+
+    /// Filters, deduplicates, sorts and truncates a scan as `query` asks.
+    ///
+    /// `known` holds the `ssid_hex` of every saved Wi-Fi connection and is
+    /// only looked at when the query filters on it.
+    pub fn apply_wifi_query(
+        networks: Vec<NetworkInfo>,
+        query: &WifiNetworkQuery,
+        known: Option<&HashSet<String>>,
+    ) -> Vec<NetworkInfo> {
+        let is_known = |n: &NetworkInfo| known.is_some_and(|k| k.contains(&n.ssid_hex));
+
+        let mut networks: Vec<NetworkInfo> = networks
+            .into_iter()
+            .filter(|n| query.min_signal.map_or(true, |min| n.signal_strength >= min))
+            .filter(|n| {
+                query
+                    .security_types
+                    .as_ref()
+                    .map_or(true, |types| types.contains(&n.security_type))
+            })
+            .filter(|n| query.include_hidden || !Self::is_hidden_ssid(&n.ssid_hex))
+            .filter(|n| match query.known {
+                KnownNetworkFilter::All => true,
+                KnownNetworkFilter::KnownOnly => is_known(n),
+                KnownNetworkFilter::UnknownOnly => !is_known(n),
+            })
+            .collect();
+
+        if query.dedupe_by_ssid {
+            // Strongest first so the entry kept for each SSID is the best one,
+            // whatever order is asked for afterwards. Hidden networks share
+            // an empty SSID without being the same network, so all of them
+            // stay.
+            networks.sort_by_key(|n| std::cmp::Reverse(n.signal_strength));
+            let mut seen = HashSet::new();
+            networks.retain(|n| Self::is_hidden_ssid(&n.ssid_hex) || seen.insert(n.ssid_hex.clone()));
+        }
+
+        match query.sort_by {
+            WifiSortKey::Signal => {
+                networks.sort_by_key(|n| std::cmp::Reverse(n.signal_strength))
+            }
+            WifiSortKey::Ssid => networks.sort_by_cached_key(|n| n.ssid.to_lowercase()),
+            WifiSortKey::None => {}
+        }
+
+        if let Some(limit) = query.limit {
+            networks.truncate(limit);
+        }
+
+        networks
+    }
+
+    /// Whether `mac` is six colon-separated hex octets.
+    pub fn is_valid_mac(mac: &str) -> bool {
+        let octets: Vec<&str> = mac.split(':').collect();
+        octets.len() == 6
+            && octets
+                .iter()
+                .all(|o| o.len() == 2 && o.bytes().all(|b| b.is_ascii_hexdigit()))
+    }
+
+    /// The `assigned-mac-address` value for a policy.
+    pub fn mac_policy_to_setting(policy: &MacAddressPolicy) -> Result<String> {
+        Ok(match policy {
+            MacAddressPolicy::Preserve => "preserve".to_string(),
+            MacAddressPolicy::Permanent => "permanent".to_string(),
+            MacAddressPolicy::Random => "random".to_string(),
+            MacAddressPolicy::Stable => "stable".to_string(),
+            MacAddressPolicy::Explicit(mac) => {
+                if !Self::is_valid_mac(mac) {
+                    return Err(NetworkError::InvalidSettings(format!(
+                        "'{}' is not a MAC address",
+                        mac
+                    )));
+                }
+                mac.to_uppercase()
+            }
+        })
+    }
+
+    /// Reads an `assigned-mac-address` back. Values this plugin does not
+    /// model (`stable-ssid`, a `${...}` template) come back as `None`.
+    pub fn mac_policy_from_setting(value: &str) -> Option<MacAddressPolicy> {
         match value {
-            Value::Array(ssid_bytes) => {
-                let bytes: Vec<u8> = ssid_bytes
+            "preserve" => Some(MacAddressPolicy::Preserve),
+            "permanent" => Some(MacAddressPolicy::Permanent),
+            "random" => Some(MacAddressPolicy::Random),
+            "stable" => Some(MacAddressPolicy::Stable),
+            mac if Self::is_valid_mac(mac) => Some(MacAddressPolicy::Explicit(mac.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Maps `NMDeviceType`. Types with no use in a desktop panel (OVS,
+    /// 6LoWPAN, MACsec…) are `Generic`.
+    pub fn device_type_from_nm(device_type: u32) -> DeviceType {
+        match device_type {
+            1 => DeviceType::Ethernet,
+            2 => DeviceType::Wifi,
+            5 => DeviceType::Bluetooth,
+            8 => DeviceType::Modem,
+            9 => DeviceType::Infiniband,
+            10 => DeviceType::Bond,
+            11 => DeviceType::Vlan,
+            13 => DeviceType::Bridge,
+            15 => DeviceType::Team,
+            16 => DeviceType::Tun,
+            17 => DeviceType::IpTunnel,
+            18 => DeviceType::Macvlan,
+            19 => DeviceType::Vxlan,
+            20 => DeviceType::Veth,
+            22 => DeviceType::Dummy,
+            23 => DeviceType::Ppp,
+            29 => DeviceType::WireGuard,
+            30 => DeviceType::WifiP2p,
+            32 => DeviceType::Loopback,
+            0 => DeviceType::Unknown,
+            _ => DeviceType::Generic,
+        }
+    }
+
+    /// Maps `NMDeviceState`.
+    pub fn device_state_from_nm(state: u32) -> DeviceState {
+        match state {
+            10 => DeviceState::Unmanaged,
+            20 => DeviceState::Unavailable,
+            30 => DeviceState::Disconnected,
+            40 => DeviceState::Prepare,
+            50 => DeviceState::Config,
+            60 => DeviceState::NeedAuth,
+            70 => DeviceState::IpConfig,
+            80 => DeviceState::IpCheck,
+            90 => DeviceState::Secondaries,
+            100 => DeviceState::Activated,
+            110 => DeviceState::Deactivating,
+            120 => DeviceState::Failed,
+            _ => DeviceState::Unknown,
+        }
+    }
+
+    /// Maps `NMActiveConnectionState`.
+    pub fn active_connection_state_from_nm(state: u32) -> ActiveConnectionState {
+        match state {
+            1 => ActiveConnectionState::Activating,
+            2 => ActiveConnectionState::Activated,
+            3 => ActiveConnectionState::Deactivating,
+            4 => ActiveConnectionState::Deactivated,
+            _ => ActiveConnectionState::Unknown,
+        }
+    }
+
+    /// Looks up a string key in an `a{sv}` dictionary nested in a property.
+    pub fn dict_value<'a>(dict: &'a zbus::zvariant::Dict<'_, '_>, key: &str) -> Option<&'a Value<'a>> {
+        dict.iter().find_map(|(k, v)| match k {
+            Value::Str(k) if k.as_str() == key => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Maps `NMConnectivityState`.
+    pub fn connectivity_from_nm(state: u32) -> ConnectivityState {
+        match state {
+            1 => ConnectivityState::None,
+            2 => ConnectivityState::Portal,
+            3 => ConnectivityState::Limited,
+            4 => ConnectivityState::Full,
+            _ => ConnectivityState::Unknown,
+        }
+    }
+
+    /// Range NetworkManager accepts for `connection.autoconnect-priority`.
+    const AUTOCONNECT_PRIORITY_MAX: i32 = 999;
+
+    pub fn validate_connection_priority(priority: &ConnectionPriority) -> Result<()> {
+        if priority.autoconnect_priority.abs() > Self::AUTOCONNECT_PRIORITY_MAX {
+            return Err(NetworkError::InvalidSettings(format!(
+                "autoconnect priority must be -{0} to {0}",
+                Self::AUTOCONNECT_PRIORITY_MAX
+            )));
+        }
+        Self::check_route_metric(priority.ipv4_route_metric)?;
+        Self::check_route_metric(priority.ipv6_route_metric)
+    }
+
+    /// `route-metric` is a `u32` with -1 standing for the per-type default.
+    fn check_route_metric(metric: Option<i64>) -> Result<()> {
+        if metric.is_some_and(|metric| metric < -1 || metric > u32::MAX as i64) {
+            return Err(NetworkError::InvalidSettings(
+                "route metric must be -1 (default) or 0 to 4294967295".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Autoconnect priorities for connections listed most preferred first:
+    /// the list's length down to 1, so every listed connection is tried
+    /// before the unranked ones at 0.
+    pub fn priorities_for_order(uuids: &[String]) -> Result<Vec<(String, i32)>> {
+        if uuids.len() > Self::AUTOCONNECT_PRIORITY_MAX as usize {
+            return Err(NetworkError::InvalidSettings(format!(
+                "at most {} connections can be ranked",
+                Self::AUTOCONNECT_PRIORITY_MAX
+            )));
+        }
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = uuids.iter().find(|uuid| !seen.insert(uuid.as_str())) {
+            return Err(NetworkError::InvalidSettings(format!(
+                "{} is listed more than once",
+                duplicate
+            )));
+        }
+        let count = uuids.len() as i32;
+        Ok(uuids
+            .iter()
+            .enumerate()
+            .map(|(rank, uuid)| (uuid.clone(), count - rank as i32))
+            .collect())
+    }
+
+    /// Reads the ranking keys of a saved connection. A route metric of -1
+    /// is NetworkManager's "use the default", reported as absent.
+    pub fn connection_priority_from_settings(
+        settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>,
+    ) -> ConnectionPriority {
+        let metric = |section: &str| {
+            settings
+                .get(section)
+                .and_then(|dict| dict.get("route-metric"))
+                .and_then(|v| v.downcast_ref::<i64>().ok())
+                .filter(|metric| *metric >= 0)
+        };
+        ConnectionPriority {
+            autoconnect_priority: settings
+                .get("connection")
+                .and_then(|dict| dict.get("autoconnect-priority"))
+                .and_then(|v| v.downcast_ref::<i32>().ok())
+                .unwrap_or(0),
+            ipv4_route_metric: metric("ipv4"),
+            ipv6_route_metric: metric("ipv6"),
+        }
+    }
+
+    /// The default route among a device's routes, when it has one. Several
+    /// can exist for a moment while a metric changes; the lowest is in use.
+    pub fn default_route(routes: &[RouteInfo]) -> Option<&RouteInfo> {
+        routes
+            .iter()
+            .filter(|route| route.prefix == 0)
+            .min_by_key(|route| route.metric.unwrap_or(0))
+    }
+
+    /// Contents of `/sys/class/net/<iface>/duplex`, which reads `unknown`
+    /// (or fails) while there is no link.
+    pub fn duplex_from_sysfs(value: &str) -> Option<Duplex> {
+        match value.trim() {
+            "full" => Some(Duplex::Full),
+            "half" => Some(Duplex::Half),
+            _ => None,
+        }
+    }
+
+    /// What an `802-3-ethernet` section asks of the port. With
+    /// `auto-negotiate` off and no speed or duplex NetworkManager leaves the
+    /// port as the driver set it, so nothing can be said.
+    pub fn autoneg_from_ethernet_section(
+        section: &HashMap<String, zbus::zvariant::OwnedValue>,
+    ) -> Option<bool> {
+        let auto = section
+            .get("auto-negotiate")
+            .and_then(|v| v.downcast_ref::<bool>().ok())
+            .unwrap_or(false);
+        let forced = section
+            .get("speed")
+            .and_then(|v| v.downcast_ref::<u32>().ok())
+            .is_some_and(|speed| speed > 0)
+            || section
+                .get("duplex")
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .is_some_and(|duplex| !duplex.is_empty());
+        match (auto, forced) {
+            (true, _) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => None,
+        }
+    }
+
+    /// Maps `NMMetered`.
+    pub fn metered_from_nm(value: u32) -> MeteredState {
+        match value {
+            NM_METERED_YES => MeteredState::Yes,
+            NM_METERED_NO => MeteredState::No,
+            NM_METERED_GUESS_YES => MeteredState::GuessYes,
+            NM_METERED_GUESS_NO => MeteredState::GuessNo,
+            _ => MeteredState::Unknown,
+        }
+    }
+
+    /// Value for `connection.metered`. The guesses are NetworkManager's to
+    /// make; a profile that wants them says `Unknown`.
+    pub fn metered_to_setting(value: MeteredState) -> Result<i32> {
+        match value {
+            MeteredState::Unknown => Ok(NM_METERED_UNKNOWN as i32),
+            MeteredState::Yes => Ok(NM_METERED_YES as i32),
+            MeteredState::No => Ok(NM_METERED_NO as i32),
+            MeteredState::GuessYes | MeteredState::GuessNo => Err(NetworkError::InvalidSettings(
+                "metered can only be set to yes, no or unknown".to_string(),
+            )),
+        }
+    }
+
+    /// Reads an `AddressData` property (`aa{sv}` with `address` and
+    /// `prefix`). Same shape for IPv4 and IPv6, unlike the deprecated
+    /// `Addresses`, which packs IPv4 into byte-swapped integers.
+    pub fn addresses_from_address_data(value: &Value) -> Vec<IpAddressInfo> {
+        let Value::Array(entries) = value else {
+            return Vec::new();
+        };
+
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let Value::Dict(dict) = entry else {
+                    return None;
+                };
+                let address = Self::dict_value(dict, "address")?.downcast_ref::<String>().ok()?;
+                let prefix = Self::dict_value(dict, "prefix")?.downcast_ref::<u32>().ok()?;
+                Some(IpAddressInfo {
+                    address,
+                    prefix: prefix.min(128) as u8,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads a `RouteData` property (`aa{sv}` with `dest`, `prefix` and the
+    /// optional `next-hop` and `metric`).
+    pub fn routes_from_route_data(value: &Value) -> Vec<RouteInfo> {
+        let Value::Array(entries) = value else {
+            return Vec::new();
+        };
+
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let Value::Dict(dict) = entry else {
+                    return None;
+                };
+                let destination = Self::dict_value(dict, "dest")?.downcast_ref::<String>().ok()?;
+                let prefix = Self::dict_value(dict, "prefix")?.downcast_ref::<u32>().ok()?;
+                Some(RouteInfo {
+                    destination,
+                    prefix: prefix.min(128) as u8,
+                    next_hop: Self::dict_value(dict, "next-hop")
+                        .and_then(|v| v.downcast_ref::<String>().ok()),
+                    metric: Self::dict_value(dict, "metric").and_then(|v| v.downcast_ref::<u32>().ok()),
+                })
+            })
+            .collect()
+    }
+
+    /// Reads `IP4Config.NameserverData` (`aa{sv}` with `address`).
+    pub fn nameservers_from_nameserver_data(value: &Value) -> Vec<String> {
+        let Value::Array(entries) = value else {
+            return Vec::new();
+        };
+
+        entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Value::Dict(dict) => Self::dict_value(dict, "address")?.downcast_ref::<String>().ok(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reads `IP6Config.Nameservers`, which has no `NameserverData`
+    /// counterpart and stays an array of 16-byte addresses.
+    pub fn ipv6_nameservers(value: &Value) -> Vec<String> {
+        let Value::Array(entries) = value else {
+            return Vec::new();
+        };
+
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let Value::Array(bytes) = entry else {
+                    return None;
+                };
+                let bytes: Vec<u8> = bytes
                     .iter()
-                    .filter_map(|v| {
-                        if let Value::U8(b) = v {
-                            Some(*b)
-                        } else {
-                            None
-                        }
-                    })
+                    .filter_map(|v| if let Value::U8(b) = v { Some(*b) } else { None })
                     .collect();
-                String::from_utf8_lossy(&bytes).to_string()
+                let octets: [u8; 16] = bytes.try_into().ok()?;
+                Some(std::net::Ipv6Addr::from(octets).to_string())
+            })
+            .collect()
+    }
+
+    /// Reads an `as` property.
+    pub fn strings_from_value(value: &Value) -> Vec<String> {
+        match value {
+            Value::Array(entries) => entries
+                .iter()
+                .filter_map(|v| v.downcast_ref::<String>().ok())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Picks the lease length, server and expiry out of DHCP `Options`.
+    /// DHCPv6 has no single lease time or expiry; the `max_life` of the
+    /// address and `life_starts` stand in for them.
+    pub fn dhcp_lease_from_options(options: HashMap<String, String>) -> DhcpLease {
+        let number = |key: &str| options.get(key).and_then(|v| v.trim().parse::<u64>().ok());
+        let lease_time = number("dhcp_lease_time").or_else(|| number("max_life"));
+        let expiry = number("expiry").or_else(|| {
+            number("life_starts").zip(lease_time).map(|(start, life)| start.saturating_add(life))
+        });
+        let server = ["dhcp_server_identifier", "dhcp6_server_id"]
+            .iter()
+            .find_map(|key| options.get(*key))
+            .filter(|v| !v.is_empty())
+            .cloned();
+
+        DhcpLease {
+            lease_time,
+            server,
+            expiry,
+            options,
+        }
+    }
+
+    /// Reads an `a{sv}` whose values are all strings, such as DHCP options.
+    pub fn string_dict(value: &Value) -> HashMap<String, String> {
+        let Value::Dict(dict) = value else {
+            return HashMap::new();
+        };
+
+        dict.iter()
+            .filter_map(|(k, v)| match (k, v.downcast_ref::<String>()) {
+                (Value::Str(k), Ok(v)) => Some((k.to_string(), v)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The `ipv4.method` / `ipv6.method` value for a method.
+    pub fn ip_method_to_setting(method: IpMethod) -> &'static str {
+        match method {
+            IpMethod::Auto => "auto",
+            IpMethod::Manual => "manual",
+            IpMethod::LinkLocal => "link-local",
+            IpMethod::Shared => "shared",
+            IpMethod::Disabled => "disabled",
+            IpMethod::Dhcp => "dhcp",
+            IpMethod::Ignore => "ignore",
+        }
+    }
+
+    /// Reads a method back; one this plugin does not know reads as auto.
+    pub fn ip_method_from_setting(method: &str) -> IpMethod {
+        match method {
+            "manual" => IpMethod::Manual,
+            "link-local" => IpMethod::LinkLocal,
+            "shared" => IpMethod::Shared,
+            "disabled" => IpMethod::Disabled,
+            "dhcp" => IpMethod::Dhcp,
+            "ignore" => IpMethod::Ignore,
+            _ => IpMethod::Auto,
+        }
+    }
+
+    fn parse_ip(address: &str, ipv6: bool) -> Result<std::net::IpAddr> {
+        let invalid = || {
+            NetworkError::InvalidSettings(format!(
+                "'{}' is not an IPv{} address",
+                address,
+                if ipv6 { 6 } else { 4 }
+            ))
+        };
+        let parsed: std::net::IpAddr = address.trim().parse().map_err(|_| invalid())?;
+        if parsed.is_ipv6() != ipv6 {
+            return Err(invalid());
+        }
+        Ok(parsed)
+    }
+
+    fn check_prefix(prefix: u8, ipv6: bool) -> Result<()> {
+        let max = if ipv6 { 128 } else { 32 };
+        if prefix > max {
+            return Err(NetworkError::InvalidSettings(format!(
+                "prefix /{} is longer than {} bits",
+                prefix, max
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks what NetworkManager would otherwise turn down on `Update2`,
+    /// where its error names a property but not the value that broke it.
+    pub fn validate_ip_family_settings(settings: &IpFamilySettings, ipv6: bool) -> Result<()> {
+        match settings.method {
+            IpMethod::Dhcp | IpMethod::Ignore if !ipv6 => {
+                return Err(NetworkError::InvalidSettings(format!(
+                    "{} method is for IPv6 only",
+                    Self::ip_method_to_setting(settings.method)
+                )));
+            }
+            IpMethod::Manual if settings.addresses.is_empty() => {
+                return Err(NetworkError::InvalidSettings(
+                    "manual method needs at least one address".to_string(),
+                ));
+            }
+            IpMethod::LinkLocal | IpMethod::Disabled | IpMethod::Ignore
+                if !settings.addresses.is_empty() || settings.gateway.is_some() =>
+            {
+                return Err(NetworkError::InvalidSettings(format!(
+                    "{} method takes no addresses or gateway",
+                    Self::ip_method_to_setting(settings.method)
+                )));
+            }
+            _ => {}
+        }
+
+        for address in &settings.addresses {
+            Self::parse_ip(&address.address, ipv6)?;
+            Self::check_prefix(address.prefix, ipv6)?;
+        }
+        if let Some(gateway) = &settings.gateway {
+            if settings.addresses.is_empty() {
+                return Err(NetworkError::InvalidSettings(
+                    "a gateway needs a static address".to_string(),
+                ));
+            }
+            Self::parse_ip(gateway, ipv6)?;
+        }
+        for server in &settings.dns {
+            Self::parse_ip(server, ipv6)?;
+        }
+        if settings.dns_search.iter().any(|domain| domain.trim().is_empty()) {
+            return Err(NetworkError::InvalidSettings("empty DNS search domain".to_string()));
+        }
+        Self::check_route_metric(settings.route_metric)?;
+        for route in &settings.routes {
+            Self::parse_ip(&route.destination, ipv6)?;
+            Self::check_prefix(route.prefix, ipv6)?;
+            if let Some(next_hop) = &route.next_hop {
+                Self::parse_ip(next_hop, ipv6)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The keys of an `ipv4` / `ipv6` setting section for `settings`,
+    /// validated. DNS servers go out in the original `au` / `aay` form,
+    /// which every NetworkManager version reads.
+    pub fn ip_family_settings_to_values(
+        settings: &IpFamilySettings,
+        ipv6: bool,
+    ) -> Result<HashMap<String, Value<'static>>> {
+        Self::validate_ip_family_settings(settings, ipv6)?;
+
+        let mut values: HashMap<String, Value<'static>> = HashMap::new();
+        values.insert(
+            "method".to_string(),
+            Value::from(Self::ip_method_to_setting(settings.method)),
+        );
+
+        let address_data: Vec<HashMap<String, Value<'static>>> = settings
+            .addresses
+            .iter()
+            .map(|address| {
+                let mut entry = HashMap::new();
+                entry.insert("address".to_string(), Value::from(address.address.trim().to_string()));
+                entry.insert("prefix".to_string(), Value::from(address.prefix as u32));
+                entry
+            })
+            .collect();
+        values.insert("address-data".to_string(), Value::from(address_data));
+
+        if let Some(gateway) = &settings.gateway {
+            values.insert("gateway".to_string(), Value::from(gateway.trim().to_string()));
+        }
+
+        let servers = settings.dns.iter().map(|server| Self::parse_ip(server, ipv6));
+        let wrong_family = |server: std::net::IpAddr| {
+            NetworkError::InvalidSettings(format!(
+                "DNS server {} is not an IPv{} address",
+                server,
+                if ipv6 { 6 } else { 4 }
+            ))
+        };
+        if ipv6 {
+            let dns: Vec<Vec<u8>> = servers
+                .map(|server| match server? {
+                    std::net::IpAddr::V6(addr) => Ok(addr.octets().to_vec()),
+                    other => Err(wrong_family(other)),
+                })
+                .collect::<Result<_>>()?;
+            values.insert("dns".to_string(), Value::from(dns));
+        } else {
+            // Network byte order, as the deprecated `addresses` uses too.
+            let dns: Vec<u32> = servers
+                .map(|server| match server? {
+                    std::net::IpAddr::V4(addr) => Ok(u32::from_ne_bytes(addr.octets())),
+                    other => Err(wrong_family(other)),
+                })
+                .collect::<Result<_>>()?;
+            values.insert("dns".to_string(), Value::from(dns));
+        }
+
+        let search: Vec<String> = settings.dns_search.iter().map(|d| d.trim().to_string()).collect();
+        values.insert("dns-search".to_string(), Value::from(search));
+        if let Some(metric) = settings.route_metric {
+            values.insert("route-metric".to_string(), Value::from(metric));
+        }
+        values.insert("ignore-auto-dns".to_string(), Value::from(settings.ignore_auto_dns));
+
+        let route_data: Vec<HashMap<String, Value<'static>>> = settings
+            .routes
+            .iter()
+            .map(|route| {
+                let mut entry = HashMap::new();
+                entry.insert("dest".to_string(), Value::from(route.destination.trim().to_string()));
+                entry.insert("prefix".to_string(), Value::from(route.prefix as u32));
+                if let Some(next_hop) = &route.next_hop {
+                    entry.insert("next-hop".to_string(), Value::from(next_hop.trim().to_string()));
+                }
+                if let Some(metric) = route.metric {
+                    entry.insert("metric".to_string(), Value::from(metric));
+                }
+                entry
+            })
+            .collect();
+        values.insert("route-data".to_string(), Value::from(route_data));
+
+        Ok(values)
+    }
+
+    /// Reads an `ipv4` / `ipv6` setting section back.
+    pub fn ip_family_settings_from_section(
+        section: &HashMap<String, zbus::zvariant::OwnedValue>,
+        ipv6: bool,
+    ) -> IpFamilySettings {
+        let get = |key: &str| section.get(key).map(|v| -> &Value { v });
+
+        let dns = match get("dns") {
+            Some(value) if ipv6 => Self::ipv6_nameservers(value),
+            Some(Value::Array(servers)) => servers
+                .iter()
+                .filter_map(|v| match v {
+                    Value::U32(raw) => Some(std::net::Ipv4Addr::from(raw.to_ne_bytes()).to_string()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        IpFamilySettings {
+            method: get("method")
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .map(|m| Self::ip_method_from_setting(&m))
+                .unwrap_or(IpMethod::Auto),
+            addresses: get("address-data")
+                .map(Self::addresses_from_address_data)
+                .unwrap_or_default(),
+            gateway: get("gateway")
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .filter(|g| !g.is_empty()),
+            dns,
+            dns_search: get("dns-search").map(Self::strings_from_value).unwrap_or_default(),
+            route_metric: get("route-metric")
+                .and_then(|v| v.downcast_ref::<i64>().ok())
+                .filter(|metric| *metric >= 0),
+            ignore_auto_dns: get("ignore-auto-dns")
+                .and_then(|v| v.downcast_ref::<bool>().ok())
+                .unwrap_or(false),
+            routes: get("route-data").map(Self::routes_from_route_data).unwrap_or_default(),
+        }
+    }
+
+    /// Name of an `NMDeviceStateReason`, in the kebab-case the rest of the
+    /// plugin's enums use.
+    pub fn device_state_reason_name(reason: u32) -> &'static str {
+        match reason {
+            0 => "none",
+            2 => "now-managed",
+            3 => "now-unmanaged",
+            4 => "config-failed",
+            5 => "ip-config-unavailable",
+            6 => "ip-config-expired",
+            7 => "no-secrets",
+            8 => "supplicant-disconnect",
+            9 => "supplicant-config-failed",
+            10 => "supplicant-failed",
+            11 => "supplicant-timeout",
+            12 => "ppp-start-failed",
+            13 => "ppp-disconnect",
+            14 => "ppp-failed",
+            15 => "dhcp-start-failed",
+            16 => "dhcp-error",
+            17 => "dhcp-failed",
+            18 => "shared-start-failed",
+            19 => "shared-failed",
+            20 => "autoip-start-failed",
+            21 => "autoip-error",
+            22 => "autoip-failed",
+            23 => "modem-busy",
+            24 => "modem-no-dial-tone",
+            25 => "modem-no-carrier",
+            26 => "modem-dial-timeout",
+            27 => "modem-dial-failed",
+            28 => "modem-init-failed",
+            29 => "gsm-apn-failed",
+            30 => "gsm-registration-not-searching",
+            31 => "gsm-registration-denied",
+            32 => "gsm-registration-timeout",
+            33 => "gsm-registration-failed",
+            34 => "gsm-pin-check-failed",
+            35 => "firmware-missing",
+            36 => "removed",
+            37 => "sleeping",
+            38 => "connection-removed",
+            39 => "user-requested",
+            40 => "carrier",
+            41 => "connection-assumed",
+            42 => "supplicant-available",
+            43 => "modem-not-found",
+            44 => "bt-failed",
+            45 => "gsm-sim-not-inserted",
+            46 => "gsm-sim-pin-required",
+            47 => "gsm-sim-puk-required",
+            48 => "gsm-sim-wrong",
+            49 => "infiniband-mode",
+            50 => "dependency-failed",
+            51 => "br2684-failed",
+            52 => "modem-manager-unavailable",
+            53 => "ssid-not-found",
+            54 => "secondary-connection-failed",
+            55 => "dcb-fcoe-failed",
+            56 => "teamd-control-failed",
+            57 => "modem-failed",
+            58 => "modem-available",
+            59 => "sim-pin-incorrect",
+            60 => "new-activation",
+            61 => "parent-changed",
+            62 => "parent-managed-changed",
+            63 => "ovsdb-failed",
+            64 => "ip-address-duplicate",
+            65 => "ip-method-unsupported",
+            66 => "sriov-configuration-failed",
+            67 => "peer-not-found",
+            _ => "unknown",
+        }
+    }
+
+    /// Milliseconds on `CLOCK_BOOTTIME`, the clock `LastScan` is kept in.
+    ///
+    /// `/proc/uptime` counts time spent suspended too, which is what makes it
+    /// that clock and not the monotonic one `Instant` uses.
+    pub fn boottime_ms() -> Option<i64> {
+        let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+        let seconds: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+        Some((seconds * 1000.0) as i64)
+    }
+
+    /// The SSID exactly as NetworkManager reports it (`ay`).
+    ///
+    /// An SSID is 0 to 32 arbitrary bytes, not text. Routers configured in
+    /// Latin-1 or GBK, or with binary names, are perfectly valid networks.
+    pub fn ssid_bytes_from_value(value: &Value<'_>) -> Option<Vec<u8>> {
+        match value {
+            Value::Array(ssid_bytes) => Some(
+                ssid_bytes
+                    .iter()
+                    .filter_map(|v| if let Value::U8(b) = v { Some(*b) } else { None })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Readable form of raw SSID bytes.
+    ///
+    /// UTF-8 when it is UTF-8. Otherwise every byte is read as Latin-1, which
+    /// gets the common case of an old router with accented letters right and
+    /// never turns a byte into "�".
+    pub fn ssid_display(bytes: &[u8]) -> String {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => s.to_string(),
+            Err(_) => bytes.iter().map(|&b| b as char).collect(),
+        }
+    }
+
+    /// Lowercase hex of the raw SSID, the form it travels in to the frontend.
+    pub fn ssid_to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Parses an SSID sent back as hex by the frontend.
+    pub fn ssid_from_hex(hex: &str) -> Result<Vec<u8>> {
+        let hex = hex.trim();
+        if hex.is_empty() || hex.len() % 2 != 0 || hex.len() > SSID_MAX_LEN * 2 {
+            return Err(NetworkError::InvalidSsid(format!(
+                "'{}' is not 1 to {} bytes of hex",
+                hex, SSID_MAX_LEN
+            )));
+        }
+
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(NetworkError::InvalidSsid(format!("'{}' is not valid hex", hex)));
+        }
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| {
+                    NetworkError::InvalidSsid(format!("'{}' is not valid hex", hex))
+                })
+            })
+            .collect()
+    }
+
+    /// The bytes to hand NetworkManager for a network the frontend picked.
+    ///
+    /// The hex form wins when present, since the display string may have
+    /// lost information on the way; `ssid` alone is taken as UTF-8, which is
+    /// what it was before hex existed.
+    pub fn ssid_bytes(ssid: &str, ssid_hex: Option<&str>) -> Result<Vec<u8>> {
+        match ssid_hex {
+            Some(hex) if !hex.trim().is_empty() => Self::ssid_from_hex(hex),
+            _ if ssid.is_empty() || ssid.len() > SSID_MAX_LEN => Err(NetworkError::InvalidSsid(
+                format!("'{}' must be 1 to {} bytes", ssid, SSID_MAX_LEN),
+            )),
+            _ => Ok(ssid.as_bytes().to_vec()),
+        }
+    }
+
+    /// Maps `MMModemState`.
+    pub fn modem_state_from_mm(state: i32) -> ModemState {
+        match state {
+            -1 => ModemState::Failed,
+            1 => ModemState::Initializing,
+            2 => ModemState::Locked,
+            3 => ModemState::Disabled,
+            4 => ModemState::Disabling,
+            5 => ModemState::Enabling,
+            6 => ModemState::Enabled,
+            7 => ModemState::Searching,
+            8 => ModemState::Registered,
+            9 => ModemState::Disconnecting,
+            10 => ModemState::Connecting,
+            11 => ModemState::Connected,
+            _ => ModemState::Unknown,
+        }
+    }
+
+    /// Maps `MMModem3gppRegistrationState`. The SMS-only and CSFB variants
+    /// are still registrations on the home or a visited network.
+    pub fn registration_state_from_3gpp(state: u32) -> RegistrationState {
+        match state {
+            0 => RegistrationState::Idle,
+            1 | 6 | 9 => RegistrationState::Home,
+            2 => RegistrationState::Searching,
+            3 => RegistrationState::Denied,
+            5 | 7 | 10 => RegistrationState::Roaming,
+            8 => RegistrationState::EmergencyOnly,
+            _ => RegistrationState::Unknown,
+        }
+    }
+
+    /// Maps `MMModemCdmaRegistrationState`. Plain "registered" does not say
+    /// where, and is taken as home.
+    pub fn registration_state_from_cdma(state: u32) -> RegistrationState {
+        match state {
+            1 | 2 => RegistrationState::Home,
+            3 => RegistrationState::Roaming,
+            _ => RegistrationState::Unknown,
+        }
+    }
+
+    /// Names of the bits set in an `MMModemAccessTechnology` mask, fastest
+    /// first, so the first one is what a status bar would show.
+    pub fn access_technology_names(mask: u32) -> Vec<String> {
+        const TECHNOLOGIES: &[(u32, &str)] = &[
+            (1 << 15, "5gnr"),
+            (1 << 14, "lte"),
+            (1 << 16, "lte-cat-m"),
+            (1 << 17, "lte-nb-iot"),
+            (1 << 9, "hspa-plus"),
+            (1 << 8, "hspa"),
+            (1 << 7, "hsupa"),
+            (1 << 6, "hsdpa"),
+            (1 << 5, "umts"),
+            (1 << 13, "evdob"),
+            (1 << 12, "evdoa"),
+            (1 << 11, "evdo0"),
+            (1 << 10, "1xrtt"),
+            (1 << 4, "edge"),
+            (1 << 3, "gprs"),
+            (1 << 2, "gsm-compact"),
+            (1 << 1, "gsm"),
+            (1 << 0, "pots"),
+        ];
+        TECHNOLOGIES
+            .iter()
+            .filter(|(bit, _)| mask & bit != 0)
+            .map(|(_, name)| name.to_string())
+            .collect()
+    }
+
+    /// Names of the bits set in an `MMModemCapability` mask.
+    pub fn modem_capability_names(mask: u32) -> Vec<String> {
+        const CAPABILITIES: &[(u32, &str)] = &[
+            (1 << 0, "pots"),
+            (1 << 1, "cdma-evdo"),
+            (1 << 2, "gsm-umts"),
+            (1 << 3, "lte"),
+            (1 << 5, "iridium"),
+            (1 << 6, "5gnr"),
+            (1 << 7, "tds"),
+        ];
+        CAPABILITIES
+            .iter()
+            .filter(|(bit, _)| mask & bit != 0)
+            .map(|(_, name)| name.to_string())
+            .collect()
+    }
+
+    /// Maps `MMModemLock`; `None` when nothing is needed to unlock.
+    pub fn modem_lock_name(lock: u32) -> Option<&'static str> {
+        match lock {
+            0 | 1 => None,
+            2 => Some("sim-pin"),
+            3 => Some("sim-pin2"),
+            4 => Some("sim-puk"),
+            5 => Some("sim-puk2"),
+            6 => Some("ph-sp-pin"),
+            7 => Some("ph-sp-puk"),
+            8 => Some("ph-net-pin"),
+            9 => Some("ph-net-puk"),
+            10 => Some("ph-sim-pin"),
+            11 => Some("ph-corp-pin"),
+            12 => Some("ph-corp-puk"),
+            13 => Some("ph-fsim-pin"),
+            14 => Some("ph-fsim-puk"),
+            15 => Some("ph-netsub-pin"),
+            16 => Some("ph-netsub-puk"),
+            _ => Some("unknown"),
+        }
+    }
+
+    pub fn validate_mobile_broadband_config(config: &MobileBroadbandConfig) -> Result<()> {
+        if let Some(apn) = &config.apn {
+            // 3GPP TS 23.003: labels of letters, digits and hyphens, 100 bytes
+            // at most. Operators also hand out APNs with underscores.
+            let valid = apn.len() <= 100
+                && apn
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+            if !valid {
+                return Err(NetworkError::InvalidSettings(format!("'{}' is not a valid APN", apn)));
+            }
+        }
+        if let Some(pin) = &config.pin {
+            if !(4..=8).contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+                return Err(NetworkError::InvalidSettings("PIN must be 4 to 8 digits".to_string()));
+            }
+        }
+        if config.network_type == MobileBroadbandType::Cdma && config.apn.is_some() {
+            return Err(NetworkError::InvalidSettings("CDMA profiles take no APN".to_string()));
+        }
+        if let Some(interface) = &config.interface {
+            Self::validate_interface_name(interface)?;
+        }
+        Ok(())
+    }
+
+    /// The `gsm` or `cdma` setting section of a mobile broadband profile,
+    /// with the section name.
+    pub fn mobile_broadband_section(
+        config: &MobileBroadbandConfig,
+    ) -> Result<(&'static str, HashMap<String, Value<'static>>)> {
+        Self::validate_mobile_broadband_config(config)?;
+
+        let mut section: HashMap<String, Value<'static>> = HashMap::new();
+        let name = match config.network_type {
+            MobileBroadbandType::Gsm => {
+                section.insert(
+                    "apn".to_string(),
+                    Value::from(config.apn.clone().unwrap_or_default()),
+                );
+                section.insert("home-only".to_string(), Value::from(!config.roaming_allowed));
+                if let Some(pin) = &config.pin {
+                    section.insert("pin".to_string(), Value::from(pin.clone()));
+                }
+                "gsm"
+            }
+            MobileBroadbandType::Cdma => {
+                // The number every CDMA operator uses for packet data.
+                section.insert("number".to_string(), Value::from("#777"));
+                "cdma"
+            }
+        };
+        if let Some(username) = &config.username {
+            section.insert("username".to_string(), Value::from(username.clone()));
+        }
+        if let Some(password) = &config.password {
+            section.insert("password".to_string(), Value::from(password.clone()));
+        }
+        Ok((name, section))
+    }
+
+    /// Whether `text` can go into the PAC script of a manual proxy: inside
+    /// a JS string literal, and in a marker comment that a line break would
+    /// end and a `,` would split when read back.
+    fn fits_pac_script(text: &str) -> bool {
+        !text.contains(|c: char| c.is_control() || matches!(c, '"' | '\\' | ','))
+    }
+
+    /// `host:port`, with IPv6 hosts in brackets.
+    fn validate_proxy_endpoint(scheme: &str, endpoint: &str) -> Result<()> {
+        let valid = match endpoint.rsplit_once(':') {
+            Some((host, port)) => {
+                !host.is_empty()
+                    && !host.contains(char::is_whitespace)
+                    && Self::fits_pac_script(host)
+                    && port.parse::<u16>().is_ok_and(|port| port > 0)
+            }
+            None => false,
+        };
+        if !valid {
+            return Err(NetworkError::InvalidSettings(format!(
+                "{} proxy '{}' must be host:port",
+                scheme, endpoint
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn validate_proxy_settings(settings: &ProxySettings) -> Result<()> {
+        match settings.method {
+            ProxyMethod::None => {}
+            ProxyMethod::Auto => {
+                if let Some(url) = &settings.pac_url {
+                    let scheme_ok = ["http://", "https://", "file://"]
+                        .iter()
+                        .any(|scheme| url.starts_with(scheme));
+                    if !scheme_ok {
+                        return Err(NetworkError::InvalidSettings(format!(
+                            "PAC URL '{}' must be http, https or file",
+                            url
+                        )));
+                    }
+                }
+                if settings
+                    .pac_script
+                    .as_deref()
+                    .is_some_and(|script| !script.contains("FindProxyForURL"))
+                {
+                    return Err(NetworkError::InvalidSettings(
+                        "PAC script has no FindProxyForURL function".to_string(),
+                    ));
+                }
+            }
+            ProxyMethod::Manual => {
+                let manual = settings.manual.as_ref().ok_or_else(|| {
+                    NetworkError::InvalidSettings("manual method needs proxies".to_string())
+                })?;
+                let endpoints = [
+                    ("http", &manual.http),
+                    ("https", &manual.https),
+                    ("ftp", &manual.ftp),
+                    ("socks", &manual.socks),
+                ];
+                if endpoints.iter().all(|(_, endpoint)| endpoint.is_none()) {
+                    return Err(NetworkError::InvalidSettings(
+                        "manual method needs at least one proxy".to_string(),
+                    ));
+                }
+                for (scheme, endpoint) in endpoints {
+                    if let Some(endpoint) = endpoint {
+                        Self::validate_proxy_endpoint(scheme, endpoint)?;
+                    }
+                }
+                if manual
+                    .ignore_hosts
+                    .iter()
+                    .any(|host| host.trim().is_empty() || !Self::fits_pac_script(host))
+                {
+                    return Err(NetworkError::InvalidSettings(
+                        "invalid host in ignore_hosts".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// NetworkManager's `proxy` setting knows only "none" and "auto" with a
+    /// PAC file. Manual proxies are saved as a PAC script that does the
+    /// same, with the values repeated in comments after this marker so they
+    /// can be read back as they were given.
+    const MANUAL_PROXY_MARKER: &'static str = "// manual-proxy:";
+
+    pub fn manual_proxy_pac_script(manual: &ManualProxy) -> String {
+        let mut script = String::new();
+        let mut record = |key: &str, value: &str| {
+            script.push_str(&format!("{} {}={}\n", Self::MANUAL_PROXY_MARKER, key, value));
+        };
+        for (key, value) in [
+            ("http", &manual.http),
+            ("https", &manual.https),
+            ("ftp", &manual.ftp),
+            ("socks", &manual.socks),
+        ] {
+            if let Some(value) = value {
+                record(key, value);
+            }
+        }
+        if !manual.ignore_hosts.is_empty() {
+            record("ignore", &manual.ignore_hosts.join(","));
+        }
+
+        let fallback = match &manual.socks {
+            Some(socks) => format!("SOCKS5 {}; SOCKS {}", socks, socks),
+            None => "DIRECT".to_string(),
+        };
+        script.push_str("function FindProxyForURL(url, host) {\n");
+        for host in &manual.ignore_hosts {
+            script.push_str(&format!(
+                "  if (shExpMatch(host, \"{}\")) return \"DIRECT\";\n",
+                host.trim()
+            ));
+        }
+        for (prefix, value) in [("https:", &manual.https), ("http:", &manual.http), ("ftp:", &manual.ftp)] {
+            if let Some(value) = value {
+                script.push_str(&format!(
+                    "  if (url.substring(0, {}) == \"{}\") return \"PROXY {}\";\n",
+                    prefix.len(),
+                    prefix,
+                    value
+                ));
+            }
+        }
+        script.push_str(&format!("  return \"{}\";\n}}\n", fallback));
+        script
+    }
+
+    /// The manual proxies a script written by [`Self::manual_proxy_pac_script`]
+    /// stands for; `None` for any other script.
+    pub fn manual_proxy_from_pac_script(script: &str) -> Option<ManualProxy> {
+        let mut manual = ManualProxy::default();
+        let mut found = false;
+        for line in script.lines() {
+            let Some(entry) = line.strip_prefix(Self::MANUAL_PROXY_MARKER) else {
+                continue;
+            };
+            let Some((key, value)) = entry.trim().split_once('=') else {
+                continue;
+            };
+            found = true;
+            let value = value.to_string();
+            match key {
+                "http" => manual.http = Some(value),
+                "https" => manual.https = Some(value),
+                "ftp" => manual.ftp = Some(value),
+                "socks" => manual.socks = Some(value),
+                "ignore" => manual.ignore_hosts = value.split(',').map(str::to_string).collect(),
+                _ => {}
+            }
+        }
+        found.then_some(manual)
+    }
+
+    /// The `proxy` setting section for `settings`.
+    pub fn proxy_settings_to_values(settings: &ProxySettings) -> Result<HashMap<String, Value<'static>>> {
+        Self::validate_proxy_settings(settings)?;
+
+        let mut values: HashMap<String, Value<'static>> = HashMap::new();
+        values.insert("browser-only".to_string(), Value::from(settings.browser_only));
+        match settings.method {
+            ProxyMethod::None => {
+                values.insert("method".to_string(), Value::from(NM_SETTING_PROXY_METHOD_NONE));
+            }
+            ProxyMethod::Auto => {
+                values.insert("method".to_string(), Value::from(NM_SETTING_PROXY_METHOD_AUTO));
+                if let Some(url) = &settings.pac_url {
+                    values.insert("pac-url".to_string(), Value::from(url.clone()));
+                }
+                if let Some(script) = &settings.pac_script {
+                    values.insert("pac-script".to_string(), Value::from(script.clone()));
+                }
+            }
+            ProxyMethod::Manual => {
+                values.insert("method".to_string(), Value::from(NM_SETTING_PROXY_METHOD_AUTO));
+                let manual = settings.manual.as_ref().cloned().unwrap_or_default();
+                values.insert(
+                    "pac-script".to_string(),
+                    Value::from(Self::manual_proxy_pac_script(&manual)),
+                );
+            }
+        }
+        Ok(values)
+    }
+
+    /// Reads a `proxy` section back. A missing section is "none".
+    pub fn proxy_settings_from_section(
+        section: Option<&HashMap<String, zbus::zvariant::OwnedValue>>,
+    ) -> ProxySettings {
+        let Some(section) = section else {
+            return ProxySettings::default();
+        };
+        let string = |key: &str| {
+            section
+                .get(key)
+                .and_then(|v| v.downcast_ref::<String>().ok())
+                .filter(|s| !s.is_empty())
+        };
+        let browser_only = section
+            .get("browser-only")
+            .and_then(|v| v.downcast_ref::<bool>().ok())
+            .unwrap_or(false);
+        let method = section
+            .get("method")
+            .and_then(|v| v.downcast_ref::<i32>().ok())
+            .unwrap_or(NM_SETTING_PROXY_METHOD_NONE);
+
+        if method != NM_SETTING_PROXY_METHOD_AUTO {
+            return ProxySettings { browser_only, ..ProxySettings::default() };
+        }
+        let pac_script = string("pac-script");
+        if let Some(manual) = pac_script.as_deref().and_then(Self::manual_proxy_from_pac_script) {
+            return ProxySettings {
+                method: ProxyMethod::Manual,
+                pac_url: None,
+                pac_script: None,
+                manual: Some(manual),
+                browser_only,
+            };
+        }
+        ProxySettings {
+            method: ProxyMethod::Auto,
+            pac_url: string("pac-url"),
+            pac_script,
+            manual: None,
+            browser_only,
+        }
+    }
+
+    /// Tether types in an `NMBluetoothCapabilities` mask. NAP on the phone's
+    /// side is what PANU on ours connects to.
+    pub fn bluetooth_capabilities(mask: u32) -> Vec<BluetoothTetherType> {
+        let mut capabilities = Vec::new();
+        if mask & NM_BT_CAPABILITY_NAP != 0 {
+            capabilities.push(BluetoothTetherType::Panu);
+        }
+        if mask & NM_BT_CAPABILITY_DUN != 0 {
+            capabilities.push(BluetoothTetherType::Dun);
+        }
+        capabilities
+    }
+
+    pub fn bluetooth_type_to_setting(tether_type: BluetoothTetherType) -> &'static str {
+        match tether_type {
+            BluetoothTetherType::Panu => "panu",
+            BluetoothTetherType::Dun => "dun",
+        }
+    }
+
+    /// `AA:BB:CC:DD:EE:FF` as the six bytes NetworkManager stores addresses in.
+    pub fn mac_to_bytes(mac: &str) -> Result<Vec<u8>> {
+        if !Self::is_valid_mac(mac) {
+            return Err(NetworkError::InvalidSettings(format!(
+                "'{}' is not a valid address",
+                mac
+            )));
+        }
+        Ok(mac
+            .split(':')
+            .map(|octet| u8::from_str_radix(octet, 16).unwrap_or(0))
+            .collect())
+    }
+
+    /// The setting sections of a Bluetooth tether besides `connection`:
+    /// `bluetooth`, plus the `gsm` or `cdma` one DUN dials with.
+    pub fn bluetooth_tether_sections(
+        config: &BluetoothTetherConfig,
+    ) -> Result<HashMap<String, HashMap<String, Value<'static>>>> {
+        let mut sections: HashMap<String, HashMap<String, Value<'static>>> = HashMap::new();
+
+        let mut bluetooth: HashMap<String, Value<'static>> = HashMap::new();
+        bluetooth.insert("bdaddr".to_string(), Value::from(Self::mac_to_bytes(&config.address)?));
+        bluetooth.insert(
+            "type".to_string(),
+            Value::from(Self::bluetooth_type_to_setting(config.tether_type)),
+        );
+        sections.insert("bluetooth".to_string(), bluetooth);
+
+        match (config.tether_type, &config.mobile_broadband) {
+            (BluetoothTetherType::Dun, Some(mobile_broadband)) => {
+                let (name, section) = Self::mobile_broadband_section(mobile_broadband)?;
+                sections.insert(name.to_string(), section);
+            }
+            (BluetoothTetherType::Dun, None) => {
+                return Err(NetworkError::InvalidSettings(
+                    "dun needs mobile broadband settings".to_string(),
+                ));
+            }
+            (BluetoothTetherType::Panu, Some(_)) => {
+                return Err(NetworkError::InvalidSettings(
+                    "panu takes no mobile broadband settings".to_string(),
+                ));
             }
-            _ => "Unknown".to_string(),
+            (BluetoothTetherType::Panu, None) => {}
+        }
+        Ok(sections)
+    }
+
+    /// Kernel interface names are at most 15 bytes and may not contain a
+    /// slash, a colon or whitespace.
+    pub fn validate_interface_name(name: &str) -> Result<()> {
+        let valid = !name.is_empty()
+            && name.len() <= 15
+            && name != "."
+            && name != ".."
+            && !name.chars().any(|c| c == '/' || c == ':' || c.is_whitespace());
+        if !valid {
+            return Err(NetworkError::InvalidSettings(format!(
+                "'{}' is not a valid interface name",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn bond_mode_to_setting(mode: BondMode) -> &'static str {
+        match mode {
+            BondMode::BalanceRr => "balance-rr",
+            BondMode::ActiveBackup => "active-backup",
+            BondMode::BalanceXor => "balance-xor",
+            BondMode::Broadcast => "broadcast",
+            BondMode::Ieee8023ad => "802.3ad",
+            BondMode::BalanceTlb => "balance-tlb",
+            BondMode::BalanceAlb => "balance-alb",
+        }
+    }
+
+    /// The kernel also takes the modes by number.
+    pub fn bond_mode_from_setting(value: &str) -> Option<BondMode> {
+        match value {
+            "balance-rr" | "0" => Some(BondMode::BalanceRr),
+            "active-backup" | "1" => Some(BondMode::ActiveBackup),
+            "balance-xor" | "2" => Some(BondMode::BalanceXor),
+            "broadcast" | "3" => Some(BondMode::Broadcast),
+            "802.3ad" | "4" => Some(BondMode::Ieee8023ad),
+            "balance-tlb" | "5" => Some(BondMode::BalanceTlb),
+            "balance-alb" | "6" => Some(BondMode::BalanceAlb),
+            _ => None,
+        }
+    }
+
+    /// The `connection.type` a kind of virtual interface is saved as.
+    pub fn virtual_interface_type(kind: &VirtualInterfaceKind) -> &'static str {
+        match kind {
+            VirtualInterfaceKind::Vlan(_) => "vlan",
+            VirtualInterfaceKind::Bridge(_) => "bridge",
+            VirtualInterfaceKind::Bond(_) => "bond",
+        }
+    }
+
+    fn check_range(name: &str, value: Option<u32>, min: u32, max: u32) -> Result<()> {
+        match value {
+            Some(value) if value < min || value > max => Err(NetworkError::InvalidSettings(
+                format!("{} must be {} to {}", name, min, max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn validate_virtual_interface_config(config: &VirtualInterfaceConfig) -> Result<()> {
+        Self::validate_interface_name(&config.interface_name)?;
+
+        let ports = match &config.kind {
+            VirtualInterfaceKind::Vlan(vlan) => {
+                if !(1..=4094).contains(&vlan.id) {
+                    return Err(NetworkError::InvalidSettings(
+                        "VLAN id must be 1 to 4094".to_string(),
+                    ));
+                }
+                Self::validate_interface_name(&vlan.parent)?;
+                return Ok(());
+            }
+            VirtualInterfaceKind::Bridge(bridge) => {
+                // The ranges the kernel accepts, which NetworkManager enforces too.
+                Self::check_range("forward_delay", bridge.forward_delay, 2, 30)?;
+                Self::check_range("hello_time", bridge.hello_time, 1, 10)?;
+                Self::check_range("max_age", bridge.max_age, 6, 40)?;
+                &bridge.ports
+            }
+            VirtualInterfaceKind::Bond(bond) => &bond.ports,
+        };
+
+        let mut seen = HashSet::new();
+        for port in ports {
+            Self::validate_interface_name(port)?;
+            if port == &config.interface_name {
+                return Err(NetworkError::InvalidSettings(format!(
+                    "{} cannot be a port of itself",
+                    port
+                )));
+            }
+            if !seen.insert(port.as_str()) {
+                return Err(NetworkError::InvalidSettings(format!("port {} is listed twice", port)));
+            }
+        }
+        Ok(())
+    }
+
+    /// The type-specific setting section of a virtual interface: `vlan`,
+    /// `bridge` or `bond`, named by [`Self::virtual_interface_type`]. Ports
+    /// are not part of it; they are profiles of their own.
+    pub fn virtual_interface_section(
+        kind: &VirtualInterfaceKind,
+    ) -> HashMap<String, Value<'static>> {
+        let mut section: HashMap<String, Value<'static>> = HashMap::new();
+        match kind {
+            VirtualInterfaceKind::Vlan(vlan) => {
+                section.insert("id".to_string(), Value::from(vlan.id as u32));
+                section.insert("parent".to_string(), Value::from(vlan.parent.clone()));
+            }
+            VirtualInterfaceKind::Bridge(bridge) => {
+                section.insert("stp".to_string(), Value::from(bridge.stp));
+                let timers = [
+                    ("priority", bridge.priority.map(u32::from)),
+                    ("forward-delay", bridge.forward_delay),
+                    ("hello-time", bridge.hello_time),
+                    ("max-age", bridge.max_age),
+                ];
+                for (key, value) in timers {
+                    if let Some(value) = value {
+                        section.insert(key.to_string(), Value::from(value));
+                    }
+                }
+            }
+            VirtualInterfaceKind::Bond(bond) => {
+                section.insert(
+                    "options".to_string(),
+                    Value::from(Self::bond_options(bond, HashMap::new())),
+                );
+            }
+        }
+        section
+    }
+
+    /// Keys of a type section that [`Self::virtual_interface_section`] owns,
+    /// so an update can clear the ones it no longer sets and keep the rest.
+    pub fn virtual_interface_keys(kind: &VirtualInterfaceKind) -> &'static [&'static str] {
+        match kind {
+            VirtualInterfaceKind::Vlan(_) => &["id", "parent"],
+            VirtualInterfaceKind::Bridge(_) => {
+                &["stp", "priority", "forward-delay", "hello-time", "max-age"]
+            }
+            VirtualInterfaceKind::Bond(_) => &["options"],
+        }
+    }
+
+    /// `bond.options` with `mode` and `miimon` from `bond`, keeping whatever
+    /// else `existing` had set (`lacp_rate`, `primary`…).
+    pub fn bond_options(bond: &BondConfig, existing: HashMap<String, String>) -> HashMap<String, String> {
+        let mut options = existing;
+        options.insert("mode".to_string(), Self::bond_mode_to_setting(bond.mode).to_string());
+        match bond.miimon {
+            Some(miimon) => {
+                options.insert("miimon".to_string(), miimon.to_string());
+            }
+            None => {
+                options.remove("miimon");
+            }
+        }
+        options
+    }
+
+    /// Reads a virtual interface kind back from a profile's settings. Ports
+    /// come out empty: they live in other profiles.
+    pub fn virtual_interface_kind_from_settings(
+        settings: &HashMap<String, HashMap<String, zbus::zvariant::OwnedValue>>,
+    ) -> Option<VirtualInterfaceKind> {
+        let value = |section: &str, key: &str| -> Option<&Value> {
+            settings.get(section)?.get(key).map(|v| -> &Value { v })
+        };
+        let u32_value = |section: &str, key: &str| {
+            value(section, key).and_then(|v| v.downcast_ref::<u32>().ok())
+        };
+        let connection_type = value("connection", "type")?.downcast_ref::<String>().ok()?;
+
+        match connection_type.as_str() {
+            "vlan" => Some(VirtualInterfaceKind::Vlan(VlanConfig {
+                id: u32_value("vlan", "id").unwrap_or(0) as u16,
+                parent: value("vlan", "parent")
+                    .and_then(|v| v.downcast_ref::<String>().ok())
+                    .unwrap_or_default(),
+            })),
+            "bridge" => Some(VirtualInterfaceKind::Bridge(BridgeConfig {
+                // NetworkManager turns STP on when the property is absent.
+                stp: value("bridge", "stp")
+                    .and_then(|v| v.downcast_ref::<bool>().ok())
+                    .unwrap_or(true),
+                priority: u32_value("bridge", "priority").map(|p| p as u16),
+                forward_delay: u32_value("bridge", "forward-delay"),
+                hello_time: u32_value("bridge", "hello-time"),
+                max_age: u32_value("bridge", "max-age"),
+                ports: Vec::new(),
+            })),
+            "bond" => {
+                let options = value("bond", "options").map(Self::string_dict).unwrap_or_default();
+                Some(VirtualInterfaceKind::Bond(BondConfig {
+                    // balance-rr is the kernel's default.
+                    mode: options
+                        .get("mode")
+                        .and_then(|mode| Self::bond_mode_from_setting(mode))
+                        .unwrap_or(BondMode::BalanceRr),
+                    miimon: options.get("miimon").and_then(|m| m.parse().ok()),
+                    ports: Vec::new(),
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn eap_method_to_setting(method: EapMethod) -> &'static str {
+        match method {
+            EapMethod::Peap => "peap",
+            EapMethod::Ttls => "ttls",
+            EapMethod::Tls => "tls",
+        }
+    }
+
+    pub fn phase2_auth_to_setting(auth: Phase2Auth) -> &'static str {
+        match auth {
+            Phase2Auth::Mschapv2 => "mschapv2",
+            Phase2Auth::Mschap => "mschap",
+            Phase2Auth::Pap => "pap",
+            Phase2Auth::Chap => "chap",
+            Phase2Auth::Gtc => "gtc",
+            Phase2Auth::Md5 => "md5",
+        }
+    }
+
+    fn check_cert_path(name: &str, path: &str) -> Result<()> {
+        if !std::path::Path::new(path).is_absolute() {
+            return Err(NetworkError::InvalidSettings(format!(
+                "{} '{}' must be an absolute path",
+                name, path
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks an 802.1X configuration before it reaches NetworkManager, which
+    /// would only say that `802-1x` is invalid and leave the user guessing.
+    pub fn validate_eap_8021x_config(config: &Eap8021xConfig) -> Result<()> {
+        if config.identity.trim().is_empty() {
+            return Err(NetworkError::InvalidSettings("identity is required".to_string()));
+        }
+
+        match config.eap {
+            EapMethod::Peap | EapMethod::Ttls => {
+                if config.password.as_deref().unwrap_or("").is_empty() {
+                    return Err(NetworkError::InvalidSettings(format!(
+                        "{} needs a password",
+                        Self::eap_method_to_setting(config.eap)
+                    )));
+                }
+                // PEAP tunnels only EAP methods; PAP, CHAP and MSCHAP are TTLS-only.
+                if let (
+                    EapMethod::Peap,
+                    Some(auth @ (Phase2Auth::Pap | Phase2Auth::Chap | Phase2Auth::Mschap)),
+                ) = (config.eap, config.phase2_auth)
+                {
+                    return Err(NetworkError::InvalidSettings(format!(
+                        "peap does not support {} as inner authentication",
+                        Self::phase2_auth_to_setting(auth)
+                    )));
+                }
+            }
+            EapMethod::Tls => {
+                if config.client_cert_path.is_none() || config.private_key_path.is_none() {
+                    return Err(NetworkError::InvalidSettings(
+                        "tls needs a client certificate and a private key".to_string(),
+                    ));
+                }
+                if config.phase2_auth.is_some() {
+                    return Err(NetworkError::InvalidSettings(
+                        "tls takes no inner authentication".to_string(),
+                    ));
+                }
+            }
+        }
+
+        for (name, path) in [
+            ("ca_cert_path", &config.ca_cert_path),
+            ("client_cert_path", &config.client_cert_path),
+            ("private_key_path", &config.private_key_path),
+        ] {
+            if let Some(path) = path {
+                Self::check_cert_path(name, path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// NetworkManager takes certificates by reference as a NUL-terminated
+    /// `file://` URI in a byte array; a plain string is rejected.
+    fn cert_path_value(path: &str) -> Value<'static> {
+        let mut bytes = format!("file://{}", path).into_bytes();
+        bytes.push(0);
+        Value::from(bytes)
+    }
+
+    /// The `802-1x` setting section for a configuration, secrets included.
+    pub fn eap_8021x_section(config: &Eap8021xConfig) -> Result<HashMap<String, Value<'static>>> {
+        Self::validate_eap_8021x_config(config)?;
+
+        let mut section: HashMap<String, Value<'static>> = HashMap::new();
+        section.insert(
+            "eap".to_string(),
+            Value::from(vec![Self::eap_method_to_setting(config.eap).to_string()]),
+        );
+        section.insert("identity".to_string(), Value::from(config.identity.trim().to_string()));
+        if let Some(anonymous_identity) = &config.anonymous_identity {
+            section.insert(
+                "anonymous-identity".to_string(),
+                Value::from(anonymous_identity.clone()),
+            );
+        }
+
+        if config.eap != EapMethod::Tls {
+            let phase2 = config.phase2_auth.unwrap_or(Phase2Auth::Mschapv2);
+            section.insert(
+                "phase2-auth".to_string(),
+                Value::from(Self::phase2_auth_to_setting(phase2)),
+            );
+            if let Some(password) = &config.password {
+                section.insert("password".to_string(), Value::from(password.clone()));
+            }
+        }
+
+        if let Some(path) = &config.ca_cert_path {
+            section.insert("ca-cert".to_string(), Self::cert_path_value(path));
+        }
+        section.insert("system-ca-certs".to_string(), Value::from(config.system_ca_certs));
+        if let Some(domain) = &config.domain_suffix_match {
+            section.insert("domain-suffix-match".to_string(), Value::from(domain.clone()));
+        }
+        if let Some(path) = &config.client_cert_path {
+            section.insert("client-cert".to_string(), Self::cert_path_value(path));
+        }
+        if let Some(path) = &config.private_key_path {
+            section.insert("private-key".to_string(), Self::cert_path_value(path));
+        }
+        if let Some(password) = &config.private_key_password {
+            section.insert("private-key-password".to_string(), Value::from(password.clone()));
+        }
+
+        Ok(section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// "Café" in Latin-1: 0xE9 on its own is not UTF-8, and `from_utf8_lossy`
+    /// turned it into "Caf�", the same as every other broken SSID.
+    #[test]
+    fn latin1_ssid_round_trips_through_hex() {
+        let raw = vec![b'C', b'a', b'f', 0xE9];
+        let hex = NetworkManagerHelpers::ssid_to_hex(&raw);
+
+        assert_eq!(hex, "436166e9");
+        assert_eq!(NetworkManagerHelpers::ssid_display(&raw), "Café");
+        assert_eq!(NetworkManagerHelpers::ssid_from_hex(&hex).unwrap(), raw);
+    }
+
+    #[test]
+    fn distinct_invalid_ssids_stay_distinct() {
+        let a = [0xFF, 0x01];
+        let b = [0xFE, 0x01];
+        assert_ne!(
+            NetworkManagerHelpers::ssid_to_hex(&a),
+            NetworkManagerHelpers::ssid_to_hex(&b)
+        );
+    }
+
+    #[test]
+    fn hex_wins_over_display_string() {
+        let bytes = NetworkManagerHelpers::ssid_bytes("Caf?", Some("436166E9")).unwrap();
+        assert_eq!(bytes, vec![b'C', b'a', b'f', 0xE9]);
+
+        let bytes = NetworkManagerHelpers::ssid_bytes("Home", None).unwrap();
+        assert_eq!(bytes, b"Home".to_vec());
+    }
+
+    fn ap(ssid: &str, signal: u8, security: WiFiSecurityType) -> NetworkInfo {
+        NetworkInfo {
+            name: ssid.to_string(),
+            ssid: ssid.to_string(),
+            ssid_hex: NetworkManagerHelpers::ssid_to_hex(ssid.as_bytes()),
+            signal_strength: signal,
+            security_type: security,
+            ..NetworkInfo::default()
+        }
+    }
+
+    #[test]
+    fn dedupe_keeps_the_strongest_access_point() {
+        let scan = vec![
+            ap("Office", 30, WiFiSecurityType::Wpa2Psk),
+            ap("Cafe", 50, WiFiSecurityType::None),
+            ap("Office", 80, WiFiSecurityType::Wpa2Psk),
+        ];
+
+        let list = NetworkManagerHelpers::apply_wifi_query(scan, &WifiNetworkQuery::default(), None);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].ssid, "Office");
+        assert_eq!(list[0].signal_strength, 80);
+    }
+
+    #[test]
+    fn dedupe_keeps_every_hidden_network() {
+        let scan = vec![
+            ap("", 30, WiFiSecurityType::Wpa2Psk),
+            ap("", 60, WiFiSecurityType::None),
+            ap("Cafe", 50, WiFiSecurityType::None),
+        ];
+
+        let list = NetworkManagerHelpers::apply_wifi_query(scan, &WifiNetworkQuery::default(), None);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().filter(|n| n.ssid.is_empty()).count(), 2);
+    }
+
+    #[test]
+    fn query_filters_sorts_and_limits() {
+        let scan = vec![
+            ap("zeta", 90, WiFiSecurityType::Wpa2Psk),
+            ap("", 95, WiFiSecurityType::None),
+            ap("Alpha", 60, WiFiSecurityType::Wpa2Psk),
+            ap("weak", 10, WiFiSecurityType::Wpa2Psk),
+            ap("open", 70, WiFiSecurityType::None),
+            ap("beta", 40, WiFiSecurityType::Wpa2Psk),
+        ];
+        let query = WifiNetworkQuery {
+            min_signal: Some(20),
+            security_types: Some(vec![WiFiSecurityType::Wpa2Psk]),
+            include_hidden: false,
+            sort_by: WifiSortKey::Ssid,
+            limit: Some(2),
+            ..WifiNetworkQuery::default()
+        };
+
+        let list = NetworkManagerHelpers::apply_wifi_query(scan, &query, None);
+        let names: Vec<&str> = list.iter().map(|n| n.ssid.as_str()).collect();
+
+        assert_eq!(names, vec!["Alpha", "beta"]);
+    }
+
+    #[test]
+    fn known_filter_uses_saved_ssids() {
+        let scan = vec![
+            ap("Home", 40, WiFiSecurityType::Wpa2Psk),
+            ap("Neighbour", 60, WiFiSecurityType::Wpa2Psk),
+        ];
+        let known: HashSet<String> = [NetworkManagerHelpers::ssid_to_hex(b"Home")].into();
+
+        let only_known = WifiNetworkQuery {
+            known: KnownNetworkFilter::KnownOnly,
+            ..WifiNetworkQuery::default()
+        };
+        let list = NetworkManagerHelpers::apply_wifi_query(scan.clone(), &only_known, Some(&known));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].ssid, "Home");
+
+        let only_unknown = WifiNetworkQuery {
+            known: KnownNetworkFilter::UnknownOnly,
+            ..WifiNetworkQuery::default()
+        };
+        let list = NetworkManagerHelpers::apply_wifi_query(scan, &only_unknown, Some(&known));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].ssid, "Neighbour");
+    }
+
+    #[test]
+    fn nul_filled_ssid_counts_as_hidden() {
+        assert!(NetworkManagerHelpers::is_hidden_ssid(""));
+        assert!(NetworkManagerHelpers::is_hidden_ssid("000000"));
+        assert!(!NetworkManagerHelpers::is_hidden_ssid("00a0"));
+    }
+
+    #[test]
+    fn mac_policy_round_trips_through_settings() {
+        for policy in [
+            MacAddressPolicy::Preserve,
+            MacAddressPolicy::Permanent,
+            MacAddressPolicy::Random,
+            MacAddressPolicy::Stable,
+            MacAddressPolicy::Explicit("02:00:5E:10:00:01".to_string()),
+        ] {
+            let setting = NetworkManagerHelpers::mac_policy_to_setting(&policy).unwrap();
+            assert_eq!(NetworkManagerHelpers::mac_policy_from_setting(&setting), Some(policy));
+        }
+    }
+
+    #[test]
+    fn explicit_mac_must_be_an_address() {
+        let bad = MacAddressPolicy::Explicit("02:00:5e:10:00".to_string());
+        assert!(NetworkManagerHelpers::mac_policy_to_setting(&bad).is_err());
+        assert_eq!(NetworkManagerHelpers::mac_policy_from_setting("stable-ssid"), None);
+    }
+
+    #[test]
+    fn device_enums_follow_networkmanager_numbering() {
+        assert_eq!(NetworkManagerHelpers::device_type_from_nm(2), DeviceType::Wifi);
+        assert_eq!(NetworkManagerHelpers::device_type_from_nm(29), DeviceType::WireGuard);
+        assert_eq!(NetworkManagerHelpers::device_type_from_nm(26), DeviceType::Generic);
+        assert_eq!(NetworkManagerHelpers::device_state_from_nm(100), DeviceState::Activated);
+        assert_eq!(NetworkManagerHelpers::device_state_from_nm(7), DeviceState::Unknown);
+        assert_eq!(NetworkManagerHelpers::device_state_reason_name(40), "carrier");
+        assert_eq!(NetworkManagerHelpers::device_state_reason_name(999), "unknown");
+    }
+
+    #[test]
+    fn address_data_keeps_every_address_with_its_prefix() {
+        let entry = |address: &str, prefix: u32| {
+            let mut dict = std::collections::HashMap::new();
+            dict.insert("address".to_string(), Value::from(address.to_string()));
+            dict.insert("prefix".to_string(), Value::from(prefix));
+            dict
+        };
+        let value = Value::from(vec![entry("fd00::5", 64), entry("fe80::1", 64)]);
+
+        assert_eq!(
+            NetworkManagerHelpers::addresses_from_address_data(&value),
+            vec![
+                IpAddressInfo { address: "fd00::5".to_string(), prefix: 64 },
+                IpAddressInfo { address: "fe80::1".to_string(), prefix: 64 },
+            ]
+        );
+        assert!(NetworkManagerHelpers::addresses_from_address_data(&Value::from(5u32)).is_empty());
+    }
+
+    #[test]
+    fn route_data_reads_optional_next_hop_and_metric() {
+        let mut default_route = HashMap::new();
+        default_route.insert("dest".to_string(), Value::from("0.0.0.0".to_string()));
+        default_route.insert("prefix".to_string(), Value::from(0u32));
+        default_route.insert("next-hop".to_string(), Value::from("192.168.1.1".to_string()));
+        default_route.insert("metric".to_string(), Value::from(600u32));
+        let mut on_link = HashMap::new();
+        on_link.insert("dest".to_string(), Value::from("192.168.1.0".to_string()));
+        on_link.insert("prefix".to_string(), Value::from(24u32));
+
+        let routes = NetworkManagerHelpers::routes_from_route_data(&Value::from(vec![default_route, on_link]));
+
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].next_hop.as_deref(), Some("192.168.1.1"));
+        assert_eq!(routes[0].metric, Some(600));
+        assert_eq!(routes[1].prefix, 24);
+        assert_eq!(routes[1].next_hop, None);
+    }
+
+    #[test]
+    fn ipv6_nameservers_are_read_from_raw_bytes() {
+        let mut google = vec![0u8; 16];
+        google[..4].copy_from_slice(&[0x20, 0x01, 0x48, 0x60]);
+        google[14..].copy_from_slice(&[0x88, 0x88]);
+        let value = Value::from(vec![google, vec![1, 2, 3]]);
+
+        assert_eq!(
+            NetworkManagerHelpers::ipv6_nameservers(&value),
+            vec!["2001:4860::8888".to_string()]
+        );
+    }
+
+    fn manual_v4() -> IpFamilySettings {
+        IpFamilySettings {
+            method: IpMethod::Manual,
+            addresses: vec![IpAddressInfo { address: "192.168.1.20".to_string(), prefix: 24 }],
+            gateway: Some("192.168.1.1".to_string()),
+            dns: vec!["1.1.1.1".to_string()],
+            dns_search: vec!["lan".to_string()],
+            route_metric: Some(50),
+            ignore_auto_dns: true,
+            routes: vec![RouteInfo {
+                destination: "10.0.0.0".to_string(),
+                prefix: 8,
+                next_hop: Some("192.168.1.254".to_string()),
+                metric: None,
+            }],
         }
     }
+
+    #[test]
+    fn ip_settings_round_trip_through_a_section() {
+        let settings = manual_v4();
+        let values = NetworkManagerHelpers::ip_family_settings_to_values(&settings, false).unwrap();
+        let section: HashMap<String, zbus::zvariant::OwnedValue> = values
+            .into_iter()
+            .map(|(k, v)| (k, zbus::zvariant::OwnedValue::try_from(v).unwrap()))
+            .collect();
+
+        assert_eq!(
+            NetworkManagerHelpers::ip_family_settings_from_section(&section, false),
+            settings
+        );
+    }
+
+    #[test]
+    fn ip_settings_are_validated_before_reaching_networkmanager() {
+        let check = |settings: &IpFamilySettings, ipv6: bool| {
+            NetworkManagerHelpers::validate_ip_family_settings(settings, ipv6).is_ok()
+        };
+        assert!(check(&manual_v4(), false));
+        // Wrong family.
+        assert!(!check(&manual_v4(), true));
+
+        let no_address = IpFamilySettings { addresses: Vec::new(), gateway: None, ..manual_v4() };
+        assert!(!check(&no_address, false));
+
+        let long_prefix = IpFamilySettings {
+            addresses: vec![IpAddressInfo { address: "192.168.1.20".to_string(), prefix: 33 }],
+            ..manual_v4()
+        };
+        assert!(!check(&long_prefix, false));
+
+        let gateway_without_address = IpFamilySettings {
+            method: IpMethod::Auto,
+            addresses: Vec::new(),
+            ..manual_v4()
+        };
+        assert!(!check(&gateway_without_address, false));
+
+        let disabled = IpFamilySettings {
+            method: IpMethod::Disabled,
+            addresses: Vec::new(),
+            gateway: None,
+            dns: Vec::new(),
+            routes: Vec::new(),
+            ..manual_v4()
+        };
+        assert!(check(&disabled, true));
+
+        let dhcpv6 = IpFamilySettings { method: IpMethod::Dhcp, ..disabled.clone() };
+        assert!(check(&dhcpv6, true));
+        assert!(!check(&dhcpv6, false));
+        let ignore = IpFamilySettings { method: IpMethod::Ignore, ..disabled };
+        assert!(check(&ignore, true));
+        assert!(!check(&ignore, false));
+    }
+
+    #[test]
+    fn ipv6_only_methods_round_trip() {
+        for method in [IpMethod::Dhcp, IpMethod::Ignore, IpMethod::Disabled] {
+            let setting = NetworkManagerHelpers::ip_method_to_setting(method);
+            assert_eq!(NetworkManagerHelpers::ip_method_from_setting(setting), method);
+        }
+    }
+
+    fn owned_section(values: HashMap<String, Value<'static>>) -> HashMap<String, zbus::zvariant::OwnedValue> {
+        values
+            .into_iter()
+            .map(|(k, v)| (k, zbus::zvariant::OwnedValue::try_from(v).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn manual_proxy_round_trips_through_a_pac_script() {
+        let settings = ProxySettings {
+            method: ProxyMethod::Manual,
+            manual: Some(ManualProxy {
+                http: Some("proxy.corp.example:3128".to_string()),
+                https: Some("proxy.corp.example:3128".to_string()),
+                socks: Some("[fd00::1]:1080".to_string()),
+                ignore_hosts: vec!["localhost".to_string(), "*.corp.example".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let values = NetworkManagerHelpers::proxy_settings_to_values(&settings).unwrap();
+        assert_eq!(values["method"], Value::from(NM_SETTING_PROXY_METHOD_AUTO));
+        let script: String = values["pac-script"].try_clone().unwrap().try_into().unwrap();
+        assert!(script.contains("FindProxyForURL"));
+        assert!(script.contains("return \"PROXY proxy.corp.example:3128\""));
+        assert!(script.contains("shExpMatch(host, \"*.corp.example\")"));
+
+        let section = owned_section(values);
+        assert_eq!(NetworkManagerHelpers::proxy_settings_from_section(Some(&section)), settings);
+    }
+
+    #[test]
+    fn proxy_settings_are_validated() {
+        let mut settings = ProxySettings {
+            method: ProxyMethod::Auto,
+            pac_url: Some("ftp://wpad/proxy.pac".to_string()),
+            ..Default::default()
+        };
+        assert!(NetworkManagerHelpers::validate_proxy_settings(&settings).is_err());
+        settings.pac_url = Some("http://wpad/wpad.dat".to_string());
+        assert!(NetworkManagerHelpers::validate_proxy_settings(&settings).is_ok());
+
+        settings.method = ProxyMethod::Manual;
+        assert!(NetworkManagerHelpers::validate_proxy_settings(&settings).is_err());
+        settings.manual = Some(ManualProxy { http: Some("proxy".to_string()), ..Default::default() });
+        assert!(NetworkManagerHelpers::validate_proxy_settings(&settings).is_err());
+        settings.manual = Some(ManualProxy {
+            http: Some("proxy:3128".to_string()),
+            ignore_hosts: vec!["localhost".to_string()],
+            ..Default::default()
+        });
+        assert!(NetworkManagerHelpers::validate_proxy_settings(&settings).is_ok());
+        for host in ["intranet\nreturn \"PROXY evil:80\";", "a,b", "c:\\d", "tab\there", "x\"y", " "] {
+            settings.manual.as_mut().unwrap().ignore_hosts = vec![host.to_string()];
+            assert!(
+                NetworkManagerHelpers::validate_proxy_settings(&settings).is_err(),
+                "{:?} accepted",
+                host
+            );
+        }
+
+        assert_eq!(
+            NetworkManagerHelpers::proxy_settings_from_section(None),
+            ProxySettings::default()
+        );
+    }
+
+    #[test]
+    fn bluetooth_tether_sections_need_dialing_settings_only_for_dun() {
+        let mut config = BluetoothTetherConfig {
+            address: "0c:8e:29:aa:bb:01".to_string(),
+            tether_type: BluetoothTetherType::Panu,
+            id: None,
+            mobile_broadband: None,
+        };
+        let sections = NetworkManagerHelpers::bluetooth_tether_sections(&config).unwrap();
+        let bdaddr: Vec<u8> = sections["bluetooth"]["bdaddr"].try_clone().unwrap().try_into().unwrap();
+        assert_eq!(bdaddr, vec![0x0c, 0x8e, 0x29, 0xaa, 0xbb, 0x01]);
+        assert_eq!(sections["bluetooth"]["type"], Value::from("panu"));
+        assert_eq!(sections.len(), 1);
+
+        config.tether_type = BluetoothTetherType::Dun;
+        assert!(NetworkManagerHelpers::bluetooth_tether_sections(&config).is_err());
+        config.mobile_broadband = Some(lte_config());
+        let sections = NetworkManagerHelpers::bluetooth_tether_sections(&config).unwrap();
+        assert!(sections.contains_key("gsm"));
+
+        config.address = "0c:8e:29".to_string();
+        assert!(NetworkManagerHelpers::bluetooth_tether_sections(&config).is_err());
+        assert_eq!(
+            NetworkManagerHelpers::bluetooth_capabilities(NM_BT_CAPABILITY_NAP | NM_BT_CAPABILITY_DUN),
+            vec![BluetoothTetherType::Panu, BluetoothTetherType::Dun]
+        );
+    }
+
+    fn lte_config() -> MobileBroadbandConfig {
+        MobileBroadbandConfig {
+            id: None,
+            network_type: MobileBroadbandType::Gsm,
+            apn: Some("internet.carrier.example".to_string()),
+            username: None,
+            password: None,
+            pin: Some("1234".to_string()),
+            roaming_allowed: false,
+            interface: None,
+            autoconnect: None,
+        }
+    }
+
+    #[test]
+    fn gsm_section_maps_roaming_to_home_only() {
+        let (name, section) = NetworkManagerHelpers::mobile_broadband_section(&lte_config()).unwrap();
+
+        assert_eq!(name, "gsm");
+        assert_eq!(section["apn"], Value::from("internet.carrier.example"));
+        assert_eq!(section["home-only"], Value::from(true));
+        assert_eq!(section["pin"], Value::from("1234"));
+    }
+
+    #[test]
+    fn mobile_broadband_config_is_validated() {
+        let mut config = lte_config();
+        config.pin = Some("12a4".to_string());
+        assert!(NetworkManagerHelpers::validate_mobile_broadband_config(&config).is_err());
+
+        let mut config = lte_config();
+        config.apn = Some("bad apn".to_string());
+        assert!(NetworkManagerHelpers::validate_mobile_broadband_config(&config).is_err());
+
+        let mut config = lte_config();
+        config.network_type = MobileBroadbandType::Cdma;
+        assert!(NetworkManagerHelpers::validate_mobile_broadband_config(&config).is_err());
+        config.apn = None;
+        let (name, section) = NetworkManagerHelpers::mobile_broadband_section(&config).unwrap();
+        assert_eq!(name, "cdma");
+        assert!(!section.contains_key("home-only"));
+    }
+
+    #[test]
+    fn modem_enums_follow_modemmanager_values() {
+        assert_eq!(NetworkManagerHelpers::modem_state_from_mm(-1), ModemState::Failed);
+        assert_eq!(NetworkManagerHelpers::modem_state_from_mm(11), ModemState::Connected);
+        assert_eq!(NetworkManagerHelpers::registration_state_from_3gpp(7), RegistrationState::Roaming);
+        assert_eq!(NetworkManagerHelpers::registration_state_from_cdma(2), RegistrationState::Home);
+        // LTE with UMTS fallback lists LTE first.
+        assert_eq!(
+            NetworkManagerHelpers::access_technology_names((1 << 5) | (1 << 14)),
+            vec!["lte", "umts"]
+        );
+        assert_eq!(NetworkManagerHelpers::modem_lock_name(1), None);
+        assert_eq!(NetworkManagerHelpers::modem_lock_name(2), Some("sim-pin"));
+    }
+
+    fn bridge_config(ports: &[&str]) -> VirtualInterfaceConfig {
+        VirtualInterfaceConfig {
+            interface_name: "br0".to_string(),
+            id: None,
+            autoconnect: None,
+            kind: VirtualInterfaceKind::Bridge(BridgeConfig {
+                stp: false,
+                forward_delay: Some(4),
+                ports: ports.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn virtual_interface_config_is_validated() {
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&bridge_config(&["eth0", "eth1"])).is_ok());
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&bridge_config(&["eth0", "eth0"])).is_err());
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&bridge_config(&["br0"])).is_err());
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&bridge_config(&["a-very-long-ifname"])).is_err());
+
+        let mut config = bridge_config(&[]);
+        config.kind = VirtualInterfaceKind::Bridge(BridgeConfig { hello_time: Some(20), ..Default::default() });
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&config).is_err());
+
+        config.interface_name = "eth0.10".to_string();
+        config.kind = VirtualInterfaceKind::Vlan(VlanConfig { id: 4095, parent: "eth0".to_string() });
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&config).is_err());
+        config.kind = VirtualInterfaceKind::Vlan(VlanConfig { id: 10, parent: "eth0".to_string() });
+        assert!(NetworkManagerHelpers::validate_virtual_interface_config(&config).is_ok());
+    }
+
+    #[test]
+    fn bond_options_keep_unrelated_keys() {
+        let bond = BondConfig { mode: BondMode::Ieee8023ad, miimon: None, ports: Vec::new() };
+        let existing = HashMap::from([
+            ("mode".to_string(), "active-backup".to_string()),
+            ("miimon".to_string(), "100".to_string()),
+            ("lacp_rate".to_string(), "fast".to_string()),
+        ]);
+
+        let options = NetworkManagerHelpers::bond_options(&bond, existing);
+
+        assert_eq!(options.get("mode").map(String::as_str), Some("802.3ad"));
+        assert_eq!(options.get("lacp_rate").map(String::as_str), Some("fast"));
+        assert!(!options.contains_key("miimon"));
+        assert_eq!(NetworkManagerHelpers::bond_mode_from_setting("4"), Some(BondMode::Ieee8023ad));
+    }
+
+    #[test]
+    fn bridge_section_only_sets_given_timers() {
+        let config = bridge_config(&["eth0"]);
+        let section = NetworkManagerHelpers::virtual_interface_section(&config.kind);
+
+        assert_eq!(section["stp"], Value::from(false));
+        assert_eq!(section["forward-delay"], Value::from(4u32));
+        assert!(!section.contains_key("hello-time"));
+        assert!(!section.contains_key("ports"));
+    }
+
+    fn peap_config() -> Eap8021xConfig {
+        Eap8021xConfig {
+            id: None,
+            eap: EapMethod::Peap,
+            identity: "alice".to_string(),
+            anonymous_identity: None,
+            password: Some("secret".to_string()),
+            phase2_auth: None,
+            ca_cert_path: Some("/etc/ssl/corp-ca.pem".to_string()),
+            system_ca_certs: false,
+            domain_suffix_match: None,
+            client_cert_path: None,
+            private_key_path: None,
+            private_key_password: None,
+        }
+    }
+
+    #[test]
+    fn peap_section_defaults_to_mschapv2_and_references_the_ca_file() {
+        let section = NetworkManagerHelpers::eap_8021x_section(&peap_config()).unwrap();
+
+        assert_eq!(NetworkManagerHelpers::strings_from_value(&section["eap"]), vec!["peap"]);
+        assert_eq!(section["phase2-auth"], Value::from("mschapv2"));
+        assert_eq!(section["password"], Value::from("secret"));
+        let ca: Vec<u8> = section["ca-cert"].try_clone().unwrap().try_into().unwrap();
+        assert_eq!(ca, b"file:///etc/ssl/corp-ca.pem\0".to_vec());
+    }
+
+    #[test]
+    fn eap_config_is_validated_per_method() {
+        let mut config = peap_config();
+        config.password = None;
+        assert!(NetworkManagerHelpers::validate_eap_8021x_config(&config).is_err());
+
+        let mut config = peap_config();
+        config.phase2_auth = Some(Phase2Auth::Pap);
+        assert!(NetworkManagerHelpers::validate_eap_8021x_config(&config).is_err());
+        config.eap = EapMethod::Ttls;
+        assert!(NetworkManagerHelpers::validate_eap_8021x_config(&config).is_ok());
+
+        let mut config = peap_config();
+        config.ca_cert_path = Some("certs/ca.pem".to_string());
+        assert!(NetworkManagerHelpers::validate_eap_8021x_config(&config).is_err());
+
+        let mut config = peap_config();
+        config.eap = EapMethod::Tls;
+        config.password = None;
+        assert!(NetworkManagerHelpers::validate_eap_8021x_config(&config).is_err());
+        config.client_cert_path = Some("/etc/ssl/alice.pem".to_string());
+        config.private_key_path = Some("/etc/ssl/alice.key".to_string());
+        let section = NetworkManagerHelpers::eap_8021x_section(&config).unwrap();
+        assert!(!section.contains_key("phase2-auth"));
+        assert!(section.contains_key("private-key"));
+    }
+
+    #[test]
+    fn preferred_order_becomes_descending_priorities() {
+        let uuids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(
+            NetworkManagerHelpers::priorities_for_order(&uuids).unwrap(),
+            vec![("a".to_string(), 3), ("b".to_string(), 2), ("c".to_string(), 1)]
+        );
+
+        let duplicated = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+        assert!(NetworkManagerHelpers::priorities_for_order(&duplicated).is_err());
+
+        let too_many: Vec<String> = (0..1000).map(|i| i.to_string()).collect();
+        assert!(NetworkManagerHelpers::priorities_for_order(&too_many).is_err());
+    }
+
+    #[test]
+    fn connection_priority_is_validated() {
+        let mut priority = ConnectionPriority {
+            autoconnect_priority: 999,
+            ipv4_route_metric: Some(-1),
+            ipv6_route_metric: Some(50),
+        };
+        assert!(NetworkManagerHelpers::validate_connection_priority(&priority).is_ok());
+
+        priority.autoconnect_priority = -1000;
+        assert!(NetworkManagerHelpers::validate_connection_priority(&priority).is_err());
+
+        priority.autoconnect_priority = 0;
+        priority.ipv6_route_metric = Some(-2);
+        assert!(NetworkManagerHelpers::validate_connection_priority(&priority).is_err());
+    }
+
+    #[test]
+    fn lowest_default_route_is_the_one_in_use() {
+        let route = |prefix: u8, metric: u32| RouteInfo {
+            destination: "0.0.0.0".to_string(),
+            prefix,
+            next_hop: Some("192.168.1.1".to_string()),
+            metric: Some(metric),
+        };
+        let routes = vec![route(24, 0), route(0, 600), route(0, 100)];
+        assert_eq!(NetworkManagerHelpers::default_route(&routes).unwrap().metric, Some(100));
+        assert!(NetworkManagerHelpers::default_route(&routes[..1]).is_none());
+    }
+
+    #[test]
+    fn wired_link_details_are_read() {
+        assert_eq!(NetworkManagerHelpers::duplex_from_sysfs("full\n"), Some(Duplex::Full));
+        assert_eq!(NetworkManagerHelpers::duplex_from_sysfs("unknown\n"), None);
+
+        let section = |pairs: Vec<(&str, Value<'static>)>| {
+            owned_section(pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+        };
+        assert_eq!(
+            NetworkManagerHelpers::autoneg_from_ethernet_section(&section(vec![(
+                "auto-negotiate",
+                Value::from(true)
+            )])),
+            Some(true)
+        );
+        assert_eq!(
+            NetworkManagerHelpers::autoneg_from_ethernet_section(&section(vec![
+                ("speed", Value::from(100u32)),
+                ("duplex", Value::from("full")),
+            ])),
+            Some(false)
+        );
+        assert_eq!(NetworkManagerHelpers::autoneg_from_ethernet_section(&section(vec![])), None);
+    }
+
+    #[test]
+    fn only_definite_metered_values_can_be_saved() {
+        assert_eq!(NetworkManagerHelpers::metered_from_nm(3), MeteredState::GuessYes);
+        assert_eq!(NetworkManagerHelpers::metered_from_nm(42), MeteredState::Unknown);
+        assert!(MeteredState::GuessYes.is_metered());
+        assert!(!MeteredState::GuessNo.is_metered());
+
+        assert_eq!(NetworkManagerHelpers::metered_to_setting(MeteredState::Yes).unwrap(), 1);
+        assert_eq!(NetworkManagerHelpers::metered_to_setting(MeteredState::Unknown).unwrap(), 0);
+        assert!(matches!(
+            NetworkManagerHelpers::metered_to_setting(MeteredState::GuessNo),
+            Err(NetworkError::InvalidSettings(_))
+        ));
+    }
+
+    #[test]
+    fn malformed_ssid_hex_is_rejected() {
+        assert!(NetworkManagerHelpers::ssid_from_hex("abc").is_err());
+        assert!(NetworkManagerHelpers::ssid_from_hex("zz").is_err());
+        assert!(NetworkManagerHelpers::ssid_from_hex("").is_err());
+        assert!(NetworkManagerHelpers::ssid_from_hex(&"00".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn dhcp_lease_reads_lease_server_and_expiry() {
+        let v4 = NetworkManagerHelpers::dhcp_lease_from_options(HashMap::from([
+            ("dhcp_lease_time".to_string(), "86400".to_string()),
+            ("dhcp_server_identifier".to_string(), "192.168.1.1".to_string()),
+            ("expiry".to_string(), "1760000000".to_string()),
+            ("domain_name".to_string(), "lan".to_string()),
+        ]));
+        assert_eq!(v4.lease_time, Some(86400));
+        assert_eq!(v4.server.as_deref(), Some("192.168.1.1"));
+        assert_eq!(v4.expiry, Some(1760000000));
+        assert_eq!(v4.options.get("domain_name").map(String::as_str), Some("lan"));
+
+        let v6 = NetworkManagerHelpers::dhcp_lease_from_options(HashMap::from([
+            ("life_starts".to_string(), "1760000000".to_string()),
+            ("max_life".to_string(), "3600".to_string()),
+            ("dhcp6_server_id".to_string(), "0:1:0:1:2a:3b".to_string()),
+        ]));
+        assert_eq!(v6.lease_time, Some(3600));
+        assert_eq!(v6.server.as_deref(), Some("0:1:0:1:2a:3b"));
+        assert_eq!(v6.expiry, Some(1760003600));
+
+        assert_eq!(
+            NetworkManagerHelpers::dhcp_lease_from_options(HashMap::new()),
+            DhcpLease::default()
+        );
+    }
 }
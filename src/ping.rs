@@ -0,0 +1,483 @@
+use socket2::{Domain, Protocol, Socket, Type};
+use std::io;
+use std::net::{IpAddr, SocketAddr, SocketAddrV6, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::error::{NetworkError, Result};
+use crate::models::{
+    IpConfiguration, PingConfig, PingMethod, PingMonitorConfig, PingResult, PingTarget,
+};
+use crate::NetworkManagerState;
+
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+
+/// Carried in every echo request so a capture shows where it came from.
+const ECHO_PAYLOAD: &[u8; 16] = b"tauri-nm-ping\0\0\0";
+
+pub fn probe_count(config: &PingConfig) -> u32 {
+    config.count.clamp(1, 100)
+}
+
+pub fn probe_interval(config: &PingConfig) -> Duration {
+    Duration::from_millis(config.interval_ms.clamp(200, 10_000))
+}
+
+pub fn probe_timeout(config: &PingConfig) -> Duration {
+    Duration::from_millis(config.timeout_ms.clamp(100, 10_000))
+}
+
+/// Time from the start of one monitor round to the start of the next.
+pub fn round_interval(config: &PingMonitorConfig) -> Duration {
+    Duration::from_millis(config.every_ms.clamp(1_000, 3_600_000))
+}
+
+/// A target with the address it stands for, or why there is none.
+pub struct ResolvedTarget {
+    pub label: String,
+    pub address: std::result::Result<SocketAddr, String>,
+}
+
+/// Turns targets into addresses. The gateway and DNS server come from
+/// `ip`, the primary connection's live configuration; a link-local IPv6
+/// one is scoped to that connection's interface, or it could not be
+/// reached.
+pub fn resolve_targets(targets: &[PingTarget], ip: Option<&IpConfiguration>) -> Vec<ResolvedTarget> {
+    targets
+        .iter()
+        .map(|target| match target {
+            PingTarget::Gateway => ResolvedTarget {
+                label: "gateway".to_string(),
+                address: from_connection(ip, 80, |family| family.gateway.iter().cloned().collect())
+                    .ok_or_else(|| "no default gateway".to_string()),
+            },
+            PingTarget::Dns => ResolvedTarget {
+                label: "dns".to_string(),
+                address: from_connection(ip, 53, |family| family.nameservers.clone())
+                    .ok_or_else(|| "no DNS server".to_string()),
+            },
+            PingTarget::Host { host, port } => ResolvedTarget {
+                label: host.clone(),
+                address: resolve_host(host, port.unwrap_or(443)),
+            },
+        })
+        .collect()
+}
+
+/// The first address `pick` finds in the IPv4 configuration, else in the
+/// IPv6 one.
+fn from_connection(
+    ip: Option<&IpConfiguration>,
+    port: u16,
+    pick: impl Fn(&crate::models::IpFamilyConfiguration) -> Vec<String>,
+) -> Option<SocketAddr> {
+    let ip = ip?;
+    let address: IpAddr = pick(&ip.ipv4)
+        .into_iter()
+        .chain(pick(&ip.ipv6))
+        .find_map(|address| address.parse().ok())?;
+    Some(match address {
+        IpAddr::V6(v6) if v6.segments()[0] & 0xffc0 == 0xfe80 => {
+            let scope = interface_index(&ip.interface).unwrap_or(0);
+            SocketAddr::V6(SocketAddrV6::new(v6, port, 0, scope))
+        }
+        address => SocketAddr::new(address, port),
+    })
+}
+
+fn interface_index(interface: &str) -> Option<u32> {
+    std::fs::read_to_string(
+        std::path::Path::new(crate::network_stats::SYSFS_NET_ROOT)
+            .join(interface)
+            .join("ifindex"),
+    )
+    .ok()?
+    .trim()
+    .parse()
+    .ok()
+}
+
+fn resolve_host(host: &str, port: u16) -> std::result::Result<SocketAddr, String> {
+    if let Ok(address) = host.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(address, port));
+    }
+    (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("{} has no address", host))
+}
+
+/// Probes every target at once and returns their results in the same
+/// order. Takes about `count` times `interval_ms`.
+pub fn ping(targets: Vec<ResolvedTarget>, config: &PingConfig) -> Vec<PingResult> {
+    std::thread::scope(|scope| {
+        let probes: Vec<_> = targets
+            .into_iter()
+            .map(|target| scope.spawn(move || probe_target(target, config)))
+            .collect();
+        probes.into_iter().filter_map(|probe| probe.join().ok()).collect()
+    })
+}
+
+fn probe_target(target: ResolvedTarget, config: &PingConfig) -> PingResult {
+    let address = match target.address {
+        Ok(address) => address,
+        Err(e) => return failed(target.label, String::new(), config.method, e),
+    };
+    let prober = match Prober::open(address, config.method) {
+        Ok(prober) => prober,
+        Err(e) => {
+            return failed(
+                target.label,
+                address.ip().to_string(),
+                config.method,
+                format!("cannot open ICMP socket: {}", e),
+            )
+        }
+    };
+
+    let count = probe_count(config);
+    let interval = probe_interval(config);
+    let timeout = probe_timeout(config);
+    let mut round_trips = Vec::new();
+    for sequence in 0..count {
+        let started = Instant::now();
+        if let Some(round_trip) = prober.round_trip(sequence as u16, timeout) {
+            round_trips.push(round_trip);
+        }
+        if sequence + 1 < count {
+            std::thread::sleep(interval.saturating_sub(started.elapsed()));
+        }
+    }
+    summarize(target.label, address.ip().to_string(), prober.method(), count, &round_trips)
+}
+
+fn failed(target: String, address: String, method: PingMethod, error: String) -> PingResult {
+    PingResult {
+        target,
+        address,
+        method,
+        sent: 0,
+        received: 0,
+        packet_loss: 100.0,
+        rtt_min_ms: None,
+        rtt_avg_ms: None,
+        rtt_max_ms: None,
+        jitter_ms: None,
+        error: Some(error),
+    }
+}
+
+/// Statistics of `sent` probes of which `round_trips` came back. Jitter is
+/// the mean difference between consecutive round trips.
+pub fn summarize(
+    target: String,
+    address: String,
+    method: PingMethod,
+    sent: u32,
+    round_trips: &[Duration],
+) -> PingResult {
+    let ms: Vec<f64> = round_trips
+        .iter()
+        .map(|round_trip| round_trip.as_secs_f64() * 1000.0)
+        .collect();
+    let received = ms.len() as u32;
+    let jitter = (ms.len() >= 2).then(|| {
+        ms.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum::<f64>() / (ms.len() - 1) as f64
+    });
+
+    PingResult {
+        target,
+        address,
+        method,
+        sent,
+        received,
+        packet_loss: if sent == 0 {
+            100.0
+        } else {
+            f64::from(sent - received.min(sent)) * 100.0 / f64::from(sent)
+        },
+        rtt_min_ms: ms.iter().copied().reduce(f64::min),
+        rtt_avg_ms: (!ms.is_empty()).then(|| ms.iter().sum::<f64>() / ms.len() as f64),
+        rtt_max_ms: ms.iter().copied().reduce(f64::max),
+        jitter_ms: jitter,
+        error: None,
+    }
+}
+
+enum Prober {
+    /// Connected unprivileged ICMP socket. The kernel picks the echo
+    /// identifier and only hands back replies that carry it.
+    Icmp { socket: UdpSocket, ipv6: bool },
+    Tcp(SocketAddr),
+}
+
+impl Prober {
+    fn open(address: SocketAddr, method: PingMethod) -> io::Result<Self> {
+        match method {
+            PingMethod::Tcp => Ok(Self::Tcp(address)),
+            PingMethod::Icmp => Self::icmp(address),
+            PingMethod::Auto => Ok(Self::icmp(address).unwrap_or(Self::Tcp(address))),
+        }
+    }
+
+    fn icmp(address: SocketAddr) -> io::Result<Self> {
+        let ipv6 = address.is_ipv6();
+        let (domain, protocol) = if ipv6 {
+            (Domain::IPV6, Protocol::ICMPV6)
+        } else {
+            (Domain::IPV4, Protocol::ICMPV4)
+        };
+        let socket = UdpSocket::from(Socket::new(domain, Type::DGRAM, Some(protocol))?);
+        let mut address = address;
+        address.set_port(0);
+        socket.connect(address)?;
+        Ok(Self::Icmp { socket, ipv6 })
+    }
+
+    fn method(&self) -> PingMethod {
+        match self {
+            Self::Icmp { .. } => PingMethod::Icmp,
+            Self::Tcp(_) => PingMethod::Tcp,
+        }
+    }
+
+    /// One probe. `None` when nothing came back within `timeout`.
+    fn round_trip(&self, sequence: u16, timeout: Duration) -> Option<Duration> {
+        match self {
+            Self::Icmp { socket, ipv6 } => {
+                let started = Instant::now();
+                socket.send(&echo_request(*ipv6, sequence)).ok()?;
+                let mut buffer = [0u8; 1500];
+                loop {
+                    let remaining = timeout
+                        .checked_sub(started.elapsed())
+                        .filter(|remaining| !remaining.is_zero())?;
+                    socket.set_read_timeout(Some(remaining)).ok()?;
+                    // Errors are timeouts or ICMP errors (unreachable):
+                    // either way, no reply.
+                    let length = socket.recv(&mut buffer).ok()?;
+                    if is_echo_reply(&buffer[..length], *ipv6, sequence) {
+                        return Some(started.elapsed());
+                    }
+                }
+            }
+            Self::Tcp(address) => {
+                let started = Instant::now();
+                match TcpStream::connect_timeout(address, timeout) {
+                    Ok(_) => Some(started.elapsed()),
+                    Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => Some(started.elapsed()),
+                    Err(_) => None,
+                }
+            }
+        }
+    }
+}
+
+/// An echo request with the identifier left to the kernel. ICMPv6
+/// checksums cover the addresses, so the kernel fills that one in too.
+fn echo_request(ipv6: bool, sequence: u16) -> Vec<u8> {
+    let mut packet = vec![
+        if ipv6 { ICMPV6_ECHO_REQUEST } else { ICMP_ECHO_REQUEST },
+        0,
+        0,
+        0,
+        0,
+        0,
+    ];
+    packet.extend_from_slice(&sequence.to_be_bytes());
+    packet.extend_from_slice(ECHO_PAYLOAD);
+    if !ipv6 {
+        let checksum = internet_checksum(&packet);
+        packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    }
+    packet
+}
+
+fn is_echo_reply(packet: &[u8], ipv6: bool, sequence: u16) -> bool {
+    let reply = if ipv6 { ICMPV6_ECHO_REPLY } else { ICMP_ECHO_REPLY };
+    packet.len() >= 8 && packet[0] == reply && packet[6..8] == sequence.to_be_bytes()
+}
+
+/// RFC 1071 one's-complement sum.
+fn internet_checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|pair| u32::from(u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)])))
+        .sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+enum Command {
+    Configure(PingMonitorConfig),
+    Stop,
+}
+
+/// Handle to the background ping monitor. Dropping it stops the thread,
+/// once the round in progress is over.
+pub struct PingMonitor {
+    tx: Sender<Command>,
+}
+
+impl PingMonitor {
+    pub fn spawn<R: Runtime>(app: AppHandle<R>, config: PingMonitorConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || run(app, rx, config));
+        Self { tx }
+    }
+
+    /// Changes the targets or the pace of a running monitor. Fails if the
+    /// thread is gone.
+    pub fn configure(&self, config: PingMonitorConfig) -> Result<()> {
+        self.tx.send(Command::Configure(config)).map_err(|_| {
+            NetworkError::OperationError("ping monitor is not running".to_string())
+        })
+    }
+}
+
+impl Drop for PingMonitor {
+    fn drop(&mut self) {
+        let _ = self.tx.send(Command::Stop);
+    }
+}
+
+fn run<R: Runtime>(app: AppHandle<R>, rx: Receiver<Command>, mut config: PingMonitorConfig) {
+    let mut deadline = Instant::now();
+    loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(Command::Configure(new_config)) => {
+                // The new targets are probed right away.
+                config = new_config;
+                deadline = Instant::now();
+                continue;
+            }
+            Ok(Command::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        let Some(state) = app.try_state::<NetworkManagerState<R>>() else {
+            break;
+        };
+        let started = Instant::now();
+        match state.ping(&config.probe) {
+            Ok(results) => {
+                let _ = app.emit("ping-result", &results);
+            }
+            Err(e) => log::debug!("ping round failed: {}", e),
+        }
+        deadline = started + round_interval(&config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn localhost(port: u16, method: PingMethod) -> (Vec<ResolvedTarget>, PingConfig) {
+        let targets = resolve_targets(
+            &[PingTarget::Host {
+                host: "127.0.0.1".to_string(),
+                port: Some(port),
+            }],
+            None,
+        );
+        let config = PingConfig {
+            targets: Vec::new(),
+            count: 3,
+            interval_ms: 200,
+            timeout_ms: 500,
+            method,
+        };
+        (targets, config)
+    }
+
+    #[test]
+    fn tcp_probe_reaches_localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (targets, config) = localhost(port, PingMethod::Tcp);
+
+        let results = ping(targets, &config);
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!((result.target.as_str(), result.address.as_str()), ("127.0.0.1", "127.0.0.1"));
+        assert_eq!(result.method, PingMethod::Tcp);
+        assert_eq!((result.sent, result.received), (3, 3));
+        assert_eq!(result.packet_loss, 0.0);
+        assert!(result.rtt_min_ms <= result.rtt_avg_ms && result.rtt_avg_ms <= result.rtt_max_ms);
+        assert!(result.jitter_ms.is_some());
+    }
+
+    #[test]
+    fn icmp_probe_reaches_localhost_where_allowed() {
+        // Auto measures with ICMP when it can and TCP otherwise, where a
+        // closed port on localhost still answers.
+        let (targets, config) = localhost(9, PingMethod::Auto);
+        assert_eq!(ping(targets, &config)[0].received, 3);
+
+        let (targets, config) = localhost(0, PingMethod::Icmp);
+        let result = ping(targets, &config).remove(0);
+        // Unprivileged ICMP is off unless net.ipv4.ping_group_range
+        // includes the user's group.
+        if result.error.is_some() {
+            assert_eq!(result.sent, 0);
+            return;
+        }
+        assert_eq!(result.method, PingMethod::Icmp);
+        assert_eq!(result.received, 3);
+    }
+
+    #[test]
+    fn connection_targets_need_a_connection() {
+        let results = resolve_targets(&[PingTarget::Gateway, PingTarget::Dns], None);
+        assert!(results.iter().all(|target| target.address.is_err()));
+
+        let ip = IpConfiguration {
+            interface: "eth0".to_string(),
+            ipv4: crate::models::IpFamilyConfiguration {
+                gateway: Some("192.168.1.1".to_string()),
+                nameservers: vec!["192.168.1.2".to_string()],
+                ..Default::default()
+            },
+            ipv6: Default::default(),
+            dhcp4: Default::default(),
+            dhcp6: Default::default(),
+        };
+        let results = resolve_targets(&[PingTarget::Gateway, PingTarget::Dns], Some(&ip));
+        assert_eq!(results[0].address, Ok("192.168.1.1:80".parse().unwrap()));
+        assert_eq!(results[1].address, Ok("192.168.1.2:53".parse().unwrap()));
+    }
+
+    #[test]
+    fn statistics_of_round_trips() {
+        let round_trips = [10, 14, 12].map(Duration::from_millis);
+        let result = summarize("x".to_string(), String::new(), PingMethod::Icmp, 4, &round_trips);
+        assert_eq!(result.received, 3);
+        assert_eq!(result.packet_loss, 25.0);
+        assert_eq!(result.rtt_min_ms, Some(10.0));
+        assert_eq!(result.rtt_avg_ms, Some(12.0));
+        assert_eq!(result.rtt_max_ms, Some(14.0));
+        assert_eq!(result.jitter_ms, Some(3.0));
+
+        let lost = summarize("x".to_string(), String::new(), PingMethod::Icmp, 2, &[]);
+        assert_eq!((lost.packet_loss, lost.rtt_avg_ms, lost.jitter_ms), (100.0, None, None));
+    }
+
+    #[test]
+    fn echo_requests_carry_a_valid_checksum() {
+        let packet = echo_request(false, 7);
+        assert_eq!(packet[0], ICMP_ECHO_REQUEST);
+        assert_eq!(internet_checksum(&packet), 0);
+        assert!(is_echo_reply(&[ICMP_ECHO_REPLY, 0, 0, 0, 0, 1, 0, 7], false, 7));
+        assert!(!is_echo_reply(&[ICMP_ECHO_REPLY, 0, 0, 0, 0, 1, 0, 8], false, 7));
+    }
+}
@@ -0,0 +1,172 @@
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::error::{NetworkError, Result};
+use crate::models::{NetworkInfo, WifiNetworkQuery, WifiScanConfig};
+use crate::NetworkManagerState;
+
+/// NetworkManager turns down a `RequestScan` that comes too soon after the
+/// last one. Asking anyway only fills the log with errors, so a device that
+/// scanned more recently than this is left alone and its list is reused.
+pub const MIN_SCAN_GAP: Duration = Duration::from_secs(10);
+
+/// How long a requested scan takes to show up in `AccessPoints`.
+const SCAN_SETTLE: Duration = Duration::from_secs(4);
+
+enum Command {
+    Configure(WifiScanConfig),
+    Stop,
+}
+
+/// Handle to the background scanning thread. Dropping it stops the thread.
+pub struct WifiScanner {
+    tx: Sender<Command>,
+}
+
+impl WifiScanner {
+    pub fn spawn<R: Runtime>(app: AppHandle<R>, config: WifiScanConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || run(app, rx, config));
+        Self { tx }
+    }
+
+    /// Changes the pace of a running scanner. Fails if the thread is gone.
+    pub fn configure(&self, config: WifiScanConfig) -> Result<()> {
+        self.tx
+            .send(Command::Configure(config))
+            .map_err(|_| NetworkError::OperationError("Wi-Fi scanner is not running".to_string()))
+    }
+}
+
+impl Drop for WifiScanner {
+    fn drop(&mut self) {
+        let _ = self.tx.send(Command::Stop);
+    }
+}
+
+/// Time between scans for a configuration, or `None` when scanning is paused.
+///
+/// The bounds keep a careless caller from hammering the radio: below
+/// [`MIN_SCAN_GAP`] NetworkManager would refuse most requests anyway.
+fn scan_interval(config: &WifiScanConfig) -> Option<Duration> {
+    if config.panel_open {
+        Some(Duration::from_millis(
+            config.active_interval_ms.clamp(MIN_SCAN_GAP.as_millis() as u64, 600_000),
+        ))
+    } else if config.idle_interval_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(config.idle_interval_ms.clamp(30_000, 3_600_000)))
+    }
+}
+
+fn run<R: Runtime>(app: AppHandle<R>, rx: Receiver<Command>, mut config: WifiScanConfig) {
+    let mut last_published: Option<Vec<NetworkInfo>> = None;
+    let mut scan_requested = false;
+    // First pass right away: whoever started the scanner wants a list now.
+    let mut deadline = Some(Instant::now());
+
+    loop {
+        let received = match deadline {
+            Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            // Paused: nothing to do until told otherwise.
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(Command::Configure(new_config)) => {
+                let panel_opened = new_config.panel_open && !config.panel_open;
+                config = new_config;
+                if panel_opened {
+                    // Opening the panel must not wait out the idle interval.
+                    deadline = Some(Instant::now());
+                    scan_requested = false;
+                } else if !scan_requested {
+                    deadline = scan_interval(&config).map(|interval| Instant::now() + interval);
+                }
+                continue;
+            }
+            Ok(Command::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        let Some(state) = app.try_state::<NetworkManagerState<R>>() else {
+            break;
+        };
+
+        if scan_requested {
+            publish(&app, &state, &mut last_published);
+            scan_requested = false;
+            deadline = scan_interval(&config)
+                .map(|interval| Instant::now() + interval.saturating_sub(SCAN_SETTLE));
+            continue;
+        }
+
+        match state.request_wifi_scan(MIN_SCAN_GAP) {
+            Ok(true) => {
+                scan_requested = true;
+                deadline = Some(Instant::now() + SCAN_SETTLE);
+            }
+            Ok(false) => {
+                // Somebody scanned recently; their results are as good as ours.
+                publish(&app, &state, &mut last_published);
+                deadline = scan_interval(&config).map(|interval| Instant::now() + interval);
+            }
+            Err(e) => {
+                log::debug!("background Wi-Fi scan skipped: {}", e);
+                deadline = scan_interval(&config).map(|interval| Instant::now() + interval);
+            }
+        }
+    }
+}
+
+/// Refreshes the shared cache and emits `wifi-networks-changed` when the
+/// list is different from the last one sent.
+fn publish<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &NetworkManagerState<R>,
+    last_published: &mut Option<Vec<NetworkInfo>>,
+) {
+    match state.list_wifi_networks(true, None, &WifiNetworkQuery::default()) {
+        Ok(networks) => {
+            if last_published.as_ref() != Some(&networks) {
+                let _ = app.emit("wifi-networks-changed", &networks);
+                *last_published = Some(networks);
+            }
+        }
+        Err(e) => log::debug!("background Wi-Fi list failed: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_panel_with_zero_idle_interval_pauses() {
+        let config = WifiScanConfig {
+            panel_open: false,
+            idle_interval_ms: 0,
+            ..WifiScanConfig::default()
+        };
+        assert_eq!(scan_interval(&config), None);
+    }
+
+    #[test]
+    fn intervals_are_kept_within_what_networkmanager_accepts() {
+        let fast = WifiScanConfig {
+            panel_open: true,
+            active_interval_ms: 100,
+            ..WifiScanConfig::default()
+        };
+        assert_eq!(scan_interval(&fast), Some(MIN_SCAN_GAP));
+
+        let slow = WifiScanConfig {
+            panel_open: false,
+            idle_interval_ms: 1_000,
+            ..WifiScanConfig::default()
+        };
+        assert_eq!(scan_interval(&slow), Some(Duration::from_secs(30)));
+    }
+}